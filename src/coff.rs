@@ -5,7 +5,7 @@ use std::mem;
 use std::result::Result;
 use std::convert::TryInto;
 
-use chrono::{DateTime, Utc, NaiveDateTime};
+use chrono::{DateTime, Utc};
 
 use crate::errors::*;
 
@@ -20,7 +20,8 @@ pub struct CoffHeader {
     f_flags: u16        /* flags */
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum CoffHeaderMachineType {
     Unknown,
     AM33,
@@ -184,8 +185,8 @@ impl CoffHeader {
     }
 
     pub fn get_timdat_as_dt(&self) -> DateTime<Utc> {
-        DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp(self.f_timdat as i64, 0), Utc)
+        DateTime::<Utc>::from_timestamp(self.f_timdat as i64, 0)
+            .unwrap_or_default()
     }
 
     pub fn set_timdat(&mut self, timdat: u32) {
@@ -197,10 +198,10 @@ impl CoffHeader {
         match timdat.timestamp().try_into() {
             Ok(t) => {
                 self.f_timdat = t;
-                return Ok(());
+                Ok(())
             },
-            Err(_e) => return Err(ButylError::ExcessiveDataError)
-        };
+            Err(_e) => Err(ButylError::ExcessiveDataError)
+        }
     }
 
     pub fn get_symptr(&self) -> u32 {
@@ -239,6 +240,7 @@ impl CoffHeader {
         self.f_flags & CoffHeaderCharacteristic::to_value(flag) != 0
     }
 
+    #[allow(clippy::field_reassign_with_default)]
     pub fn from_be_bytes(bytes: &[u8]) -> Result<CoffHeader, ButylError> {
         if bytes.len() < mem::size_of::<CoffHeader>() { /* bounds check */
             return Err(ButylError::InsufficientDataError);
@@ -269,6 +271,7 @@ impl CoffHeader {
         Ok(coff_header)
     }
 
+    #[allow(clippy::field_reassign_with_default)]
     pub fn from_le_bytes(bytes: &[u8]) -> Result<CoffHeader, ButylError> {
         if bytes.len() < mem::size_of::<CoffHeader>() { /* bounds check */
             return Err(ButylError::InsufficientDataError);
@@ -307,17 +310,17 @@ pub struct CoffFile<'a> {
 }
 
 impl<'a> CoffFile<'a> {
-    pub fn from_be_bytes(data: &[u8]) -> Result<CoffFile, ButylError> {
+    pub fn from_be_bytes(data: &[u8]) -> Result<CoffFile<'_>, ButylError> {
         Ok(CoffFile {
             header: CoffHeader::from_be_bytes(data)?,
-            data: data
+            data
         })
     }
 
-    pub fn from_le_bytes(data: &[u8]) -> Result<CoffFile, ButylError> {
+    pub fn from_le_bytes(data: &[u8]) -> Result<CoffFile<'_>, ButylError> {
         Ok(CoffFile {
             header: CoffHeader::from_le_bytes(data)?,
-            data: data
+            data
         })
     }
 }
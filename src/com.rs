@@ -0,0 +1,179 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::{DosHeader, DOS_HEADER_LEN, DOS_MAGIC};
+use crate::pe::stub::{DOS_PAGE_LEN, DOS_PARAGRAPH_LEN};
+use crate::utils::*;
+
+/* the program follows the 256-byte PSP in a single 64K segment */
+pub const COM_LOAD_OFFSET: usize = 0x100;
+pub const COM_SEGMENT_LEN: usize = 0x1_0000;
+
+/* DOS refuses anything that leaves no room for the stack */
+pub const COM_MAX_LEN: usize = 0xff00;
+
+/* DOS points SP at the top of the segment, with a zero word pushed so that
+ * a near return lands on the INT 20h at the start of the PSP */
+pub const COM_INITIAL_SP: u16 = 0xfffe;
+
+/* where the original, 28-byte MZ header ends */
+const DOS_RELOCATIONS_OFFSET: u16 = 0x1c;
+
+/* there is no header, so detection comes down to the instructions
+ * programs are commonly seen to open with */
+const COM_FIRST_OPCODES: &[(u8, &str)] = &[
+    (0xe9, "jmp near"), (0xeb, "jmp short"), (0xe8, "call near"),
+    (0xb4, "mov ah, imm8"), (0xb8, "mov ax, imm16"), (0xb9, "mov cx, imm16"),
+    (0xba, "mov dx, imm16"), (0xbb, "mov bx, imm16"),
+    (0xbc, "mov sp, imm16"), (0xbe, "mov si, imm16"),
+    (0xbf, "mov di, imm16"), (0x8c, "mov r/m16, sreg"),
+    (0x8e, "mov sreg, r/m16"), (0x0e, "push cs"), (0x1e, "push ds"),
+    (0x06, "push es"), (0xfc, "cld"), (0xfa, "cli"), (0xfb, "sti"),
+    (0xcd, "int")
+];
+
+const OPCODE_CALL_NEAR: u8 = 0xe8;
+const OPCODE_JMP_NEAR: u8 = 0xe9;
+const OPCODE_JMP_SHORT: u8 = 0xeb;
+
+pub fn get_opcode_name(opcode: u8) -> Option<&'static str> {
+    COM_FIRST_OPCODES.iter()
+        .find(|(t, _)| *t == opcode)
+        .map(|(_, name)| *name)
+}
+
+/* a leading jump or call also has to land inside the program; this alone
+ * matches plenty of other data, so it is only worth trusting alongside
+ * another sign such as the file's name */
+pub fn is_com(data: &[u8]) -> bool {
+    if data.is_empty() || data.len() > COM_MAX_LEN {
+        return false;
+    }
+
+    match read_le_u16(data, 0) {
+        Ok(DOS_MAGIC) => return false,
+        Ok(t) if t == DOS_MAGIC.swap_bytes() => return false,
+        _ => ()
+    }
+
+    let target: Option<usize> = match data[0] {
+        OPCODE_CALL_NEAR | OPCODE_JMP_NEAR => match read_le_u16(data, 1) {
+            Ok(t) => Some((COM_LOAD_OFFSET + 3 + t as usize) & 0xffff),
+            Err(_e) => return false
+        },
+        OPCODE_JMP_SHORT => match read_u8(data, 1) {
+            Ok(t) => Some((COM_LOAD_OFFSET as isize + 2 +
+                t as i8 as isize) as usize),
+            Err(_e) => return false
+        },
+        t => match get_opcode_name(t) {
+            Some(_) => None,
+            None => return false
+        }
+    };
+
+    match target {
+        Some(t) => t >= COM_LOAD_OFFSET && t < COM_LOAD_OFFSET + data.len(),
+        None => true
+    }
+}
+
+#[derive(Debug)]
+pub struct ComFile<'a> {
+    data: &'a[u8]
+}
+
+impl<'a> ComFile<'a> {
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn get_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /* both relative to the PSP segment, which CS, DS, ES and SS all hold */
+    pub fn get_entry_point(&self) -> u16 {
+        COM_LOAD_OFFSET as u16
+    }
+
+    pub fn get_initial_sp(&self) -> u16 {
+        COM_INITIAL_SP
+    }
+
+    pub fn get_first_opcode(&self) -> u8 {
+        self.data[0]
+    }
+
+    pub fn offset_to_address(&self, offset: usize) ->
+        Result<u16, ButylError> {
+        match offset {
+            t if t < self.data.len() => Ok((t + COM_LOAD_OFFSET) as u16),
+            _ => Err(ButylError::UnmappedAddressError)
+        }
+    }
+
+    pub fn address_to_offset(&self, address: u16) ->
+        Result<usize, ButylError> {
+        match (address as usize).checked_sub(COM_LOAD_OFFSET) {
+            Some(t) if t < self.data.len() => Ok(t),
+            _ => Err(ButylError::UnmappedAddressError)
+        }
+    }
+
+    /* the segment from CS:0000 as DOS leaves it, with a minimal PSP that
+     * exits through INT 20h and holds an empty command tail */
+    pub fn get_image(&self) -> Vec<u8> {
+        let mut image: Vec<u8> = vec![0; COM_LOAD_OFFSET];
+
+        image[0x00] = 0xcd;
+        image[0x01] = 0x20;
+        image[0x81] = 0x0d;
+        image.extend_from_slice(self.data);
+
+        image
+    }
+
+    /* the load module starts 10h paragraphs past the PSP, so a relative CS
+     * and SS of -10h put the code back at PSP:0100 with every segment
+     * register equal, as they would be for the .COM file; DOS does not push
+     * the zero word for an MZ file though, so the load module is padded out
+     * to the end of the segment to leave a zero at SS:FFFE for a bare RET */
+    pub fn to_mz(&self) -> Result<Vec<u8>, ButylError> {
+        if self.data.len() > COM_MAX_LEN {
+            return Err(ButylError::ExcessiveDataError);
+        }
+
+        let module_len: usize = COM_SEGMENT_LEN - COM_LOAD_OFFSET;
+        let len: usize = DOS_HEADER_LEN + module_len;
+        let psp_segment: u16 = (COM_LOAD_OFFSET / DOS_PARAGRAPH_LEN) as u16;
+        let mut header: DosHeader = DosHeader::default();
+
+        header.set_magic(DOS_MAGIC);
+        header.set_cblp((len % DOS_PAGE_LEN) as u16);
+        header.set_cp(len.div_ceil(DOS_PAGE_LEN) as u16);
+        header.set_cparhdr((DOS_HEADER_LEN / DOS_PARAGRAPH_LEN) as u16);
+        header.set_maxalloc(0xffff);
+        header.set_ss(psp_segment.wrapping_neg());
+        header.set_sp(COM_INITIAL_SP);
+        header.set_ip(COM_LOAD_OFFSET as u16);
+        header.set_cs(psp_segment.wrapping_neg());
+        header.set_lfarlc(DOS_RELOCATIONS_OFFSET);
+
+        let mut data: Vec<u8> = header.to_le_bytes();
+        data.extend_from_slice(self.data);
+        data.resize(len, 0);
+
+        Ok(data)
+    }
+
+    pub fn from_le_bytes(data: &[u8]) -> Result<ComFile<'_>, ButylError> {
+        match data.len() {
+            0 => Err(ButylError::InsufficientDataError),
+            t if t > COM_MAX_LEN => Err(ButylError::ExcessiveDataError),
+            _ => Ok(ComFile { data })
+        }
+    }
+}
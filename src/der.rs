@@ -0,0 +1,210 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+
+pub const DER_TAG_BOOLEAN: u8 = 0x01;
+pub const DER_TAG_INTEGER: u8 = 0x02;
+pub const DER_TAG_BIT_STRING: u8 = 0x03;
+pub const DER_TAG_OCTET_STRING: u8 = 0x04;
+pub const DER_TAG_NULL: u8 = 0x05;
+pub const DER_TAG_OID: u8 = 0x06;
+pub const DER_TAG_UTF8_STRING: u8 = 0x0c;
+pub const DER_TAG_PRINTABLE_STRING: u8 = 0x13;
+pub const DER_TAG_T61_STRING: u8 = 0x14;
+pub const DER_TAG_IA5_STRING: u8 = 0x16;
+pub const DER_TAG_UTC_TIME: u8 = 0x17;
+pub const DER_TAG_GENERALIZED_TIME: u8 = 0x18;
+pub const DER_TAG_BMP_STRING: u8 = 0x1e;
+pub const DER_TAG_SEQUENCE: u8 = 0x30;
+pub const DER_TAG_SET: u8 = 0x31;
+pub const DER_TAG_CONTEXT: u8 = 0x80;
+pub const DER_TAG_CONSTRUCTED: u8 = 0x20;
+
+/* a single tag-length-value triple; `contents` excludes the header while
+ * `raw` covers the whole encoding */
+#[derive(Debug, Clone, Copy)]
+pub struct DerObject<'a> {
+    tag: u8,
+    contents: &'a[u8],
+    raw: &'a[u8]
+}
+
+impl<'a> DerObject<'a> {
+    pub fn get_tag(&self) -> u8 {
+        self.tag
+    }
+
+    pub fn get_contents(&self) -> &'a[u8] {
+        self.contents
+    }
+
+    pub fn get_raw(&self) -> &'a[u8] {
+        self.raw
+    }
+
+    pub fn is_context(&self, number: u8) -> bool {
+        self.tag & 0xdf == DER_TAG_CONTEXT | number
+    }
+
+    pub fn expect(self, tag: u8) -> Result<DerObject<'a>, ButylError> {
+        if self.tag != tag {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        Ok(self)
+    }
+
+    pub fn get_children(&self) -> Result<Vec<DerObject<'a>>, ButylError> {
+        let mut children: Vec<DerObject<'a>> = Vec::new();
+        let mut rest: &'a[u8] = self.contents;
+
+        while !rest.is_empty() {
+            let (child, remainder) = DerObject::parse(rest)?;
+            children.push(child);
+            rest = remainder;
+        }
+
+        Ok(children)
+    }
+
+    pub fn get_child(&self, index: usize) -> Result<DerObject<'a>, ButylError> {
+        match self.get_children()?.get(index) {
+            Some(t) => Ok(*t),
+            None => Err(ButylError::MalformedDataError)
+        }
+    }
+
+    pub fn as_oid(&self) -> Result<String, ButylError> {
+        if self.tag != DER_TAG_OID || self.contents.is_empty() {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let mut arcs: Vec<u64> = Vec::new();
+        let mut value: u64 = 0;
+
+        for byte in self.contents.iter() {
+            value = value.checked_shl(7)
+                .ok_or(ButylError::MalformedDataError)? |
+                (byte & 0x7f) as u64;
+
+            if byte & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first: u64 = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+
+                value = 0;
+            }
+        }
+
+        Ok(arcs.iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<String>>()
+            .join("."))
+    }
+
+    pub fn as_string(&self) -> Result<String, ButylError> {
+        match self.tag {
+            DER_TAG_UTF8_STRING | DER_TAG_PRINTABLE_STRING |
+            DER_TAG_IA5_STRING | DER_TAG_UTC_TIME |
+            DER_TAG_GENERALIZED_TIME =>
+                Ok(String::from_utf8_lossy(self.contents).into_owned()),
+            /* T61 is close enough to Latin-1 for names */
+            DER_TAG_T61_STRING =>
+                Ok(self.contents.iter().map(|t| *t as char).collect()),
+            DER_TAG_BMP_STRING => {
+                let units: Vec<u16> = self.contents.chunks_exact(2)
+                    .map(|t| ((t[0] as u16) << 8) | t[1] as u16)
+                    .collect();
+                Ok(String::from_utf16_lossy(&units))
+            },
+            _ => Err(ButylError::MalformedDataError)
+        }
+    }
+
+    pub fn parse(bytes: &'a[u8]) ->
+        Result<(DerObject<'a>, &'a[u8]), ButylError> {
+        if bytes.len() < 2 {
+            return Err(ButylError::InsufficientDataError);
+        }
+
+        let tag: u8 = bytes[0];
+
+        /* high tag numbers never appear in the structures we decode */
+        if tag & 0x1f == 0x1f {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        let (length, header_len): (usize, usize) = match bytes[1] {
+            t if t & 0x80 == 0 => (t as usize, 2),
+            0x80 => return Err(ButylError::UnsupportedFeatureError),
+            t => {
+                let count: usize = (t & 0x7f) as usize;
+
+                if count > 4 || bytes.len() < 2 + count {
+                    return Err(ButylError::MalformedDataError);
+                }
+
+                let length: usize = bytes[2..2 + count].iter()
+                    .fold(0, |acc, b| (acc << 8) | *b as usize);
+
+                (length, 2 + count)
+            }
+        };
+
+        let end: usize = header_len.checked_add(length)
+            .ok_or(ButylError::MalformedDataError)?;
+
+        if end > bytes.len() {
+            return Err(ButylError::InsufficientDataError);
+        }
+
+        Ok((DerObject {
+            tag,
+            contents: &bytes[header_len..end],
+            raw: &bytes[..end]
+        }, &bytes[end..]))
+    }
+}
+
+pub fn get_oid_name(oid: &str) -> Option<&'static str> {
+    match oid {
+        "2.5.4.3" => Some("CN"),
+        "2.5.4.5" => Some("serialNumber"),
+        "2.5.4.6" => Some("C"),
+        "2.5.4.7" => Some("L"),
+        "2.5.4.8" => Some("ST"),
+        "2.5.4.9" => Some("street"),
+        "2.5.4.10" => Some("O"),
+        "2.5.4.11" => Some("OU"),
+        "2.5.4.15" => Some("businessCategory"),
+        "1.2.840.113549.1.9.1" => Some("emailAddress"),
+        "1.3.6.1.4.1.311.60.2.1.2" => Some("jurisdictionST"),
+        "1.3.6.1.4.1.311.60.2.1.3" => Some("jurisdictionC"),
+        _ => None
+    }
+}
+
+/* renders an X.501 Name as a comma-separated list of `attr=value` pairs */
+pub fn format_name(name: &DerObject) -> Result<String, ButylError> {
+    let mut parts: Vec<String> = Vec::new();
+
+    for rdn in name.get_children()? {
+        for attribute in rdn.get_children()? {
+            let oid: String = attribute.get_child(0)?.as_oid()?;
+            let value: String = attribute.get_child(1)?.as_string()
+                .unwrap_or_else(|_e| String::from("?"));
+
+            parts.push(format!("{}={}",
+                get_oid_name(&oid).map(String::from).unwrap_or(oid), value));
+        }
+    }
+
+    Ok(parts.join(", "))
+}
+
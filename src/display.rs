@@ -0,0 +1,1494 @@
+use serde::Serialize;
+
+use crate::errors::*;
+use crate::formats;
+use crate::pe;
+use crate::elf::{self, ElfHeader, SectionType};
+use crate::elf::symbols::Symbol;
+use crate::elf::dynamic::{DynamicEntry, DynamicTag};
+use crate::elf::relocs::Relocation;
+use crate::elf::notes::{Note, GnuProperty};
+use crate::elf::versions::{self, SymbolVersion, VersionDefinition,
+    VersionNeed};
+use crate::pe::imports::Import;
+use crate::pe::resources::{ResourceId, ResourceDirectory};
+use crate::pe::version::VersionInfo;
+use crate::pe::manifest::{self, Manifest};
+use crate::pe::authenticode::{self, WinCertificate, DigestCheck};
+use crate::pe::debug::{DebugDirectory, DebugInfo};
+use crate::pe::tls::TlsDirectory;
+use crate::pe::loadconfig::{LoadConfigDirectory, HardeningSummary};
+use crate::pe::exceptions::Unwind;
+use crate::pe::clr::{CorHeader, Metadata, MethodDef, TypeDef};
+use crate::pe::rich::RichHeader;
+use crate::ne::{self, NeHeader};
+use crate::ne::names::Name;
+use crate::ne::relocs::{Relocation as NeRelocation, RelocationTarget};
+use crate::le::{self, LeHeader};
+use crate::le::fixups::{Fixup, FixupTarget};
+use crate::le::entries::{Entry, EntryTarget};
+use crate::com;
+use crate::macho::{self, MachOHeader};
+use crate::macho::commands::{self, LoadCommandInfo, Segment, DylibCommand,
+    BuildVersion, BuildTool};
+use crate::macho::fat::FatArch;
+
+pub fn show_field(file: &formats::File, field: &str, json: bool) ->
+    Result<(), ButylError> {
+    match (file, field, json) {
+        (formats::File::PE(pe), "imports", false) => show_imports(pe),
+        (formats::File::PE(pe), "bound", false) => show_bound_imports(pe),
+        (formats::File::PE(pe), "iat", false) => show_iat(pe),
+        (formats::File::PE(pe), "relocations", false) => show_relocations(pe),
+        (formats::File::PE(pe), "version", false) => show_version(pe),
+        (formats::File::PE(pe), "version", true) =>
+            show_json(&pe.get_version_info()?),
+        (formats::File::PE(pe), "manifest", false) => show_manifest(pe),
+        (formats::File::PE(pe), "manifest", true) =>
+            show_json(&pe.get_manifest()?),
+        (formats::File::PE(pe), "signature", false) => show_signature(pe),
+        (formats::File::PE(pe), "verify", false) => show_verification(pe),
+        (formats::File::PE(pe), "debug", false) => show_debug(pe),
+        (formats::File::PE(pe), "tls", false) => show_tls(pe),
+        (formats::File::PE(pe), "loadconfig", false) => show_load_config(pe),
+        (formats::File::PE(pe), "hardening", false) => show_hardening(pe),
+        (formats::File::PE(pe), "exceptions", false) => show_exceptions(pe),
+        (formats::File::PE(pe), "functions", false) => show_functions(pe),
+        (formats::File::PE(pe), "clr", false) => show_clr(pe),
+        (formats::File::PE(pe), "rich", false) => show_rich(pe),
+        (formats::File::ELF(elf), "header", false) => show_elf_header(elf),
+        (formats::File::ELF(elf), "segments", false) => show_segments(elf),
+        (formats::File::ELF(elf), "sections", false) => show_sections(elf),
+        (formats::File::ELF(elf), "symbols", false) => show_symbols(elf),
+        (formats::File::ELF(elf), "dynamic", false) => show_dynamic(elf),
+        (formats::File::ELF(elf), "relocations", false) =>
+            show_elf_relocations(elf),
+        (formats::File::ELF(elf), "notes", false) => show_notes(elf),
+        (formats::File::ELF(elf), "buildid", false) => show_build_id(elf),
+        (formats::File::ELF(elf), "versions", false) => show_versions(elf),
+        (formats::File::NE(ne), "header", false) => show_ne_header(ne),
+        (formats::File::NE(ne), "segments", false) => show_ne_segments(ne),
+        (formats::File::NE(ne), "names", false) => show_ne_names(ne),
+        (formats::File::NE(ne), "modules", false) =>
+            show_modules(&ne.get_module_references()?),
+        (formats::File::NE(ne), "relocations", false) =>
+            show_ne_relocations(ne),
+        (formats::File::LE(le), field, false) |
+        (formats::File::LX(le), field, false) => show_le_field(le, field),
+        (formats::File::MachO(macho), "header", false) =>
+            show_macho_header(macho),
+        (formats::File::MachO(macho), "commands", false) =>
+            show_load_commands(macho),
+        (formats::File::MachO(macho), "segments", false) =>
+            show_macho_segments(macho),
+        (formats::File::MachO(macho), "libraries", false) =>
+            show_libraries(macho),
+        (formats::File::MachO(macho), "uuid", false) => show_uuid(macho),
+        (formats::File::MachO(macho), "build", false) =>
+            show_build_version(macho),
+        (formats::File::FAT(fat), "arches", false) => show_arches(fat),
+        (formats::File::FAT(fat), field, json) =>
+            match fat.get_arches().first() {
+                Some(t) => show_field(&formats::File::MachO(fat.get_macho(t)?),
+                    field, json),
+                None => Err(ButylError::NotFoundError)
+            },
+        (formats::File::COM(com), "header", false) => show_com_header(com),
+        (_, _, true) => Err(ButylError::UnsupportedFeatureError),
+        _ => Err(ButylError::UnknownFieldError)
+    }
+}
+
+fn show_json<T: Serialize>(value: &T) -> Result<(), ButylError> {
+    match serde_json::to_string_pretty(value) {
+        Ok(t) => {
+            println!("{}", t);
+            Ok(())
+        },
+        Err(_e) => Err(ButylError::MalformedDataError)
+    }
+}
+
+fn show_imports(pe: &pe::PeFile) -> Result<(), ButylError> {
+    for import in pe.get_imports()? {
+        let symbol: String = match (import.get_name(), import.get_ordinal()) {
+            (Some(name), _) => format!("{} (hint {})", name, import.get_hint()),
+            (None, Some(ordinal)) => format!("#{}", ordinal),
+            (None, None) => String::from("?")
+        };
+
+        println!("{:#010x}  {}!{}{}", import.get_thunk(), import.get_module(),
+            symbol, if import.is_delayed() { "  [delayed]" } else { "" });
+    }
+
+    Ok(())
+}
+
+fn show_bound_imports(pe: &pe::PeFile) -> Result<(), ButylError> {
+    for descriptor in pe.get_bound_import_descriptors()? {
+        println!("{}  timestamp {:#010x}", descriptor.get_module(),
+            descriptor.get_time_date_stamp());
+
+        for forwarder in descriptor.get_forwarder_refs() {
+            println!("    forwarder {}  timestamp {:#010x}",
+                forwarder.get_module(), forwarder.get_time_date_stamp());
+        }
+    }
+
+    for binding in pe.get_import_bindings()? {
+        let format_stamp = |t: Option<u32>| t.map(|t| format!("{:#010x}", t))
+            .unwrap_or_else(|| String::from("-"));
+
+        println!("{}: {:?}{} (descriptor {}, bound {})", binding.get_module(),
+            binding.get_status(),
+            if binding.get_status().is_stale() { " [stale]" } else { "" },
+            format_stamp(binding.get_descriptor_time_date_stamp()),
+            format_stamp(binding.get_bound_time_date_stamp()));
+    }
+
+    Ok(())
+}
+
+fn show_iat(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let imports: Vec<Import> = pe.get_imports()?;
+
+    for entry in pe.get_import_address_table()? {
+        let symbol: String = match imports.iter()
+            .find(|t| t.get_thunk() == entry.get_rva() && !t.is_delayed()) {
+            Some(t) => format!("{}!{}", t.get_module(), t.get_name()
+                .map(String::from)
+                .or_else(|| t.get_ordinal().map(|t| format!("#{}", t)))
+                .unwrap_or_default()),
+            None => String::new()
+        };
+
+        println!("{:#010x}  {:#018x}  {}", entry.get_rva(), entry.get_value(),
+            symbol);
+    }
+
+    Ok(())
+}
+
+fn show_relocations(pe: &pe::PeFile) -> Result<(), ButylError> {
+    for block in pe.get_base_relocations()? {
+        println!("page {:#010x} ({} entries)", block.get_page_rva(),
+            block.get_entries().len());
+
+        for entry in block.get_entries() {
+            println!("    {:#010x}  {}",
+                block.get_page_rva().wrapping_add(entry.get_offset() as u32),
+                entry.get_kind().get_name());
+        }
+    }
+
+    Ok(())
+}
+
+fn show_version(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let version_info: VersionInfo = match pe.get_version_info()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    if let Some(fixed) = version_info.get_fixed_file_info() {
+        println!("FileVersion (fixed): {}", fixed.get_file_version_as_string());
+        println!("ProductVersion (fixed): {}",
+            fixed.get_product_version_as_string());
+        println!("FileFlags: {:#010x}", fixed.get_file_flags());
+        println!("FileOS: {:#010x}", fixed.get_file_os());
+        println!("FileType: {:#010x}", fixed.get_file_type());
+    }
+
+    for table in version_info.get_string_tables() {
+        println!("[{}]", table.get_key());
+
+        for (key, value) in table.get_strings() {
+            println!("    {}: {}", key, value);
+        }
+    }
+
+    for (language, code_page) in version_info.get_translations() {
+        println!("Translation: {:#06x} {}", language, code_page);
+    }
+
+    Ok(())
+}
+
+fn show_manifest(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let manifest: Manifest = match pe.get_manifest()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    if let Some(identity) = manifest.get_assembly_identity() {
+        println!("Assembly: {} {}", identity.get_name().unwrap_or("?"),
+            identity.get_version().unwrap_or("?"));
+    }
+
+    println!("requestedExecutionLevel: {}",
+        manifest.get_requested_execution_level().unwrap_or("(none)"));
+
+    if let Some(ui_access) = manifest.get_ui_access() {
+        println!("uiAccess: {}", ui_access);
+    }
+
+    if let Some(dpi_aware) = manifest.get_dpi_aware() {
+        println!("dpiAware: {}", dpi_aware);
+    }
+
+    if let Some(dpi_awareness) = manifest.get_dpi_awareness() {
+        println!("dpiAwareness: {}", dpi_awareness);
+    }
+
+    for id in manifest.get_supported_os() {
+        println!("supportedOS: {} ({})", id,
+            manifest::get_supported_os_name(id).unwrap_or("unknown"));
+    }
+
+    for dependency in manifest.get_dependent_assemblies() {
+        println!("dependency: {} {} ({})", dependency.get_name().unwrap_or("?"),
+            dependency.get_version().unwrap_or("?"),
+            dependency.get_processor_architecture().unwrap_or("?"));
+    }
+
+    Ok(())
+}
+
+fn show_signature(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let certificates: Vec<WinCertificate> = pe.get_certificates()?;
+
+    if certificates.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for certificate in certificates {
+        println!("WIN_CERTIFICATE: revision {:#06x}, type {:?}, {} bytes",
+            certificate.get_revision(),
+            certificate.get_certificate_type_as_enum(),
+            certificate.get_length());
+
+        let signed_data = match certificate.get_signed_data() {
+            Ok(t) => t,
+            Err(_e) => continue
+        };
+
+        println!("    Digest: {} {}",
+            signed_data.get_digest_algorithm().get_name(),
+            authenticode::format_hex(signed_data.get_digest()));
+
+        for signer in signed_data.get_signers() {
+            println!("    Signer: {} (serial {})", signer.get_issuer(),
+                signer.get_serial_number_as_string());
+            println!("        Digest algorithm: {}",
+                signer.get_digest_algorithm().get_name());
+
+            if let Some(digest) = signer.get_message_digest() {
+                println!("        Message digest: {}",
+                    authenticode::format_hex(digest));
+            }
+        }
+
+        for cert in signed_data.get_certificates() {
+            println!("    Certificate: {}", cert.get_subject());
+            println!("        Issuer: {}", cert.get_issuer());
+            println!("        Serial: {}", cert.get_serial_number_as_string());
+            println!("        Validity: {} - {}", cert.get_not_before(),
+                cert.get_not_after());
+        }
+    }
+
+    Ok(())
+}
+
+fn show_verification(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let checks: Vec<DigestCheck> = pe.check_signature_digests()?;
+
+    if checks.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for check in checks {
+        println!("{}: {} (signed {}, computed {})",
+            check.get_algorithm().get_name(),
+            if check.is_match() { "unmodified" } else { "MODIFIED" },
+            authenticode::format_hex(check.get_expected()),
+            authenticode::format_hex(check.get_actual()));
+    }
+
+    Ok(())
+}
+
+fn show_debug(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let entries: Vec<DebugDirectory> = pe.get_debug_directories()?;
+
+    if entries.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for entry in entries {
+        println!("{:?}: timestamp {:#010x}, version {}.{}, {} bytes at \
+            RVA {:#010x} (offset {:#010x})", entry.get_debug_type_as_enum(),
+            entry.get_time_date_stamp(), entry.get_major_version(),
+            entry.get_minor_version(), entry.get_size_of_data(),
+            entry.get_address_of_raw_data(), entry.get_pointer_to_raw_data());
+
+        let info: DebugInfo = match pe.get_debug_info(&entry) {
+            Ok(Some(t)) => t,
+            _ => continue
+        };
+
+        match info {
+            DebugInfo::CodeView(t) => {
+                println!("    PDB: {}", t.get_path());
+                println!("    GUID: {}, age {}", t.get_guid_as_string(),
+                    t.get_age());
+                println!("    Symbol server: {}", t.get_symbol_server_path());
+            },
+            DebugInfo::Pogo(t) => {
+                for pogo in t.get_entries() {
+                    println!("    {:#010x}  {:#010x}  {}", pogo.get_rva(),
+                        pogo.get_size(), pogo.get_name());
+                }
+            },
+            DebugInfo::Repro(t) => println!("    Hash: {}",
+                authenticode::format_hex(&t)),
+            DebugInfo::VcFeature(t) => println!("    Pre-VC++ 11.00: {}, \
+                C/C++: {}, /GS: {}, /sdl: {}, guardN: {}", t.get_pre_vc11(),
+                t.get_c_cpp(), t.get_gs(), t.get_sdl(), t.get_guard_n())
+        }
+    }
+
+    Ok(())
+}
+
+fn show_rich(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let rich_header: RichHeader = match pe.get_rich_header()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    println!("Offset: {:#x} ({} bytes)", rich_header.get_offset(),
+        rich_header.get_len());
+    println!("Key: {:#010x}", rich_header.get_key());
+    println!("Checksum: {:#010x} ({})", rich_header.get_check_sum(),
+        if rich_header.is_valid() { "valid" } else { "invalid" });
+
+    for entry in rich_header.get_entries() {
+        println!("    {:#06x} {:<20} build {:<6} count {:<6} {}",
+            entry.get_product_id(),
+            entry.get_product_name().unwrap_or("?"), entry.get_build(),
+            entry.get_count(), entry.get_toolchain().unwrap_or(""));
+    }
+
+    Ok(())
+}
+
+fn show_tls(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let tls_directory: TlsDirectory = match pe.get_tls_directory()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    println!("Raw data: {:#x} - {:#x}",
+        tls_directory.get_start_address_of_raw_data(),
+        tls_directory.get_end_address_of_raw_data());
+    println!("Index: {:#x}", tls_directory.get_address_of_index());
+    println!("Callbacks: {:#x}", tls_directory.get_address_of_callbacks());
+    println!("Zero fill: {} bytes", tls_directory.get_size_of_zero_fill());
+    println!("Characteristics: {:#010x}", tls_directory.get_characteristics());
+
+    for callback in pe.get_tls_callbacks()? {
+        println!("    VA {:#x}  RVA {}  offset {}", callback.get_va(),
+            callback.get_rva().map(|t| format!("{:#010x}", t))
+                .unwrap_or_else(|| String::from("-")),
+            callback.get_offset().map(|t| format!("{:#010x}", t))
+                .unwrap_or_else(|| String::from("-")));
+    }
+
+    Ok(())
+}
+
+fn show_load_config(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let load_config: LoadConfigDirectory = match pe.get_load_config()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    println!("Size: {} bytes", load_config.get_size());
+    println!("Security cookie: {:#x}", load_config.get_security_cookie());
+    println!("SafeSEH handlers: {} at {:#x}",
+        load_config.get_se_handler_count(),
+        load_config.get_se_handler_table());
+    println!("Guard flags: {:#010x}", load_config.get_guard_flags());
+    println!("CFG check function: {:#x}",
+        load_config.get_guard_cf_check_function_pointer());
+    println!("CFG dispatch function: {:#x}",
+        load_config.get_guard_cf_dispatch_function_pointer());
+    println!("CFG functions: {} at {:#x}",
+        load_config.get_guard_cf_function_count(),
+        load_config.get_guard_cf_function_table());
+    println!("CHPE metadata: {:#x}", load_config.get_chpe_metadata_pointer());
+    println!("Dynamic value relocations: {:#x} (section {}, offset {:#x})",
+        load_config.get_dynamic_value_reloc_table(),
+        load_config.get_dynamic_value_reloc_table_section(),
+        load_config.get_dynamic_value_reloc_table_offset());
+
+    for handler in pe.get_safe_seh_handlers()? {
+        println!("    SEH handler {:#010x}", handler);
+    }
+
+    for function in pe.get_guard_cf_functions()? {
+        println!("    CFG target {:#010x} (flags {:#04x})", function.get_rva(),
+            function.get_flags());
+    }
+
+    Ok(())
+}
+
+fn show_hardening(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let summary: HardeningSummary = pe.get_hardening_summary()?;
+
+    println!("ASLR: {}", summary.is_dynamic_base());
+    println!("High entropy VA: {}", summary.is_high_entropy_va());
+    println!("DEP: {}", summary.is_nx_compat());
+    println!("Force integrity: {}", summary.is_force_integrity());
+    println!("No SEH: {}", summary.is_no_seh());
+
+    if let Some(safe_seh) = summary.is_safe_seh() {
+        println!("SafeSEH: {}", safe_seh);
+    }
+
+    println!("/GS: {}", summary.has_security_cookie());
+    println!("CFG: {}", summary.is_control_flow_guard());
+    println!("RFG: {}", summary.is_return_flow_guard());
+    println!("EH continuation: {}", summary.is_eh_continuation());
+    println!("XFG: {}", summary.is_xfg());
+    println!("CET compatible: {}", summary.is_cet_compat());
+
+    Ok(())
+}
+
+fn show_exceptions(pe: &pe::PeFile) -> Result<(), ButylError> {
+    for function in pe.get_runtime_functions()? {
+        println!("{:#010x} - {:#010x}  unwind {:#010x}",
+            function.get_begin_address(), function.get_end_address(),
+            function.get_unwind_data());
+
+        match pe.get_unwind(&function)? {
+            Unwind::Amd64(t) => {
+                println!("    version {}, flags {:#x}, prolog {} bytes",
+                    t.get_version(), t.get_flags(), t.get_size_of_prolog());
+
+                for code in t.get_codes() {
+                    println!("    {:#04x}: {}", code.get_code_offset(),
+                        code.get_description());
+                }
+
+                if let Some(handler) = t.get_handler() {
+                    println!("    handler {:#010x}", handler);
+                }
+
+                if let Some(chained) = t.get_chained() {
+                    println!("    chained to {:#010x}",
+                        chained.get_begin_address());
+                }
+            },
+            Unwind::Arm64Packed(t) => println!("    packed: flag {}, RegF {}, \
+                RegI {}, H {}, CR {}, frame {} bytes", t.get_flag(),
+                t.get_reg_f(), t.get_reg_i(), t.get_h() as u8, t.get_cr(),
+                t.get_frame_size()),
+            Unwind::Arm64(t) => {
+                for scope in t.get_epilog_scopes() {
+                    println!("    epilog at +{:#x}, code {}",
+                        scope.get_start_offset(), scope.get_start_index());
+                }
+
+                for code in t.get_codes() {
+                    println!("    {:>3}: {}", code.get_index(),
+                        code.get_text());
+                }
+
+                if let Some(handler) = t.get_handler() {
+                    println!("    handler {:#010x}", handler);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn show_functions(pe: &pe::PeFile) -> Result<(), ButylError> {
+    for (begin, end) in pe.get_function_boundaries()? {
+        println!("{:#010x} - {:#010x}", begin, end);
+    }
+
+    Ok(())
+}
+
+fn show_clr(pe: &pe::PeFile) -> Result<(), ButylError> {
+    let cor_header: CorHeader = match pe.get_cor_header()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    println!("Runtime: {}.{}", cor_header.get_major_runtime_version(),
+        cor_header.get_minor_runtime_version());
+    println!("Flags: {:#010x}", cor_header.get_flags());
+    println!("Entry point: {:#010x}", cor_header.get_entry_point());
+
+    let metadata: Metadata = match pe.get_metadata()? {
+        Some(t) => t,
+        None => return Ok(())
+    };
+
+    println!("Metadata version: {}", metadata.get_version());
+
+    for stream in metadata.get_streams() {
+        println!("    {}  {:#010x}  {} bytes", stream.get_name(),
+            stream.get_offset(), stream.get_size());
+    }
+
+    if let Some(assembly) = metadata.get_assembly()? {
+        println!("Assembly: {} {}", assembly.get_name(),
+            assembly.get_version_as_string());
+    }
+
+    for reference in metadata.get_assembly_refs()? {
+        println!("AssemblyRef: {} {}", reference.get_name(),
+            reference.get_version_as_string());
+    }
+
+    let methods: Vec<MethodDef> = metadata.get_method_defs()?;
+    let types: Vec<TypeDef> = metadata.get_type_defs()?;
+
+    for (index, type_def) in types.iter().enumerate() {
+        println!("TypeDef: {} (flags {:#010x})", type_def.get_full_name(),
+            type_def.get_flags());
+
+        /* a type owns the methods up to the next type's method list */
+        let first: usize = type_def.get_method_list() as usize;
+        let last: usize = types.get(index + 1)
+            .map(|t| t.get_method_list() as usize)
+            .unwrap_or(methods.len() + 1);
+
+        for method in methods.iter()
+            .take(last.saturating_sub(1))
+            .skip(first.saturating_sub(1)) {
+            println!("    {}  RVA {:#010x}", method.get_name(),
+                method.get_rva());
+        }
+    }
+
+    for member in metadata.get_member_refs()? {
+        let class: String = match member.get_class() {
+            Some(t) => metadata.get_type_name(&t)?,
+            None => String::from("?")
+        };
+
+        println!("MemberRef: {}::{}", class, member.get_name());
+    }
+
+    for string in metadata.get_user_strings()? {
+        println!("UserString: {:?}", string);
+    }
+
+    Ok(())
+}
+
+pub fn show_resources(file: &formats::File) -> Result<(), ButylError> {
+    let resources: ResourceDirectory = match file {
+        formats::File::PE(pe) => match pe.get_resources()? {
+            Some(t) => t,
+            None => return Ok(())
+        },
+        formats::File::NE(ne) => return show_ne_resources(ne),
+        _ => return Err(ButylError::UnsupportedFeatureError)
+    };
+
+    for resource in resources.get_resources() {
+        let type_name: String = match resource.get_type().get_type() {
+            Some(t) => format!("{} ({})", resource.get_type(), t.get_name()),
+            None => resource.get_type().to_string()
+        };
+
+        println!("{}/{}/{}  {:#010x}  {} bytes", type_name,
+            resource.get_name(), resource.get_language(),
+            resource.get_data_entry().get_offset_to_data(),
+            resource.get_data_entry().get_size());
+    }
+
+    Ok(())
+}
+
+/* looks up a `type/name/lang` specifier, where the language may be omitted
+ * to select the first available one */
+pub fn get_resource_data(file: &formats::File, spec: &str) ->
+    Result<Vec<u8>, ButylError> {
+    let resources: ResourceDirectory = match file {
+        formats::File::PE(pe) => match pe.get_resources()? {
+            Some(t) => t,
+            None => return Err(ButylError::NotFoundError)
+        },
+        formats::File::NE(ne) => return get_ne_resource_data(ne, spec),
+        _ => return Err(ButylError::UnsupportedFeatureError)
+    };
+
+    let parts: Vec<&str> = spec.split('/').collect();
+
+    if parts.len() < 2 || parts.len() > 3 {
+        return Err(ButylError::MalformedDataError);
+    }
+
+    let resource_type: ResourceId = ResourceId::from_string(parts[0], true);
+    let name: ResourceId = ResourceId::from_string(parts[1], false);
+    let language: Option<ResourceId> = parts.get(2)
+        .map(|t| ResourceId::from_string(t, false));
+
+    match resources.find(&resource_type, &name, language.as_ref()) {
+        Some(t) => Ok(t.get_data().to_vec()),
+        None => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_ne_resources(ne: &ne::NeFile) -> Result<(), ButylError> {
+    for resource in ne.get_resources()? {
+        let type_name: String = match resource.get_type().get_type() {
+            Some(t) => format!("{} ({})", resource.get_type(), t.get_name()),
+            None => resource.get_type().to_string()
+        };
+
+        println!("{}/{}  {:#010x}  {} bytes", type_name, resource.get_name(),
+            resource.get_offset(), resource.get_data().len());
+    }
+
+    Ok(())
+}
+
+/* NE resources have no language, so the specifier is just `type/name` */
+fn get_ne_resource_data(ne: &ne::NeFile, spec: &str) ->
+    Result<Vec<u8>, ButylError> {
+    let parts: Vec<&str> = spec.split('/').collect();
+
+    if parts.len() != 2 {
+        return Err(ButylError::MalformedDataError);
+    }
+
+    match ne.find_resource(&ResourceId::from_string(parts[0], true),
+        &ResourceId::from_string(parts[1], false))? {
+        Some(t) => Ok(t.get_data().to_vec()),
+        None => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_elf_header(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let header: &ElfHeader = elf.get_header();
+
+    println!("Class: {}", if header.get_encoding().is_64_bit() {
+        "ELF64"
+    } else {
+        "ELF32"
+    });
+    println!("Data: {}", if header.get_encoding().is_big_endian() {
+        "2's complement, big endian"
+    } else {
+        "2's complement, little endian"
+    });
+    println!("OS/ABI: {}", header.get_os_abi_name());
+    println!("ABI version: {}", header.get_abi_version());
+    println!("Type: {}", header.get_type_as_enum().get_name());
+    println!("Machine: {}", header.get_machine_as_enum().get_name());
+    println!("Version: {:#x}", header.get_version());
+    println!("Entry point: {:#x}", header.get_entry());
+    println!("Program headers: {} at offset {}",
+        elf.get_program_headers().len(), header.get_phoff());
+    println!("Section headers: {} at offset {}",
+        elf.get_section_headers().len(), header.get_shoff());
+    println!("Flags: {:#x}", header.get_flags());
+    println!("Section name table: {}", elf.get_shstrndx());
+
+    Ok(())
+}
+
+fn show_segments(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let width: usize = elf.get_encoding().get_address_len() * 2 + 2;
+
+    for segment in elf.get_program_headers() {
+        println!("{:<14} {:#010x} {:#0w$x} {:#0w$x} {:#010x} {:#010x} {} {:#x}",
+            segment.get_type_as_enum().get_name(), segment.get_offset(),
+            segment.get_vaddr(), segment.get_paddr(), segment.get_filesz(),
+            segment.get_memsz(), segment.get_flags_as_string(),
+            segment.get_align(), w = width);
+    }
+
+    Ok(())
+}
+
+fn show_sections(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let width: usize = elf.get_encoding().get_address_len() * 2 + 2;
+
+    for (i, section) in elf.get_section_headers().iter().enumerate() {
+        println!("[{:>2}] {:<18} {:<14} {:#0w$x} {:#08x} {:#08x} {:02x} \
+            {:>3} {:>2} {:>3} {:>2}",
+            i, section.get_name(), section.get_type_as_enum().get_name(),
+            section.get_addr(), section.get_offset(), section.get_size(),
+            section.get_entsize(), section.get_flags_as_string(),
+            section.get_link(), section.get_info(), section.get_addralign(),
+            w = width);
+    }
+
+    Ok(())
+}
+
+/* versions only apply to the dynamic symbol table */
+fn get_symbol_versions(elf: &elf::ElfFile, index: usize) ->
+    Result<Vec<Option<SymbolVersion>>, ButylError> {
+    match elf.get_section(index) {
+        Ok(t) if t.get_type_as_enum() == SectionType::Dynsym =>
+            elf.get_symbol_versions(),
+        _ => Ok(Vec::new())
+    }
+}
+
+/* readelf's "name@@VERSION", with the index of needed versions if asked;
+ * the symbols naming a version definition are left as they are */
+fn get_versioned_name(name: String, version: Option<&Option<SymbolVersion>>,
+    with_index: bool) -> String {
+    match version {
+        Some(Some(t)) if t.get_name() != name => match t.is_needed() {
+            true if with_index => format!("{}{} ({})", name, t.get_suffix(),
+                t.get_index()),
+            _ => format!("{}{}", name, t.get_suffix())
+        },
+        _ => name
+    }
+}
+
+fn show_symbols(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let width: usize = elf.get_encoding().get_address_len() * 2;
+    let mut found: bool = false;
+
+    for (i, section) in elf.get_section_headers().iter().enumerate() {
+        match section.get_type_as_enum() {
+            SectionType::Symtab | SectionType::Dynsym => (),
+            _ => continue
+        }
+
+        let symbols: Vec<Symbol> = elf.get_symbols(i)?;
+        let versions: Vec<Option<SymbolVersion>> =
+            get_symbol_versions(elf, i)?;
+        found = true;
+
+        println!("Symbol table '{}' contains {} {}:", section.get_name(),
+            symbols.len(),
+            if symbols.len() == 1 { "entry" } else { "entries" });
+        println!("   Num:    {:<w$}Size Type    Bind   Vis      Ndx Name",
+            "Value", w = width - 1);
+
+        for (j, symbol) in symbols.iter().enumerate() {
+            let name: String = get_versioned_name(
+                elf.get_symbol_name(symbol), versions.get(j), true);
+
+            println!("{:>6}: {:0w$x} {:>5} {:<7} {:<6} {:<8} {:>3} {}", j,
+                symbol.get_value(), symbol.get_size(),
+                symbol.get_type().get_name(), symbol.get_binding().get_name(),
+                symbol.get_visibility().get_name(),
+                symbol.get_section_index_as_string(), name, w = width);
+        }
+    }
+
+    match found {
+        true => Ok(()),
+        false => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_dynamic(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let width: usize = elf.get_encoding().get_address_len() * 2;
+    let entries: Vec<DynamicEntry> = elf.get_dynamic_entries()?;
+
+    if entries.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for entry in entries.iter() {
+        let tag: DynamicTag = entry.get_tag_as_enum();
+        let value: String = match tag {
+            DynamicTag::Needed => format!("Shared library: [{}]",
+                elf.get_dynamic_string(entry)?),
+            DynamicTag::Soname => format!("Library soname: [{}]",
+                elf.get_dynamic_string(entry)?),
+            DynamicTag::Rpath => format!("Library rpath: [{}]",
+                elf.get_dynamic_string(entry)?),
+            DynamicTag::Runpath => format!("Library runpath: [{}]",
+                elf.get_dynamic_string(entry)?),
+            DynamicTag::Flags => entry.get_flags_as_strings().join(" "),
+            DynamicTag::Flags1 => format!("Flags: {}",
+                entry.get_flags_as_strings().join(" ")),
+            DynamicTag::PltRel => DynamicTag::from_value(entry.get_value())
+                .get_name(),
+            DynamicTag::PltRelSz | DynamicTag::RelaSz | DynamicTag::RelaEnt |
+                DynamicTag::StrSz | DynamicTag::SymEnt | DynamicTag::RelSz |
+                DynamicTag::RelEnt | DynamicTag::InitArraySz |
+                DynamicTag::FiniArraySz | DynamicTag::PreinitArraySz |
+                DynamicTag::RelrSz | DynamicTag::RelrEnt =>
+                format!("{} (bytes)", entry.get_value()),
+            DynamicTag::RelaCount | DynamicTag::RelCount |
+                DynamicTag::VerDefNum | DynamicTag::VerNeedNum =>
+                entry.get_value().to_string(),
+            _ => format!("{:#x}", entry.get_value())
+        };
+
+        println!("{:#0w$x} {:<20} {}", entry.get_tag(),
+            format!("({})", tag.get_name()), value, w = width + 2);
+    }
+
+    Ok(())
+}
+
+fn show_elf_relocations(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let width: usize = elf.get_encoding().get_address_len() * 2;
+    let mut found: bool = false;
+
+    for (i, section) in elf.get_section_headers().iter().enumerate() {
+        match section.get_type_as_enum() {
+            SectionType::Rel | SectionType::Rela => (),
+            _ => continue
+        }
+
+        let relocations: Vec<Relocation> = elf.get_relocations(i)?;
+        let versions: Vec<Option<SymbolVersion>> =
+            get_symbol_versions(elf, section.get_link() as usize)?;
+        found = true;
+
+        println!("Relocation section '{}' at offset {:#x} contains {} \
+            entries:", section.get_name(), section.get_offset(),
+            relocations.len());
+
+        for relocation in relocations.iter() {
+            let mut line: String = format!("{:0w$x} {:0w$x} {:<22}",
+                relocation.get_offset(), relocation.get_info(),
+                elf.get_relocation_type_name(relocation), w = width);
+
+            if let Some(symbol) = relocation.get_symbol() {
+                line += &format!(" {:0w$x} {}", symbol.get_value(),
+                    get_versioned_name(elf.get_symbol_name(symbol),
+                        versions.get(relocation.get_symbol_index() as usize),
+                        false), w = width);
+            }
+
+            match (relocation.get_addend(), relocation.get_symbol()) {
+                (Some(t), Some(_)) if t < 0 =>
+                    line += &format!(" - {:x}", t.unsigned_abs()),
+                (Some(t), Some(_)) => line += &format!(" + {:x}", t),
+                (Some(t), None) => line += &format!(" {:x}", t),
+                (None, _) => ()
+            }
+
+            println!("{}", line);
+        }
+    }
+
+    match found {
+        true => Ok(()),
+        false => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_note(elf: &elf::ElfFile, note: &Note) -> Result<(), ButylError> {
+    println!("  {:<20} {:#010x} {}", note.get_name(), note.get_desc().len(),
+        note.get_type_name());
+
+    if let Some(t) = note.get_build_id() {
+        println!("    Build ID: {}", t);
+    }
+
+    if let Some(t) = note.get_go_build_id() {
+        println!("    Go build ID: {}", t);
+    }
+
+    if let Some(t) = note.get_abi_tag(elf.get_encoding()) {
+        println!("    {}", t);
+    }
+
+    let properties: Vec<GnuProperty> =
+        note.get_properties(elf.get_encoding())?;
+
+    for property in properties.iter() {
+        println!("    Property: {}", property.get_description(
+            elf.get_header().get_machine_as_enum(), elf.get_encoding()));
+    }
+
+    Ok(())
+}
+
+fn show_notes(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let mut found: bool = false;
+
+    for section in elf.get_section_headers().iter()
+        .filter(|t| t.get_type_as_enum() == SectionType::Note) {
+        found = true;
+        println!("Notes in section '{}':", section.get_name());
+
+        for note in elf.get_section_notes(section)?.iter() {
+            show_note(elf, note)?;
+        }
+    }
+
+    /* without section headers the segments are all there is */
+    if elf.get_section_headers().is_empty() {
+        for segment in elf.get_program_headers().iter()
+            .filter(|t| t.get_type_as_enum() == elf::ProgramHeaderType::Note) {
+            found = true;
+            println!("Notes in segment at offset {:#x}:", segment.get_offset());
+
+            for note in elf.get_segment_notes(segment)?.iter() {
+                show_note(elf, note)?;
+            }
+        }
+    }
+
+    match found {
+        true => Ok(()),
+        false => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_build_id(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    match elf.get_build_id()? {
+        Some(t) => {
+            println!("{}", t);
+            Ok(())
+        },
+        None => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_versions(elf: &elf::ElfFile) -> Result<(), ButylError> {
+    let definitions: Vec<VersionDefinition> = elf.get_version_definitions()?;
+    let needs: Vec<VersionNeed> = elf.get_version_needs()?;
+
+    if definitions.is_empty() && needs.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    if !definitions.is_empty() {
+        println!("Version definitions:");
+    }
+
+    for definition in definitions.iter() {
+        println!("  Index: {}  Flags: {}  Name: {}", definition.get_index(),
+            versions::get_version_flags_as_string(definition.get_flags()),
+            definition.get_name());
+
+        for parent in definition.get_parents() {
+            println!("    Parent: {}", parent);
+        }
+    }
+
+    if !needs.is_empty() {
+        println!("Version needs:");
+    }
+
+    for need in needs.iter() {
+        println!("  File: {}  Cnt: {}", need.get_file(),
+            need.get_entries().len());
+
+        for entry in need.get_entries() {
+            println!("    Name: {}  Flags: {}  Version: {}", entry.get_name(),
+                versions::get_version_flags_as_string(entry.get_flags()),
+                entry.get_index());
+        }
+    }
+
+    Ok(())
+}
+
+fn show_macho_header(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    let header: &MachOHeader = macho.get_header();
+
+    println!("Magic: {:#x} ({}, {})", header.get_magic(),
+        if header.get_encoding().is_64_bit() { "64-bit" } else { "32-bit" },
+        if header.get_encoding().is_big_endian() {
+            "big endian"
+        } else {
+            "little endian"
+        });
+    println!("CPU type: {} ({:#x}, subtype {:#x})", header.get_arch_name(),
+        header.get_cpu_type(), header.get_cpu_subtype());
+    println!("File type: {}", header.get_file_type_as_enum().get_name());
+    println!("Load commands: {} ({} bytes)", header.get_ncmds(),
+        header.get_sizeofcmds());
+    println!("Flags: {:#x} {}", header.get_flags(),
+        header.get_flags_as_strings().join(" "));
+
+    Ok(())
+}
+
+fn get_load_command_summary(info: &LoadCommandInfo) -> String {
+    match info {
+        LoadCommandInfo::Segment(t) => format!("{} ({} sections)",
+            t.get_name(), t.get_sections().len()),
+        LoadCommandInfo::Symtab(t) => format!(
+            "symoff {:#x} nsyms {} stroff {:#x} strsize {:#x}",
+            t.get_symoff(), t.get_nsyms(), t.get_stroff(), t.get_strsize()),
+        LoadCommandInfo::Dysymtab(t) => format!(
+            "locals {}+{} extdefs {}+{} undefs {}+{} indirect {}",
+            t.get_ilocalsym(), t.get_nlocalsym(), t.get_iextdefsym(),
+            t.get_nextdefsym(), t.get_iundefsym(), t.get_nundefsym(),
+            t.get_nindirectsyms()),
+        LoadCommandInfo::Dylib(t) => String::from(t.get_name()),
+        LoadCommandInfo::Path(t) => t.clone(),
+        LoadCommandInfo::Uuid(t) => commands::get_uuid_as_string(t),
+        LoadCommandInfo::BuildVersion(t) => format!("{} minos {} sdk {}",
+            t.get_platform_as_enum().get_name(),
+            macho::get_version_as_string(t.get_minos()),
+            macho::get_version_as_string(t.get_sdk())),
+        LoadCommandInfo::LinkeditData(t) => format!(
+            "dataoff {:#x} datasize {:#x}", t.get_dataoff(),
+            t.get_datasize()),
+        LoadCommandInfo::EntryPoint(t) => format!(
+            "entryoff {:#x} stacksize {:#x}", t.get_entryoff(),
+            t.get_stacksize())
+    }
+}
+
+fn show_load_commands(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    for (i, command) in macho.get_load_commands().iter().enumerate() {
+        let summary: String = match macho.get_load_command_info(command)? {
+            Some(t) => get_load_command_summary(&t),
+            None => String::new()
+        };
+
+        println!("{:>3}: {:<28} {:#08x} {:>5} {}", i,
+            command.get_type_as_enum().get_name(), command.get_offset(),
+            command.get_cmdsize(), summary);
+    }
+
+    Ok(())
+}
+
+fn show_macho_segments(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    let width: usize = macho.get_encoding().get_address_len() * 2 + 2;
+    let segments: Vec<Segment> = macho.get_segments()?;
+
+    if segments.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for segment in segments.iter() {
+        println!("{:<16} {:#0w$x} {:#0w$x} {:#010x} {:#010x} {}/{}",
+            segment.get_name(), segment.get_vmaddr(), segment.get_vmsize(),
+            segment.get_fileoff(), segment.get_filesize(),
+            commands::get_protection_as_string(segment.get_initprot()),
+            commands::get_protection_as_string(segment.get_maxprot()),
+            w = width);
+
+        for section in segment.get_sections() {
+            println!("    {:<20} {:#0w$x} {:#0w$x} {:#010x} 2^{} {:#x}",
+                section.get_name(), section.get_addr(), section.get_size(),
+                section.get_offset(), section.get_align(),
+                section.get_flags(), w = width);
+        }
+    }
+
+    Ok(())
+}
+
+/* otool -L style */
+fn show_libraries(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    let id: Option<DylibCommand> = macho.get_id_dylib()?;
+    let dylibs: Vec<DylibCommand> = macho.get_dylibs()?;
+
+    if id.is_none() && dylibs.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for dylib in id.iter().chain(dylibs.iter()) {
+        let kind: String = match dylib.get_type_as_enum() {
+            macho::LoadCommandType::LoadDylib => String::new(),
+            t => format!(" [{}]", t.get_name())
+        };
+
+        println!("{} (compatibility version {}, current version {}){}",
+            dylib.get_name(),
+            macho::get_version_as_string(dylib.get_compatibility_version()),
+            macho::get_version_as_string(dylib.get_current_version()), kind);
+    }
+
+    Ok(())
+}
+
+fn show_uuid(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    match macho.get_uuid()? {
+        Some(t) => {
+            println!("{}", commands::get_uuid_as_string(&t));
+            Ok(())
+        },
+        None => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_build_version(macho: &macho::MachOFile) -> Result<(), ButylError> {
+    let build_version: BuildVersion = match macho.get_build_version()? {
+        Some(t) => t,
+        None => return Err(ButylError::NotFoundError)
+    };
+
+    println!("Platform: {}", build_version.get_platform_as_enum().get_name());
+    println!("Minimum OS: {}",
+        macho::get_version_as_string(build_version.get_minos()));
+    println!("SDK: {}", macho::get_version_as_string(build_version.get_sdk()));
+
+    for (tool, version) in build_version.get_tools() {
+        println!("Tool: {} {}", BuildTool::from_value(*tool).get_name(),
+            macho::get_version_as_string(*version));
+    }
+
+    Ok(())
+}
+
+fn show_arches(fat: &macho::fat::FatFile) -> Result<(), ButylError> {
+    let arches: &[FatArch] = fat.get_arches();
+
+    if arches.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for arch in arches.iter() {
+        let file_type: String = match fat.get_macho(arch) {
+            Ok(t) => t.get_header().get_file_type_as_enum().get_name(),
+            Err(_e) => String::from("<not Mach-O>")
+        };
+
+        println!("{:<10} {:#010x} {:#010x} 2^{:<2} {}", arch.get_arch_name(),
+            arch.get_offset(), arch.get_size(), arch.get_align(), file_type);
+    }
+
+    Ok(())
+}
+
+fn show_ne_header(ne: &ne::NeFile) -> Result<(), ButylError> {
+    let header: &NeHeader = ne.get_header();
+    let (cs, ip): (u16, u16) = header.get_entry_point();
+    let (ss, sp): (u32, u32) = (header.get_sssp() >> 16,
+        header.get_sssp() & 0xffff);
+
+    println!("Linker version: {}.{}", header.get_linker_version(),
+        header.get_linker_revision());
+    println!("Target OS: {}", header.get_target_os().get_name());
+    println!("Expected Windows version: {}", header.get_expver_as_string());
+    println!("Flags: {:#06x} {}", header.get_flags(),
+        header.get_flags_as_strings().join(" "));
+    println!("Type: {}", if header.is_library() {
+        "Library"
+    } else {
+        "Program"
+    });
+    println!("Entry point: {}:{:04x}", cs, ip);
+    println!("Initial stack: {}:{:04x}", ss, sp);
+    println!("Automatic data segment: {}", header.get_autodata());
+    println!("Heap size: {:#x}", header.get_heap());
+    println!("Stack size: {:#x}", header.get_stack());
+    println!("Segments: {}", header.get_cseg());
+    println!("Module references: {}", header.get_cmod());
+    println!("Movable entry points: {}", header.get_cmovent());
+    println!("Alignment: {} bytes", 1u32 << header.get_align_shift());
+    println!("Checksum: {:#010x}", header.get_crc());
+
+    Ok(())
+}
+
+fn show_ne_segments(ne: &ne::NeFile) -> Result<(), ButylError> {
+    if ne.get_segments().is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for (i, segment) in ne.get_segments().iter().enumerate() {
+        println!("{:>3}: {:#08x} {:#07x} {:#07x} {}", i + 1,
+            segment.get_offset(), segment.get_size(),
+            segment.get_alloc_size(), segment.get_flags_as_string());
+    }
+
+    Ok(())
+}
+
+fn show_ne_names(ne: &ne::NeFile) -> Result<(), ButylError> {
+    show_names(&ne.get_resident_names()?, &ne.get_non_resident_names()?)
+}
+
+/* NE and LE/LX name tables lead with the module name and description */
+fn show_names(resident: &[Name], non_resident: &[Name]) ->
+    Result<(), ButylError> {
+    if let Some(t) = resident.first() {
+        println!("Module: {}", t.get_name());
+    }
+
+    if let Some(t) = non_resident.first() {
+        println!("Description: {}", t.get_name());
+    }
+
+    for (title, names) in [("Resident", resident),
+        ("Non-resident", non_resident)].iter() {
+        if names.len() > 1 {
+            println!("{} names:", title);
+        }
+
+        for name in names.iter().skip(1) {
+            println!("  {:>5} {}", name.get_ordinal(), name.get_name());
+        }
+    }
+
+    Ok(())
+}
+
+fn show_modules(modules: &[String]) -> Result<(), ButylError> {
+    if modules.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for (i, module) in modules.iter().enumerate() {
+        println!("{:>3}: {}", i + 1, module);
+    }
+
+    Ok(())
+}
+
+/* "KERNEL.91", "USER.MESSAGEBOX", "segment 2:0010" or "entry 5" */
+fn get_ne_relocation_target(ne: &ne::NeFile, modules: &[String],
+    relocation: &NeRelocation) -> Result<String, ButylError> {
+    let get_module = |t: u16| -> String {
+        match (t as usize).checked_sub(1).and_then(|u| modules.get(u)) {
+            Some(u) => u.clone(),
+            None => format!("<module {}>", t)
+        }
+    };
+
+    Ok(match relocation.get_target() {
+        RelocationTarget::Internal { segment, offset } =>
+            format!("segment {}:{:04x}", segment, offset),
+        RelocationTarget::Moveable { ordinal } => format!("entry {}", ordinal),
+        RelocationTarget::ImportOrdinal { module, ordinal } =>
+            format!("{}.{}", get_module(*module), ordinal),
+        RelocationTarget::ImportName { module, offset } =>
+            format!("{}.{}", get_module(*module),
+                ne.get_imported_name(*offset)?),
+        RelocationTarget::OsFixup { kind } => format!("OS fixup {}", kind)
+    })
+}
+
+fn show_ne_relocations(ne: &ne::NeFile) -> Result<(), ButylError> {
+    let modules: Vec<String> = ne.get_module_references()?;
+    let mut found: bool = false;
+
+    for (i, segment) in ne.get_segments().iter().enumerate() {
+        let relocations: Vec<NeRelocation> = ne.get_relocations(segment)?;
+
+        if relocations.is_empty() {
+            continue;
+        }
+
+        found = true;
+        println!("Segment {} contains {} relocations:", i + 1,
+            relocations.len());
+
+        for relocation in relocations.iter() {
+            println!("  {:04x} {:<9} {}{}", relocation.get_offset(),
+                relocation.get_source_as_enum().get_name(),
+                get_ne_relocation_target(ne, &modules, relocation)?,
+                if relocation.is_additive() { " (additive)" } else { "" });
+        }
+    }
+
+    match found {
+        true => Ok(()),
+        false => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_le_field(le: &le::LeFile, field: &str) -> Result<(), ButylError> {
+    match field {
+        "header" => show_le_header(le),
+        "objects" => show_objects(le),
+        "fixups" => show_fixups(le),
+        "entries" => show_entries(le),
+        "names" => show_names(&le.get_resident_names()?,
+            &le.get_non_resident_names()?),
+        "modules" => show_modules(&le.get_import_modules()?),
+        _ => Err(ButylError::UnknownFieldError)
+    }
+}
+
+fn show_le_header(le: &le::LeFile) -> Result<(), ButylError> {
+    let header: &LeHeader = le.get_header();
+
+    println!("Signature: {}", if header.is_lx() { "LX" } else { "LE" });
+    println!("Format level: {}", header.get_level());
+    println!("CPU: {}", header.get_cpu_as_enum().get_name());
+    println!("Target OS: {}", header.get_target_os().get_name());
+    println!("Module version: {:#x}", header.get_ver());
+    println!("Module type: {}", header.get_module_type().get_name());
+    println!("Flags: {:#010x} {}", header.get_mflags(),
+        header.get_mflags_as_strings().join(" "));
+    println!("Entry point: {}:{:08x}", header.get_startobj(),
+        header.get_eip());
+    println!("Initial stack: {}:{:08x}", header.get_stackobj(),
+        header.get_esp());
+    println!("Objects: {}", header.get_objcnt());
+    println!("Pages: {} of {:#x} bytes", header.get_mpages(),
+        header.get_pagesize());
+
+    match header.is_lx() {
+        true => println!("Page offset shift: {}", header.get_pageshift()),
+        false => println!("Last page size: {:#x}", header.get_pageshift())
+    }
+
+    println!("Data pages: {:#x}", header.get_datapage());
+    println!("Automatic data object: {}", header.get_autodata());
+    println!("Heap size: {:#x}", header.get_heapsize());
+
+    if header.is_lx() {
+        println!("Stack size: {:#x}", header.get_stacksize());
+    }
+
+    Ok(())
+}
+
+fn show_objects(le: &le::LeFile) -> Result<(), ButylError> {
+    if le.get_objects().is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for (i, object) in le.get_objects().iter().enumerate() {
+        println!("{:>3}: {:#010x} {:#010x} {} {}", i + 1, object.get_base(),
+            object.get_size(), object.get_protection_as_string(),
+            object.get_flags_as_strings().join(" "));
+
+        for (j, page) in le.get_object_pages(object).iter().enumerate() {
+            println!("    {:>5} {:#010x} {:#06x} {}",
+                object.get_pagemap() as usize + j, page.get_offset(),
+                page.get_size(), page.get_type_as_enum().get_name());
+        }
+    }
+
+    Ok(())
+}
+
+/* source offsets can reach back into the previous page */
+fn get_fixup_offset_as_string(offset: i16) -> String {
+    match offset < 0 {
+        true => format!("-{:03x}", -(offset as i32)),
+        false => format!("{:04x}", offset)
+    }
+}
+
+/* module numbers count from 1 */
+fn get_import_module(modules: &[String], module: u16) -> String {
+    match (module as usize).checked_sub(1).and_then(|t| modules.get(t)) {
+        Some(t) => t.clone(),
+        None => format!("<module {}>", module)
+    }
+}
+
+/* "object 2:00001000", "DOSCALLS.282", "KERNEL.name" or "entry 5" */
+fn get_fixup_target(le: &le::LeFile, modules: &[String], fixup: &Fixup) ->
+    Result<String, ButylError> {
+    let get_module = |t: u16| get_import_module(modules, t);
+
+    let target: String = match fixup.get_target() {
+        FixupTarget::Internal { object, offset } =>
+            format!("object {}:{:08x}", object, offset),
+        FixupTarget::ImportOrdinal { module, ordinal } =>
+            format!("{}.{}", get_module(*module), ordinal),
+        FixupTarget::ImportName { module, offset } =>
+            format!("{}.{}", get_module(*module),
+                le.get_import_procedure(*offset)?),
+        FixupTarget::Entry { ordinal } => format!("entry {}", ordinal)
+    };
+
+    Ok(match fixup.get_additive() {
+        Some(t) => format!("{} + {:#x}", target, t),
+        None => target
+    })
+}
+
+fn show_fixups(le: &le::LeFile) -> Result<(), ButylError> {
+    let modules: Vec<String> = le.get_import_modules()?;
+    let mut found: bool = false;
+
+    for i in 0..le.get_pages().len() {
+        let fixups: Vec<Fixup> = le.get_fixups(i)?;
+
+        if fixups.is_empty() {
+            continue;
+        }
+
+        found = true;
+        println!("Page {} contains {} fixups:", i + 1, fixups.len());
+
+        for fixup in fixups.iter() {
+            let offsets: Vec<String> = fixup.get_offsets().iter()
+                .map(|t| get_fixup_offset_as_string(*t))
+                .collect();
+
+            println!("  {:<8} {} {}", fixup.get_source_as_enum().get_name(),
+                offsets.join(","), get_fixup_target(le, &modules, fixup)?);
+        }
+    }
+
+    match found {
+        true => Ok(()),
+        false => Err(ButylError::NotFoundError)
+    }
+}
+
+fn show_entries(le: &le::LeFile) -> Result<(), ButylError> {
+    let entries: Vec<Entry> = le.get_entries()?;
+    let modules: Vec<String> = le.get_import_modules()?;
+    let names: Vec<Name> = le.get_resident_names()?.into_iter()
+        .chain(le.get_non_resident_names()?)
+        .filter(|t| t.get_ordinal() != 0)
+        .collect();
+
+    if entries.is_empty() {
+        return Err(ButylError::NotFoundError);
+    }
+
+    for entry in entries.iter() {
+        let name: &str = match names.iter()
+            .find(|t| t.get_ordinal() == entry.get_ordinal()) {
+            Some(t) => t.get_name(),
+            None => ""
+        };
+
+        let target: String = match entry.get_target() {
+            EntryTarget::Object { object, offset } =>
+                format!("{}:{:08x}", object, offset),
+            EntryTarget::ImportOrdinal { module, ordinal } =>
+                format!("{}.{}", get_import_module(&modules, module), ordinal),
+            EntryTarget::ImportName { module, offset } =>
+                format!("{}.{}", get_import_module(&modules, module),
+                    le.get_import_procedure(offset)?)
+        };
+
+        println!("{:>5} {:<9} {} {:#04x} {}", entry.get_ordinal(),
+            entry.get_type_as_enum().get_name(), target, entry.get_flags(),
+            name);
+    }
+
+    Ok(())
+}
+
+/* there is no header as such, only what DOS sets up when loading it */
+fn show_com_header(com: &com::ComFile) -> Result<(), ButylError> {
+    let opcode: u8 = com.get_first_opcode();
+
+    println!("Size: {} bytes", com.get_len());
+    println!("Load address: CS:{:04x}", com::COM_LOAD_OFFSET);
+    println!("Entry point: CS:{:04x}", com.get_entry_point());
+    println!("Initial stack: SS:{:04x}", com.get_initial_sp());
+    println!("First opcode: {:#04x} {}", opcode,
+        com::get_opcode_name(opcode).unwrap_or("?"));
+
+    Ok(())
+}
@@ -4,6 +4,9 @@ use std::mem;
 use std::result::Result;
 use crate::errors::*;
 
+pub const DOS_HEADER_LEN: usize = 64;
+pub const DOS_MAGIC: u16 = 0x5a4d; /* "MZ" */
+
 const DOS_HEADER_FIELD_LEN_RES1: usize = 4 * 2;
 const DOS_HEADER_FIELD_LEN_RES2: usize = 10 * 2;
 
@@ -183,6 +186,7 @@ impl DosHeader {
         self.e_lfanew = lfanew;
     }
 
+    #[allow(clippy::field_reassign_with_default)]
     pub fn from_be_bytes(bytes: &[u8]) -> Result<DosHeader, ButylError> {
         if bytes.len() < mem::size_of::<DosHeader>() { /* bounds check */
             return Err(ButylError::InsufficientDataError);
@@ -208,7 +212,7 @@ impl DosHeader {
         dos_header.e_res = [bytes[28], bytes[29], bytes[30], bytes[31],
         bytes[32], bytes[33], bytes[34], bytes[35]];
 
-        dos_header.e_oemid = ((bytes[36] as u16) << 8) as u16 |
+        dos_header.e_oemid = ((bytes[36] as u16) << 8) |
             bytes[37] as u16;
         dos_header.e_oeminfo = ((bytes[38] as u16) << 8) | bytes[39] as u16;
         
@@ -220,14 +224,15 @@ impl DosHeader {
             bytes[56], bytes[57], bytes[58], bytes[59],
         ];
 
-        dos_header.e_lfanew = ((bytes[60] as u32) << 24) as u32 | 
-            ((bytes[61] as u32) << 16) as u32 | 
-            ((bytes[62] as u32) << 8) as u32 |
+        dos_header.e_lfanew = ((bytes[60] as u32) << 24) | 
+            ((bytes[61] as u32) << 16) | 
+            ((bytes[62] as u32) << 8) |
             bytes[63] as u32;
 
         Ok(dos_header)
     }
 
+    #[allow(clippy::field_reassign_with_default)]
     pub fn from_le_bytes(bytes: &[u8]) -> Result<DosHeader, ButylError> {
         if bytes.len() < mem::size_of::<DosHeader>() { /* bounds check */
             return Err(ButylError::InsufficientDataError);
@@ -255,7 +260,7 @@ impl DosHeader {
             bytes[31], bytes[30], bytes[29], bytes[28]
         ];
 
-        dos_header.e_oemid = ((bytes[37] as u16) << 8) as u16 |
+        dos_header.e_oemid = ((bytes[37] as u16) << 8) |
             bytes[36] as u16;
         dos_header.e_oeminfo = ((bytes[39] as u16) << 8) | bytes[38] as u16;
         
@@ -267,13 +272,33 @@ impl DosHeader {
             bytes[43], bytes[42], bytes[41], bytes[40],
         ];
 
-        dos_header.e_lfanew = ((bytes[63] as u32) << 24) as u32 | 
-            ((bytes[62] as u32) << 16) as u32 | 
-            ((bytes[61] as u32) << 8) as u32 |
+        dos_header.e_lfanew = ((bytes[63] as u32) << 24) | 
+            ((bytes[62] as u32) << 16) | 
+            ((bytes[61] as u32) << 8) |
             bytes[60] as u32;
 
         Ok(dos_header)
     }
+
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(DOS_HEADER_LEN);
+
+        for field in &[self.e_magic, self.e_cblp, self.e_cp, self.e_crlc,
+            self.e_cparhdr, self.e_minalloc, self.e_maxalloc, self.e_ss,
+            self.e_sp, self.e_csum, self.e_ip, self.e_cs, self.e_lfarlc,
+            self.e_ovno] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+
+        /* from_le_bytes reads the reserved words back to front */
+        bytes.extend(self.e_res.iter().rev());
+        bytes.extend_from_slice(&self.e_oemid.to_le_bytes());
+        bytes.extend_from_slice(&self.e_oeminfo.to_le_bytes());
+        bytes.extend(self.e_res2.iter().rev());
+        bytes.extend_from_slice(&self.e_lfanew.to_le_bytes());
+
+        bytes
+    }
 }
 
 #[derive(Debug)]
@@ -283,17 +308,17 @@ pub struct DosFile<'a> {
 }
 
 impl<'a> DosFile<'a> {
-    pub fn from_be_bytes(data: &[u8]) -> Result<DosFile, ButylError> {
+    pub fn from_be_bytes(data: &[u8]) -> Result<DosFile<'_>, ButylError> {
         Ok(DosFile {
             header: DosHeader::from_be_bytes(data)?,
-            data: data
+            data
         })
     }
 
-    pub fn from_le_bytes(data: &[u8]) -> Result<DosFile, ButylError> {
+    pub fn from_le_bytes(data: &[u8]) -> Result<DosFile<'_>, ButylError> {
         Ok(DosFile {
             header: DosHeader::from_le_bytes(data)?,
-            data: data
+            data
         })
     }
 }
@@ -0,0 +1,1015 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::utils::*;
+
+pub mod symbols;
+pub mod dynamic;
+pub mod relocs;
+pub mod notes;
+pub mod versions;
+
+pub const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+pub const ELF_IDENT_LEN: usize = 16;
+pub const ELF_HEADER_LEN_32: usize = 52;
+pub const ELF_HEADER_LEN_64: usize = 64;
+pub const ELF_PROGRAM_HEADER_LEN_32: usize = 32;
+pub const ELF_PROGRAM_HEADER_LEN_64: usize = 56;
+pub const ELF_SECTION_HEADER_LEN_32: usize = 40;
+pub const ELF_SECTION_HEADER_LEN_64: usize = 64;
+
+pub const SHN_UNDEF: u16 = 0x0000;
+pub const SHN_LORESERVE: u16 = 0xff00;
+pub const SHN_ABS: u16 = 0xfff1;
+pub const SHN_COMMON: u16 = 0xfff2;
+pub const SHN_XINDEX: u16 = 0xffff;
+pub const PN_XNUM: u16 = 0xffff;
+
+pub const SHF_WRITE: u64 = 0x0001;
+pub const SHF_ALLOC: u64 = 0x0002;
+pub const SHF_EXECINSTR: u64 = 0x0004;
+pub const SHF_MERGE: u64 = 0x0010;
+pub const SHF_STRINGS: u64 = 0x0020;
+pub const SHF_INFO_LINK: u64 = 0x0040;
+pub const SHF_LINK_ORDER: u64 = 0x0080;
+pub const SHF_OS_NONCONFORMING: u64 = 0x0100;
+pub const SHF_GROUP: u64 = 0x0200;
+pub const SHF_TLS: u64 = 0x0400;
+pub const SHF_COMPRESSED: u64 = 0x0800;
+pub const SHF_EXCLUDE: u64 = 0x8000_0000;
+
+pub const PF_X: u32 = 0x1;
+pub const PF_W: u32 = 0x2;
+pub const PF_R: u32 = 0x4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfClass {
+    Elf32,
+    Elf64
+}
+
+impl ElfClass {
+    pub fn to_value(class: ElfClass) -> u8 {
+        match class {
+            ElfClass::Elf32 => 1,
+            ElfClass::Elf64 => 2
+        }
+    }
+
+    pub fn from_value(value: u8) -> Option<ElfClass> {
+        match value {
+            1 => Some(ElfClass::Elf32),
+            2 => Some(ElfClass::Elf64),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfData {
+    Lsb,
+    Msb
+}
+
+impl ElfData {
+    pub fn to_value(data: ElfData) -> u8 {
+        match data {
+            ElfData::Lsb => 1,
+            ElfData::Msb => 2
+        }
+    }
+
+    pub fn from_value(value: u8) -> Option<ElfData> {
+        match value {
+            1 => Some(ElfData::Lsb),
+            2 => Some(ElfData::Msb),
+            _ => None
+        }
+    }
+}
+
+/* the class and data encoding from e_ident, which between them decide how
+ * every later structure in the file is laid out and read */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElfEncoding {
+    class: ElfClass,
+    data: ElfData
+}
+
+impl ElfEncoding {
+    pub fn new(class: ElfClass, data: ElfData) -> ElfEncoding {
+        ElfEncoding {
+            class,
+            data
+        }
+    }
+
+    pub fn get_class(&self) -> ElfClass {
+        self.class
+    }
+
+    pub fn get_data(&self) -> ElfData {
+        self.data
+    }
+
+    pub fn is_64_bit(&self) -> bool {
+        self.class == ElfClass::Elf64
+    }
+
+    pub fn is_big_endian(&self) -> bool {
+        self.data == ElfData::Msb
+    }
+
+    pub fn get_address_len(&self) -> usize {
+        if self.is_64_bit() { 8 } else { 4 }
+    }
+
+    pub fn read_u16(&self, bytes: &[u8], offset: usize) ->
+        Result<u16, ButylError> {
+        match self.data {
+            ElfData::Lsb => read_le_u16(bytes, offset),
+            ElfData::Msb => read_be_u16(bytes, offset)
+        }
+    }
+
+    pub fn read_u32(&self, bytes: &[u8], offset: usize) ->
+        Result<u32, ButylError> {
+        match self.data {
+            ElfData::Lsb => read_le_u32(bytes, offset),
+            ElfData::Msb => read_be_u32(bytes, offset)
+        }
+    }
+
+    pub fn read_u64(&self, bytes: &[u8], offset: usize) ->
+        Result<u64, ButylError> {
+        match self.data {
+            ElfData::Lsb => read_le_u64(bytes, offset),
+            ElfData::Msb => read_be_u64(bytes, offset)
+        }
+    }
+
+    /* an address, offset or size, which follows the class in width */
+    pub fn read_address(&self, bytes: &[u8], offset: usize) ->
+        Result<u64, ButylError> {
+        match self.class {
+            ElfClass::Elf32 => Ok(self.read_u32(bytes, offset)? as u64),
+            ElfClass::Elf64 => self.read_u64(bytes, offset)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfType {
+    None,
+    Rel,
+    Exec,
+    Dyn,
+    Core,
+    Other(u16)
+}
+
+impl ElfType {
+    pub fn to_value(kind: ElfType) -> u16 {
+        match kind {
+            ElfType::None => 0,
+            ElfType::Rel => 1,
+            ElfType::Exec => 2,
+            ElfType::Dyn => 3,
+            ElfType::Core => 4,
+            ElfType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u16) -> ElfType {
+        match value {
+            0 => ElfType::None,
+            1 => ElfType::Rel,
+            2 => ElfType::Exec,
+            3 => ElfType::Dyn,
+            4 => ElfType::Core,
+            t => ElfType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ElfType::None => String::from("NONE (None)"),
+            ElfType::Rel => String::from("REL (Relocatable file)"),
+            ElfType::Exec => String::from("EXEC (Executable file)"),
+            ElfType::Dyn => String::from("DYN (Shared object file)"),
+            ElfType::Core => String::from("CORE (Core file)"),
+            ElfType::Other(t) => format!("<unknown>: {:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum ElfMachine {
+    None,
+    SPARC,
+    I386,
+    M68K,
+    MIPS,
+    PARISC,
+    SPARC32Plus,
+    PowerPC,
+    PowerPC64,
+    S390,
+    ARM,
+    SuperH,
+    SPARCV9,
+    IA64,
+    X86_64,
+    AVR,
+    MSP430,
+    AArch64,
+    AMDGPU,
+    RISCV,
+    BPF,
+    LoongArch,
+    Other(u16)
+}
+
+impl ElfMachine {
+    pub fn to_value(machine: ElfMachine) -> u16 {
+        match machine {
+            ElfMachine::None => 0,
+            ElfMachine::SPARC => 2,
+            ElfMachine::I386 => 3,
+            ElfMachine::M68K => 4,
+            ElfMachine::MIPS => 8,
+            ElfMachine::PARISC => 15,
+            ElfMachine::SPARC32Plus => 18,
+            ElfMachine::PowerPC => 20,
+            ElfMachine::PowerPC64 => 21,
+            ElfMachine::S390 => 22,
+            ElfMachine::ARM => 40,
+            ElfMachine::SuperH => 42,
+            ElfMachine::SPARCV9 => 43,
+            ElfMachine::IA64 => 50,
+            ElfMachine::X86_64 => 62,
+            ElfMachine::AVR => 83,
+            ElfMachine::MSP430 => 105,
+            ElfMachine::AArch64 => 183,
+            ElfMachine::AMDGPU => 224,
+            ElfMachine::RISCV => 243,
+            ElfMachine::BPF => 247,
+            ElfMachine::LoongArch => 258,
+            ElfMachine::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u16) -> ElfMachine {
+        match value {
+            0 => ElfMachine::None,
+            2 => ElfMachine::SPARC,
+            3 => ElfMachine::I386,
+            4 => ElfMachine::M68K,
+            8 => ElfMachine::MIPS,
+            15 => ElfMachine::PARISC,
+            18 => ElfMachine::SPARC32Plus,
+            20 => ElfMachine::PowerPC,
+            21 => ElfMachine::PowerPC64,
+            22 => ElfMachine::S390,
+            40 => ElfMachine::ARM,
+            42 => ElfMachine::SuperH,
+            43 => ElfMachine::SPARCV9,
+            50 => ElfMachine::IA64,
+            62 => ElfMachine::X86_64,
+            83 => ElfMachine::AVR,
+            105 => ElfMachine::MSP430,
+            183 => ElfMachine::AArch64,
+            224 => ElfMachine::AMDGPU,
+            243 => ElfMachine::RISCV,
+            247 => ElfMachine::BPF,
+            258 => ElfMachine::LoongArch,
+            t => ElfMachine::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ElfMachine::None => String::from("None"),
+            ElfMachine::SPARC => String::from("Sparc"),
+            ElfMachine::I386 => String::from("Intel 80386"),
+            ElfMachine::M68K => String::from("MC68000"),
+            ElfMachine::MIPS => String::from("MIPS R3000"),
+            ElfMachine::PARISC => String::from("HPPA"),
+            ElfMachine::SPARC32Plus => String::from("Sparc v8+"),
+            ElfMachine::PowerPC => String::from("PowerPC"),
+            ElfMachine::PowerPC64 => String::from("PowerPC64"),
+            ElfMachine::S390 => String::from("IBM S/390"),
+            ElfMachine::ARM => String::from("ARM"),
+            ElfMachine::SuperH => String::from("Renesas / SuperH SH"),
+            ElfMachine::SPARCV9 => String::from("Sparc v9"),
+            ElfMachine::IA64 => String::from("Intel IA-64"),
+            ElfMachine::X86_64 =>
+                String::from("Advanced Micro Devices X86-64"),
+            ElfMachine::AVR => String::from("Atmel AVR 8-bit microcontroller"),
+            ElfMachine::MSP430 =>
+                String::from("Texas Instruments msp430 microcontroller"),
+            ElfMachine::AArch64 => String::from("AArch64"),
+            ElfMachine::AMDGPU => String::from("AMD GPU"),
+            ElfMachine::RISCV => String::from("RISC-V"),
+            ElfMachine::BPF => String::from("Linux BPF"),
+            ElfMachine::LoongArch => String::from("LoongArch"),
+            ElfMachine::Other(t) => format!("<unknown>: {:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgramHeaderType {
+    Null,
+    Load,
+    Dynamic,
+    Interp,
+    Note,
+    Shlib,
+    Phdr,
+    Tls,
+    GnuEhFrame,
+    GnuStack,
+    GnuRelro,
+    GnuProperty,
+    Other(u32)
+}
+
+impl ProgramHeaderType {
+    pub fn to_value(kind: ProgramHeaderType) -> u32 {
+        match kind {
+            ProgramHeaderType::Null => 0,
+            ProgramHeaderType::Load => 1,
+            ProgramHeaderType::Dynamic => 2,
+            ProgramHeaderType::Interp => 3,
+            ProgramHeaderType::Note => 4,
+            ProgramHeaderType::Shlib => 5,
+            ProgramHeaderType::Phdr => 6,
+            ProgramHeaderType::Tls => 7,
+            ProgramHeaderType::GnuEhFrame => 0x6474_e550,
+            ProgramHeaderType::GnuStack => 0x6474_e551,
+            ProgramHeaderType::GnuRelro => 0x6474_e552,
+            ProgramHeaderType::GnuProperty => 0x6474_e553,
+            ProgramHeaderType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> ProgramHeaderType {
+        match value {
+            0 => ProgramHeaderType::Null,
+            1 => ProgramHeaderType::Load,
+            2 => ProgramHeaderType::Dynamic,
+            3 => ProgramHeaderType::Interp,
+            4 => ProgramHeaderType::Note,
+            5 => ProgramHeaderType::Shlib,
+            6 => ProgramHeaderType::Phdr,
+            7 => ProgramHeaderType::Tls,
+            0x6474_e550 => ProgramHeaderType::GnuEhFrame,
+            0x6474_e551 => ProgramHeaderType::GnuStack,
+            0x6474_e552 => ProgramHeaderType::GnuRelro,
+            0x6474_e553 => ProgramHeaderType::GnuProperty,
+            t => ProgramHeaderType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ProgramHeaderType::Null => String::from("NULL"),
+            ProgramHeaderType::Load => String::from("LOAD"),
+            ProgramHeaderType::Dynamic => String::from("DYNAMIC"),
+            ProgramHeaderType::Interp => String::from("INTERP"),
+            ProgramHeaderType::Note => String::from("NOTE"),
+            ProgramHeaderType::Shlib => String::from("SHLIB"),
+            ProgramHeaderType::Phdr => String::from("PHDR"),
+            ProgramHeaderType::Tls => String::from("TLS"),
+            ProgramHeaderType::GnuEhFrame => String::from("GNU_EH_FRAME"),
+            ProgramHeaderType::GnuStack => String::from("GNU_STACK"),
+            ProgramHeaderType::GnuRelro => String::from("GNU_RELRO"),
+            ProgramHeaderType::GnuProperty => String::from("GNU_PROPERTY"),
+            ProgramHeaderType::Other(t) => format!("{:#010x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SectionType {
+    Null,
+    Progbits,
+    Symtab,
+    Strtab,
+    Rela,
+    Hash,
+    Dynamic,
+    Note,
+    Nobits,
+    Rel,
+    Shlib,
+    Dynsym,
+    InitArray,
+    FiniArray,
+    PreinitArray,
+    Group,
+    SymtabShndx,
+    Relr,
+    GnuAttributes,
+    GnuHash,
+    GnuVerdef,
+    GnuVerneed,
+    GnuVersym,
+    Other(u32)
+}
+
+impl SectionType {
+    pub fn to_value(kind: SectionType) -> u32 {
+        match kind {
+            SectionType::Null => 0,
+            SectionType::Progbits => 1,
+            SectionType::Symtab => 2,
+            SectionType::Strtab => 3,
+            SectionType::Rela => 4,
+            SectionType::Hash => 5,
+            SectionType::Dynamic => 6,
+            SectionType::Note => 7,
+            SectionType::Nobits => 8,
+            SectionType::Rel => 9,
+            SectionType::Shlib => 10,
+            SectionType::Dynsym => 11,
+            SectionType::InitArray => 14,
+            SectionType::FiniArray => 15,
+            SectionType::PreinitArray => 16,
+            SectionType::Group => 17,
+            SectionType::SymtabShndx => 18,
+            SectionType::Relr => 19,
+            SectionType::GnuAttributes => 0x6fff_fff5,
+            SectionType::GnuHash => 0x6fff_fff6,
+            SectionType::GnuVerdef => 0x6fff_fffd,
+            SectionType::GnuVerneed => 0x6fff_fffe,
+            SectionType::GnuVersym => 0x6fff_ffff,
+            SectionType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> SectionType {
+        match value {
+            0 => SectionType::Null,
+            1 => SectionType::Progbits,
+            2 => SectionType::Symtab,
+            3 => SectionType::Strtab,
+            4 => SectionType::Rela,
+            5 => SectionType::Hash,
+            6 => SectionType::Dynamic,
+            7 => SectionType::Note,
+            8 => SectionType::Nobits,
+            9 => SectionType::Rel,
+            10 => SectionType::Shlib,
+            11 => SectionType::Dynsym,
+            14 => SectionType::InitArray,
+            15 => SectionType::FiniArray,
+            16 => SectionType::PreinitArray,
+            17 => SectionType::Group,
+            18 => SectionType::SymtabShndx,
+            19 => SectionType::Relr,
+            0x6fff_fff5 => SectionType::GnuAttributes,
+            0x6fff_fff6 => SectionType::GnuHash,
+            0x6fff_fffd => SectionType::GnuVerdef,
+            0x6fff_fffe => SectionType::GnuVerneed,
+            0x6fff_ffff => SectionType::GnuVersym,
+            t => SectionType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SectionType::Null => String::from("NULL"),
+            SectionType::Progbits => String::from("PROGBITS"),
+            SectionType::Symtab => String::from("SYMTAB"),
+            SectionType::Strtab => String::from("STRTAB"),
+            SectionType::Rela => String::from("RELA"),
+            SectionType::Hash => String::from("HASH"),
+            SectionType::Dynamic => String::from("DYNAMIC"),
+            SectionType::Note => String::from("NOTE"),
+            SectionType::Nobits => String::from("NOBITS"),
+            SectionType::Rel => String::from("REL"),
+            SectionType::Shlib => String::from("SHLIB"),
+            SectionType::Dynsym => String::from("DYNSYM"),
+            SectionType::InitArray => String::from("INIT_ARRAY"),
+            SectionType::FiniArray => String::from("FINI_ARRAY"),
+            SectionType::PreinitArray => String::from("PREINIT_ARRAY"),
+            SectionType::Group => String::from("GROUP"),
+            SectionType::SymtabShndx =>
+                String::from("SYMTAB SECTION INDICES"),
+            SectionType::Relr => String::from("RELR"),
+            SectionType::GnuAttributes => String::from("GNU_ATTRIBUTES"),
+            SectionType::GnuHash => String::from("GNU_HASH"),
+            SectionType::GnuVerdef => String::from("VERDEF"),
+            SectionType::GnuVerneed => String::from("VERNEED"),
+            SectionType::GnuVersym => String::from("VERSYM"),
+            SectionType::Other(t) => format!("{:#010x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ElfHeader {
+    encoding: ElfEncoding,  /* EI_CLASS and EI_DATA */
+    ident_version: u8,      /* EI_VERSION */
+    os_abi: u8,             /* EI_OSABI */
+    abi_version: u8,        /* EI_ABIVERSION */
+    e_type: u16,            /* object file type */
+    e_machine: u16,         /* target architecture */
+    e_version: u32,         /* object file version */
+    e_entry: u64,           /* entry point VA */
+    e_phoff: u64,           /* program header table offset */
+    e_shoff: u64,           /* section header table offset */
+    e_flags: u32,           /* processor-specific flags */
+    e_ehsize: u16,          /* size of this header */
+    e_phentsize: u16,       /* size of a program header */
+    e_phnum: u16,           /* number of program headers */
+    e_shentsize: u16,       /* size of a section header */
+    e_shnum: u16,           /* number of section headers */
+    e_shstrndx: u16         /* section holding the section names */
+}
+
+impl ElfHeader {
+    pub fn get_encoding(&self) -> ElfEncoding {
+        self.encoding
+    }
+
+    pub fn get_ident_version(&self) -> u8 {
+        self.ident_version
+    }
+
+    pub fn get_os_abi(&self) -> u8 {
+        self.os_abi
+    }
+
+    pub fn get_abi_version(&self) -> u8 {
+        self.abi_version
+    }
+
+    pub fn get_type(&self) -> u16 {
+        self.e_type
+    }
+
+    pub fn get_type_as_enum(&self) -> ElfType {
+        ElfType::from_value(self.e_type)
+    }
+
+    pub fn get_machine(&self) -> u16 {
+        self.e_machine
+    }
+
+    pub fn get_machine_as_enum(&self) -> ElfMachine {
+        ElfMachine::from_value(self.e_machine)
+    }
+
+    pub fn get_version(&self) -> u32 {
+        self.e_version
+    }
+
+    pub fn get_entry(&self) -> u64 {
+        self.e_entry
+    }
+
+    pub fn get_phoff(&self) -> u64 {
+        self.e_phoff
+    }
+
+    pub fn get_shoff(&self) -> u64 {
+        self.e_shoff
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.e_flags
+    }
+
+    pub fn get_ehsize(&self) -> u16 {
+        self.e_ehsize
+    }
+
+    pub fn get_phentsize(&self) -> u16 {
+        self.e_phentsize
+    }
+
+    pub fn get_phnum(&self) -> u16 {
+        self.e_phnum
+    }
+
+    pub fn get_shentsize(&self) -> u16 {
+        self.e_shentsize
+    }
+
+    pub fn get_shnum(&self) -> u16 {
+        self.e_shnum
+    }
+
+    pub fn get_shstrndx(&self) -> u16 {
+        self.e_shstrndx
+    }
+
+    pub fn get_os_abi_name(&self) -> String {
+        match self.os_abi {
+            0 => String::from("UNIX - System V"),
+            1 => String::from("UNIX - HP-UX"),
+            2 => String::from("UNIX - NetBSD"),
+            3 => String::from("UNIX - GNU"),
+            6 => String::from("UNIX - Solaris"),
+            7 => String::from("UNIX - AIX"),
+            8 => String::from("UNIX - IRIX"),
+            9 => String::from("UNIX - FreeBSD"),
+            12 => String::from("UNIX - OpenBSD"),
+            97 => String::from("ARM"),
+            255 => String::from("Standalone App"),
+            t => format!("<unknown: {:x}>", t)
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<ElfHeader, ButylError> {
+        if read_bytes(bytes, 0, ELF_MAGIC.len())? != ELF_MAGIC {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let class: ElfClass = match ElfClass::from_value(read_u8(bytes, 4)?) {
+            Some(t) => t,
+            None => return Err(ButylError::MalformedDataError)
+        };
+        let data: ElfData = match ElfData::from_value(read_u8(bytes, 5)?) {
+            Some(t) => t,
+            None => return Err(ButylError::MalformedDataError)
+        };
+        let encoding: ElfEncoding = ElfEncoding::new(class, data);
+
+        /* only the three address-sized fields differ between classes */
+        let n: usize = encoding.get_address_len();
+
+        Ok(ElfHeader {
+            encoding,
+            ident_version: read_u8(bytes, 6)?,
+            os_abi: read_u8(bytes, 7)?,
+            abi_version: read_u8(bytes, 8)?,
+            e_type: encoding.read_u16(bytes, 16)?,
+            e_machine: encoding.read_u16(bytes, 18)?,
+            e_version: encoding.read_u32(bytes, 20)?,
+            e_entry: encoding.read_address(bytes, 24)?,
+            e_phoff: encoding.read_address(bytes, 24 + n)?,
+            e_shoff: encoding.read_address(bytes, 24 + 2 * n)?,
+            e_flags: encoding.read_u32(bytes, 24 + 3 * n)?,
+            e_ehsize: encoding.read_u16(bytes, 28 + 3 * n)?,
+            e_phentsize: encoding.read_u16(bytes, 30 + 3 * n)?,
+            e_phnum: encoding.read_u16(bytes, 32 + 3 * n)?,
+            e_shentsize: encoding.read_u16(bytes, 34 + 3 * n)?,
+            e_shnum: encoding.read_u16(bytes, 36 + 3 * n)?,
+            e_shstrndx: encoding.read_u16(bytes, 38 + 3 * n)?
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ProgramHeader {
+    p_type: u32,        /* segment type */
+    p_flags: u32,       /* PF_R, PF_W and PF_X */
+    p_offset: u64,      /* file offset of the segment */
+    p_vaddr: u64,       /* VA of the segment */
+    p_paddr: u64,       /* physical address, where relevant */
+    p_filesz: u64,      /* bytes in the file */
+    p_memsz: u64,       /* bytes in memory */
+    p_align: u64        /* alignment in memory and the file */
+}
+
+impl ProgramHeader {
+    pub fn get_type(&self) -> u32 {
+        self.p_type
+    }
+
+    pub fn get_type_as_enum(&self) -> ProgramHeaderType {
+        ProgramHeaderType::from_value(self.p_type)
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.p_flags
+    }
+
+    pub fn get_flags_as_string(&self) -> String {
+        format!("{}{}{}",
+            if self.p_flags & PF_R != 0 { "R" } else { " " },
+            if self.p_flags & PF_W != 0 { "W" } else { " " },
+            if self.p_flags & PF_X != 0 { "E" } else { " " })
+    }
+
+    pub fn get_offset(&self) -> u64 {
+        self.p_offset
+    }
+
+    pub fn get_vaddr(&self) -> u64 {
+        self.p_vaddr
+    }
+
+    pub fn get_paddr(&self) -> u64 {
+        self.p_paddr
+    }
+
+    pub fn get_filesz(&self) -> u64 {
+        self.p_filesz
+    }
+
+    pub fn get_memsz(&self) -> u64 {
+        self.p_memsz
+    }
+
+    pub fn get_align(&self) -> u64 {
+        self.p_align
+    }
+
+    pub fn contains_vaddr(&self, vaddr: u64) -> bool {
+        vaddr >= self.p_vaddr && vaddr - self.p_vaddr < self.p_memsz
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_bytes(bytes: &[u8], encoding: ElfEncoding) ->
+        Result<ProgramHeader, ButylError> {
+        let mut program_header: ProgramHeader = ProgramHeader::default();
+
+        program_header.p_type = encoding.read_u32(bytes, 0)?;
+
+        /* ELF64 moves p_flags up to keep the wider fields aligned */
+        if encoding.is_64_bit() {
+            program_header.p_flags = encoding.read_u32(bytes, 4)?;
+            program_header.p_offset = encoding.read_u64(bytes, 8)?;
+            program_header.p_vaddr = encoding.read_u64(bytes, 16)?;
+            program_header.p_paddr = encoding.read_u64(bytes, 24)?;
+            program_header.p_filesz = encoding.read_u64(bytes, 32)?;
+            program_header.p_memsz = encoding.read_u64(bytes, 40)?;
+            program_header.p_align = encoding.read_u64(bytes, 48)?;
+        } else {
+            program_header.p_offset = encoding.read_u32(bytes, 4)? as u64;
+            program_header.p_vaddr = encoding.read_u32(bytes, 8)? as u64;
+            program_header.p_paddr = encoding.read_u32(bytes, 12)? as u64;
+            program_header.p_filesz = encoding.read_u32(bytes, 16)? as u64;
+            program_header.p_memsz = encoding.read_u32(bytes, 20)? as u64;
+            program_header.p_flags = encoding.read_u32(bytes, 24)?;
+            program_header.p_align = encoding.read_u32(bytes, 28)? as u64;
+        }
+
+        Ok(program_header)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SectionHeader {
+    name: String,       /* resolved from the section name string table */
+    sh_name: u32,       /* offset of the name in that table */
+    sh_type: u32,       /* section type */
+    sh_flags: u64,      /* SHF_* flags */
+    sh_addr: u64,       /* VA once loaded, if allocated */
+    sh_offset: u64,     /* file offset of the contents */
+    sh_size: u64,       /* size of the contents */
+    sh_link: u32,       /* type-dependent associated section */
+    sh_info: u32,       /* type-dependent extra information */
+    sh_addralign: u64,  /* alignment of the contents */
+    sh_entsize: u64     /* size of each entry, for tables */
+}
+
+impl SectionHeader {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_name_offset(&self) -> u32 {
+        self.sh_name
+    }
+
+    pub fn get_type(&self) -> u32 {
+        self.sh_type
+    }
+
+    pub fn get_type_as_enum(&self) -> SectionType {
+        SectionType::from_value(self.sh_type)
+    }
+
+    pub fn get_flags(&self) -> u64 {
+        self.sh_flags
+    }
+
+    /* the single-letter key used by readelf */
+    pub fn get_flags_as_string(&self) -> String {
+        [
+            (SHF_WRITE, 'W'), (SHF_ALLOC, 'A'), (SHF_EXECINSTR, 'X'),
+            (SHF_MERGE, 'M'), (SHF_STRINGS, 'S'), (SHF_INFO_LINK, 'I'),
+            (SHF_LINK_ORDER, 'L'), (SHF_OS_NONCONFORMING, 'O'),
+            (SHF_GROUP, 'G'), (SHF_TLS, 'T'), (SHF_COMPRESSED, 'C'),
+            (SHF_EXCLUDE, 'E')
+        ].iter()
+            .filter(|(flag, _)| self.sh_flags & flag != 0)
+            .map(|(_, t)| *t)
+            .collect()
+    }
+
+    pub fn get_addr(&self) -> u64 {
+        self.sh_addr
+    }
+
+    pub fn get_offset(&self) -> u64 {
+        self.sh_offset
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.sh_size
+    }
+
+    pub fn get_link(&self) -> u32 {
+        self.sh_link
+    }
+
+    pub fn get_info(&self) -> u32 {
+        self.sh_info
+    }
+
+    pub fn get_addralign(&self) -> u64 {
+        self.sh_addralign
+    }
+
+    pub fn get_entsize(&self) -> u64 {
+        self.sh_entsize
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: ElfEncoding) ->
+        Result<SectionHeader, ButylError> {
+        let mut section_header: SectionHeader = SectionHeader::default();
+        let n: usize = encoding.get_address_len();
+
+        section_header.sh_name = encoding.read_u32(bytes, 0)?;
+        section_header.sh_type = encoding.read_u32(bytes, 4)?;
+        section_header.sh_flags = encoding.read_address(bytes, 8)?;
+        section_header.sh_addr = encoding.read_address(bytes, 8 + n)?;
+        section_header.sh_offset = encoding.read_address(bytes, 8 + 2 * n)?;
+        section_header.sh_size = encoding.read_address(bytes, 8 + 3 * n)?;
+        section_header.sh_link = encoding.read_u32(bytes, 8 + 4 * n)?;
+        section_header.sh_info = encoding.read_u32(bytes, 12 + 4 * n)?;
+        section_header.sh_addralign =
+            encoding.read_address(bytes, 16 + 4 * n)?;
+        section_header.sh_entsize = encoding.read_address(bytes, 16 + 5 * n)?;
+
+        Ok(section_header)
+    }
+}
+
+#[derive(Debug)]
+pub struct ElfFile<'a> {
+    header: ElfHeader,
+    program_headers: Vec<ProgramHeader>,
+    section_headers: Vec<SectionHeader>,
+    shstrndx: usize,
+    data: &'a[u8]
+}
+
+impl<'a> ElfFile<'a> {
+    pub fn get_header(&self) -> &ElfHeader {
+        &self.header
+    }
+
+    pub fn get_encoding(&self) -> ElfEncoding {
+        self.header.get_encoding()
+    }
+
+    pub fn get_program_headers(&self) -> &[ProgramHeader] {
+        &self.program_headers
+    }
+
+    pub fn get_section_headers(&self) -> &[SectionHeader] {
+        &self.section_headers
+    }
+
+    /* the real e_shstrndx, after any SHN_XINDEX escape */
+    pub fn get_shstrndx(&self) -> usize {
+        self.shstrndx
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn get_section(&self, index: usize) ->
+        Result<&SectionHeader, ButylError> {
+        match self.section_headers.get(index) {
+            Some(t) => Ok(t),
+            None => Err(ButylError::NotFoundError)
+        }
+    }
+
+    pub fn get_section_by_name(&self, name: &str) -> Option<&SectionHeader> {
+        self.section_headers.iter().find(|t| t.get_name() == name)
+    }
+
+    /* the contents of a section as stored in the file; SHT_NOBITS sections
+     * occupy no space there */
+    pub fn get_section_data(&self, section: &SectionHeader) ->
+        Result<&'a[u8], ButylError> {
+        if section.get_type_as_enum() == SectionType::Nobits {
+            return Ok(&[]);
+        }
+
+        read_bytes(self.data, section.get_offset() as usize,
+            section.get_size() as usize)
+    }
+
+    pub fn get_segment_data(&self, segment: &ProgramHeader) ->
+        Result<&'a[u8], ButylError> {
+        read_bytes(self.data, segment.get_offset() as usize,
+            segment.get_filesz() as usize)
+    }
+
+    /* reads a NUL-terminated string from the string table section at
+     * `index` */
+    pub fn get_string(&self, index: usize, offset: usize) ->
+        Result<String, ButylError> {
+        read_cstring(self.get_section_data(self.get_section(index)?)?,
+            offset)
+    }
+
+    pub fn vaddr_to_offset(&self, vaddr: u64) -> Result<usize, ButylError> {
+        self.program_headers.iter()
+            .filter(|t| t.get_type_as_enum() == ProgramHeaderType::Load)
+            .find(|t| vaddr >= t.get_vaddr() &&
+                vaddr - t.get_vaddr() < t.get_filesz())
+            .map(|t| (vaddr - t.get_vaddr() + t.get_offset()) as usize)
+            .ok_or(ButylError::UnmappedAddressError)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<ElfFile<'_>, ButylError> {
+        let header: ElfHeader = ElfHeader::from_bytes(data)?;
+        let encoding: ElfEncoding = header.get_encoding();
+
+        let (phentsize, shentsize): (usize, usize) = if encoding.is_64_bit() {
+            (ELF_PROGRAM_HEADER_LEN_64, ELF_SECTION_HEADER_LEN_64)
+        } else {
+            (ELF_PROGRAM_HEADER_LEN_32, ELF_SECTION_HEADER_LEN_32)
+        };
+
+        /* counts that don't fit in the header are kept in section 0 */
+        let initial: Option<SectionHeader> = match header.get_shoff() {
+            0 => None,
+            t => Some(SectionHeader::from_bytes(read_bytes(data, t as usize,
+                shentsize)?, encoding)?)
+        };
+
+        let shnum: usize = match (header.get_shnum(), &initial) {
+            (0, Some(t)) => t.get_size() as usize,
+            (_, None) => 0,
+            (t, _) => t as usize
+        };
+        let phnum: usize = match (header.get_phnum(), &initial) {
+            (PN_XNUM, Some(t)) => t.get_info() as usize,
+            (t, _) => t as usize
+        };
+        let shstrndx: usize = match (header.get_shstrndx(), &initial) {
+            (SHN_XINDEX, Some(t)) => t.get_link() as usize,
+            (t, _) => t as usize
+        };
+
+        if (phnum > 0 && (header.get_phentsize() as usize) < phentsize) ||
+            (shnum > 0 && (header.get_shentsize() as usize) < shentsize) {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let mut program_headers: Vec<ProgramHeader> = Vec::new();
+
+        for i in 0..phnum {
+            let offset: usize = header.get_phoff() as usize +
+                i * header.get_phentsize() as usize;
+            program_headers.push(ProgramHeader::from_bytes(
+                read_bytes(data, offset, phentsize)?, encoding)?);
+        }
+
+        let mut section_headers: Vec<SectionHeader> = Vec::new();
+
+        for i in 0..shnum {
+            let offset: usize = header.get_shoff() as usize +
+                i * header.get_shentsize() as usize;
+            section_headers.push(SectionHeader::from_bytes(
+                read_bytes(data, offset, shentsize)?, encoding)?);
+        }
+
+        let mut elf_file: ElfFile = ElfFile {
+            header,
+            program_headers,
+            section_headers,
+            shstrndx,
+            data
+        };
+
+        /* names are left empty if the string table itself is unusable */
+        if shstrndx != SHN_UNDEF as usize && shstrndx < shnum {
+            for i in 0..shnum {
+                let name: String = elf_file.get_string(shstrndx,
+                    elf_file.section_headers[i].get_name_offset() as usize)
+                    .unwrap_or_default();
+                elf_file.section_headers[i].name = name;
+            }
+        }
+
+        Ok(elf_file)
+    }
+}
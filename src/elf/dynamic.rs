@@ -0,0 +1,403 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::elf::{ElfFile, ElfEncoding, ProgramHeaderType, SectionType};
+use crate::utils::*;
+
+pub const DF_ORIGIN: u64 = 0x01;
+pub const DF_SYMBOLIC: u64 = 0x02;
+pub const DF_TEXTREL: u64 = 0x04;
+pub const DF_BIND_NOW: u64 = 0x08;
+pub const DF_STATIC_TLS: u64 = 0x10;
+
+pub const DF_1_NOW: u64 = 0x0000_0001;
+pub const DF_1_GLOBAL: u64 = 0x0000_0002;
+pub const DF_1_GROUP: u64 = 0x0000_0004;
+pub const DF_1_NODELETE: u64 = 0x0000_0008;
+pub const DF_1_LOADFLTR: u64 = 0x0000_0010;
+pub const DF_1_INITFIRST: u64 = 0x0000_0020;
+pub const DF_1_NOOPEN: u64 = 0x0000_0040;
+pub const DF_1_ORIGIN: u64 = 0x0000_0080;
+pub const DF_1_DIRECT: u64 = 0x0000_0100;
+pub const DF_1_INTERPOSE: u64 = 0x0000_0400;
+pub const DF_1_NODEFLIB: u64 = 0x0000_0800;
+pub const DF_1_NODUMP: u64 = 0x0000_1000;
+pub const DF_1_CONFALT: u64 = 0x0000_2000;
+pub const DF_1_ENDFILTEE: u64 = 0x0000_4000;
+pub const DF_1_DISPRELDNE: u64 = 0x0000_8000;
+pub const DF_1_DISPRELPND: u64 = 0x0001_0000;
+pub const DF_1_NODIRECT: u64 = 0x0002_0000;
+pub const DF_1_IGNMULDEF: u64 = 0x0004_0000;
+pub const DF_1_NOKSYMS: u64 = 0x0008_0000;
+pub const DF_1_NOHDR: u64 = 0x0010_0000;
+pub const DF_1_EDITED: u64 = 0x0020_0000;
+pub const DF_1_NORELOC: u64 = 0x0040_0000;
+pub const DF_1_SYMINTPOSE: u64 = 0x0080_0000;
+pub const DF_1_GLOBAUDIT: u64 = 0x0100_0000;
+pub const DF_1_SINGLETON: u64 = 0x0200_0000;
+pub const DF_1_STUB: u64 = 0x0400_0000;
+pub const DF_1_PIE: u64 = 0x0800_0000;
+
+const DF_NAMES: &[(u64, &str)] = &[
+    (DF_ORIGIN, "ORIGIN"), (DF_SYMBOLIC, "SYMBOLIC"), (DF_TEXTREL, "TEXTREL"),
+    (DF_BIND_NOW, "BIND_NOW"), (DF_STATIC_TLS, "STATIC_TLS")
+];
+
+const DF_1_NAMES: &[(u64, &str)] = &[
+    (DF_1_NOW, "NOW"), (DF_1_GLOBAL, "GLOBAL"), (DF_1_GROUP, "GROUP"),
+    (DF_1_NODELETE, "NODELETE"), (DF_1_LOADFLTR, "LOADFLTR"),
+    (DF_1_INITFIRST, "INITFIRST"), (DF_1_NOOPEN, "NOOPEN"),
+    (DF_1_ORIGIN, "ORIGIN"), (DF_1_DIRECT, "DIRECT"),
+    (DF_1_INTERPOSE, "INTERPOSE"), (DF_1_NODEFLIB, "NODEFLIB"),
+    (DF_1_NODUMP, "NODUMP"), (DF_1_CONFALT, "CONFALT"),
+    (DF_1_ENDFILTEE, "ENDFILTEE"), (DF_1_DISPRELDNE, "DISPRELDNE"),
+    (DF_1_DISPRELPND, "DISPRELPND"), (DF_1_NODIRECT, "NODIRECT"),
+    (DF_1_IGNMULDEF, "IGNMULDEF"), (DF_1_NOKSYMS, "NOKSYMS"),
+    (DF_1_NOHDR, "NOHDR"), (DF_1_EDITED, "EDITED"),
+    (DF_1_NORELOC, "NORELOC"), (DF_1_SYMINTPOSE, "SYMINTPOSE"),
+    (DF_1_GLOBAUDIT, "GLOBAUDIT"), (DF_1_SINGLETON, "SINGLETON"),
+    (DF_1_STUB, "STUB"), (DF_1_PIE, "PIE")
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DynamicTag {
+    Null,
+    Needed,
+    PltRelSz,
+    PltGot,
+    Hash,
+    StrTab,
+    SymTab,
+    Rela,
+    RelaSz,
+    RelaEnt,
+    StrSz,
+    SymEnt,
+    Init,
+    Fini,
+    Soname,
+    Rpath,
+    Symbolic,
+    Rel,
+    RelSz,
+    RelEnt,
+    PltRel,
+    Debug,
+    TextRel,
+    JmpRel,
+    BindNow,
+    InitArray,
+    FiniArray,
+    InitArraySz,
+    FiniArraySz,
+    Runpath,
+    Flags,
+    PreinitArray,
+    PreinitArraySz,
+    SymtabShndx,
+    RelrSz,
+    Relr,
+    RelrEnt,
+    GnuHash,
+    VerSym,
+    RelaCount,
+    RelCount,
+    Flags1,
+    VerDef,
+    VerDefNum,
+    VerNeed,
+    VerNeedNum,
+    Other(u64)
+}
+
+impl DynamicTag {
+    pub fn to_value(tag: DynamicTag) -> u64 {
+        match tag {
+            DynamicTag::Null => 0,
+            DynamicTag::Needed => 1,
+            DynamicTag::PltRelSz => 2,
+            DynamicTag::PltGot => 3,
+            DynamicTag::Hash => 4,
+            DynamicTag::StrTab => 5,
+            DynamicTag::SymTab => 6,
+            DynamicTag::Rela => 7,
+            DynamicTag::RelaSz => 8,
+            DynamicTag::RelaEnt => 9,
+            DynamicTag::StrSz => 10,
+            DynamicTag::SymEnt => 11,
+            DynamicTag::Init => 12,
+            DynamicTag::Fini => 13,
+            DynamicTag::Soname => 14,
+            DynamicTag::Rpath => 15,
+            DynamicTag::Symbolic => 16,
+            DynamicTag::Rel => 17,
+            DynamicTag::RelSz => 18,
+            DynamicTag::RelEnt => 19,
+            DynamicTag::PltRel => 20,
+            DynamicTag::Debug => 21,
+            DynamicTag::TextRel => 22,
+            DynamicTag::JmpRel => 23,
+            DynamicTag::BindNow => 24,
+            DynamicTag::InitArray => 25,
+            DynamicTag::FiniArray => 26,
+            DynamicTag::InitArraySz => 27,
+            DynamicTag::FiniArraySz => 28,
+            DynamicTag::Runpath => 29,
+            DynamicTag::Flags => 30,
+            DynamicTag::PreinitArray => 32,
+            DynamicTag::PreinitArraySz => 33,
+            DynamicTag::SymtabShndx => 34,
+            DynamicTag::RelrSz => 35,
+            DynamicTag::Relr => 36,
+            DynamicTag::RelrEnt => 37,
+            DynamicTag::GnuHash => 0x6fff_fef5,
+            DynamicTag::VerSym => 0x6fff_fff0,
+            DynamicTag::RelaCount => 0x6fff_fff9,
+            DynamicTag::RelCount => 0x6fff_fffa,
+            DynamicTag::Flags1 => 0x6fff_fffb,
+            DynamicTag::VerDef => 0x6fff_fffc,
+            DynamicTag::VerDefNum => 0x6fff_fffd,
+            DynamicTag::VerNeed => 0x6fff_fffe,
+            DynamicTag::VerNeedNum => 0x6fff_ffff,
+            DynamicTag::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u64) -> DynamicTag {
+        match value {
+            0 => DynamicTag::Null,
+            1 => DynamicTag::Needed,
+            2 => DynamicTag::PltRelSz,
+            3 => DynamicTag::PltGot,
+            4 => DynamicTag::Hash,
+            5 => DynamicTag::StrTab,
+            6 => DynamicTag::SymTab,
+            7 => DynamicTag::Rela,
+            8 => DynamicTag::RelaSz,
+            9 => DynamicTag::RelaEnt,
+            10 => DynamicTag::StrSz,
+            11 => DynamicTag::SymEnt,
+            12 => DynamicTag::Init,
+            13 => DynamicTag::Fini,
+            14 => DynamicTag::Soname,
+            15 => DynamicTag::Rpath,
+            16 => DynamicTag::Symbolic,
+            17 => DynamicTag::Rel,
+            18 => DynamicTag::RelSz,
+            19 => DynamicTag::RelEnt,
+            20 => DynamicTag::PltRel,
+            21 => DynamicTag::Debug,
+            22 => DynamicTag::TextRel,
+            23 => DynamicTag::JmpRel,
+            24 => DynamicTag::BindNow,
+            25 => DynamicTag::InitArray,
+            26 => DynamicTag::FiniArray,
+            27 => DynamicTag::InitArraySz,
+            28 => DynamicTag::FiniArraySz,
+            29 => DynamicTag::Runpath,
+            30 => DynamicTag::Flags,
+            32 => DynamicTag::PreinitArray,
+            33 => DynamicTag::PreinitArraySz,
+            34 => DynamicTag::SymtabShndx,
+            35 => DynamicTag::RelrSz,
+            36 => DynamicTag::Relr,
+            37 => DynamicTag::RelrEnt,
+            0x6fff_fef5 => DynamicTag::GnuHash,
+            0x6fff_fff0 => DynamicTag::VerSym,
+            0x6fff_fff9 => DynamicTag::RelaCount,
+            0x6fff_fffa => DynamicTag::RelCount,
+            0x6fff_fffb => DynamicTag::Flags1,
+            0x6fff_fffc => DynamicTag::VerDef,
+            0x6fff_fffd => DynamicTag::VerDefNum,
+            0x6fff_fffe => DynamicTag::VerNeed,
+            0x6fff_ffff => DynamicTag::VerNeedNum,
+            t => DynamicTag::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            DynamicTag::Null => String::from("NULL"),
+            DynamicTag::Needed => String::from("NEEDED"),
+            DynamicTag::PltRelSz => String::from("PLTRELSZ"),
+            DynamicTag::PltGot => String::from("PLTGOT"),
+            DynamicTag::Hash => String::from("HASH"),
+            DynamicTag::StrTab => String::from("STRTAB"),
+            DynamicTag::SymTab => String::from("SYMTAB"),
+            DynamicTag::Rela => String::from("RELA"),
+            DynamicTag::RelaSz => String::from("RELASZ"),
+            DynamicTag::RelaEnt => String::from("RELAENT"),
+            DynamicTag::StrSz => String::from("STRSZ"),
+            DynamicTag::SymEnt => String::from("SYMENT"),
+            DynamicTag::Init => String::from("INIT"),
+            DynamicTag::Fini => String::from("FINI"),
+            DynamicTag::Soname => String::from("SONAME"),
+            DynamicTag::Rpath => String::from("RPATH"),
+            DynamicTag::Symbolic => String::from("SYMBOLIC"),
+            DynamicTag::Rel => String::from("REL"),
+            DynamicTag::RelSz => String::from("RELSZ"),
+            DynamicTag::RelEnt => String::from("RELENT"),
+            DynamicTag::PltRel => String::from("PLTREL"),
+            DynamicTag::Debug => String::from("DEBUG"),
+            DynamicTag::TextRel => String::from("TEXTREL"),
+            DynamicTag::JmpRel => String::from("JMPREL"),
+            DynamicTag::BindNow => String::from("BIND_NOW"),
+            DynamicTag::InitArray => String::from("INIT_ARRAY"),
+            DynamicTag::FiniArray => String::from("FINI_ARRAY"),
+            DynamicTag::InitArraySz => String::from("INIT_ARRAYSZ"),
+            DynamicTag::FiniArraySz => String::from("FINI_ARRAYSZ"),
+            DynamicTag::Runpath => String::from("RUNPATH"),
+            DynamicTag::Flags => String::from("FLAGS"),
+            DynamicTag::PreinitArray => String::from("PREINIT_ARRAY"),
+            DynamicTag::PreinitArraySz => String::from("PREINIT_ARRAYSZ"),
+            DynamicTag::SymtabShndx => String::from("SYMTAB_SHNDX"),
+            DynamicTag::RelrSz => String::from("RELRSZ"),
+            DynamicTag::Relr => String::from("RELR"),
+            DynamicTag::RelrEnt => String::from("RELRENT"),
+            DynamicTag::GnuHash => String::from("GNU_HASH"),
+            DynamicTag::VerSym => String::from("VERSYM"),
+            DynamicTag::RelaCount => String::from("RELACOUNT"),
+            DynamicTag::RelCount => String::from("RELCOUNT"),
+            DynamicTag::Flags1 => String::from("FLAGS_1"),
+            DynamicTag::VerDef => String::from("VERDEF"),
+            DynamicTag::VerDefNum => String::from("VERDEFNUM"),
+            DynamicTag::VerNeed => String::from("VERNEED"),
+            DynamicTag::VerNeedNum => String::from("VERNEEDNUM"),
+            DynamicTag::Other(t) => format!("{:#x}", t)
+        }
+    }
+
+    /* tags whose value is an offset into the dynamic string table */
+    pub fn is_string(&self) -> bool {
+        matches!(self, DynamicTag::Needed | DynamicTag::Soname |
+            DynamicTag::Rpath | DynamicTag::Runpath)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DynamicEntry {
+    d_tag: u64,         /* DT_* tag */
+    d_val: u64          /* value or address, depending on the tag */
+}
+
+impl DynamicEntry {
+    pub fn get_tag(&self) -> u64 {
+        self.d_tag
+    }
+
+    pub fn get_tag_as_enum(&self) -> DynamicTag {
+        DynamicTag::from_value(self.d_tag)
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.d_val
+    }
+
+    /* names the set bits of DT_FLAGS or DT_FLAGS_1 */
+    pub fn get_flags_as_strings(&self) -> Vec<String> {
+        let names: &[(u64, &str)] = match self.get_tag_as_enum() {
+            DynamicTag::Flags => DF_NAMES,
+            DynamicTag::Flags1 => DF_1_NAMES,
+            _ => return Vec::new()
+        };
+
+        names.iter()
+            .filter(|(flag, _)| self.d_val & flag != 0)
+            .map(|(_, name)| String::from(*name))
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: ElfEncoding) ->
+        Result<DynamicEntry, ButylError> {
+        Ok(DynamicEntry {
+            d_tag: encoding.read_address(bytes, 0)?,
+            d_val: encoding.read_address(bytes, encoding.get_address_len())?
+        })
+    }
+}
+
+impl<'a> ElfFile<'a> {
+    /* the entries up to and including the terminating DT_NULL, taken from
+     * the SHT_DYNAMIC section or, failing that, the PT_DYNAMIC segment */
+    pub fn get_dynamic_entries(&self) ->
+        Result<Vec<DynamicEntry>, ButylError> {
+        let encoding: ElfEncoding = self.get_encoding();
+        let data: &[u8] = match self.get_section_headers().iter()
+            .find(|t| t.get_type_as_enum() == SectionType::Dynamic) {
+            Some(t) => self.get_section_data(t)?,
+            None => match self.get_program_headers().iter()
+                .find(|t| t.get_type_as_enum() == ProgramHeaderType::Dynamic) {
+                Some(t) => self.get_segment_data(t)?,
+                None => return Ok(Vec::new())
+            }
+        };
+
+        let mut entries: Vec<DynamicEntry> = Vec::new();
+
+        for bytes in data.chunks_exact(encoding.get_address_len() * 2) {
+            let entry: DynamicEntry =
+                DynamicEntry::from_bytes(bytes, encoding)?;
+            let tag: DynamicTag = entry.get_tag_as_enum();
+            entries.push(entry);
+
+            if tag == DynamicTag::Null {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /* the table named by DT_STRTAB, falling back to the one the .dynamic
+     * section links to when that address isn't mapped by any segment */
+    pub fn get_dynamic_string_table(&self) -> Result<&'a[u8], ButylError> {
+        let entries: Vec<DynamicEntry> = self.get_dynamic_entries()?;
+        let find = |tag: DynamicTag| entries.iter()
+            .find(|t| t.get_tag_as_enum() == tag)
+            .map(|t| t.get_value());
+
+        if let (Some(address), Some(size)) =
+            (find(DynamicTag::StrTab), find(DynamicTag::StrSz)) {
+            if let Ok(offset) = self.vaddr_to_offset(address) {
+                return read_bytes(self.get_data(), offset, size as usize);
+            }
+        }
+
+        match self.get_section_headers().iter()
+            .find(|t| t.get_type_as_enum() == SectionType::Dynamic) {
+            Some(t) => self.get_section_data(
+                self.get_section(t.get_link() as usize)?),
+            None => Err(ButylError::NotFoundError)
+        }
+    }
+
+    pub fn get_dynamic_string(&self, entry: &DynamicEntry) ->
+        Result<String, ButylError> {
+        read_cstring(self.get_dynamic_string_table()?,
+            entry.get_value() as usize)
+    }
+
+    pub fn get_needed(&self) -> Result<Vec<String>, ButylError> {
+        self.get_dynamic_strings(DynamicTag::Needed)
+    }
+
+    pub fn get_soname(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_dynamic_strings(DynamicTag::Soname)?.into_iter().next())
+    }
+
+    pub fn get_rpath(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_dynamic_strings(DynamicTag::Rpath)?.into_iter().next())
+    }
+
+    pub fn get_runpath(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_dynamic_strings(DynamicTag::Runpath)?.into_iter().next())
+    }
+
+    fn get_dynamic_strings(&self, tag: DynamicTag) ->
+        Result<Vec<String>, ButylError> {
+        self.get_dynamic_entries()?.iter()
+            .filter(|t| t.get_tag_as_enum() == tag)
+            .map(|t| self.get_dynamic_string(t))
+            .collect()
+    }
+}
@@ -0,0 +1,331 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::elf::{ElfFile, ElfEncoding, ElfMachine, ProgramHeader,
+    ProgramHeaderType, SectionHeader, SectionType};
+use crate::utils::*;
+
+pub const ELF_NOTE_HEADER_LEN: usize = 12;
+
+pub const NT_GNU_ABI_TAG: u32 = 1;
+pub const NT_GNU_HWCAP: u32 = 2;
+pub const NT_GNU_BUILD_ID: u32 = 3;
+pub const NT_GNU_GOLD_VERSION: u32 = 4;
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+pub const NT_GO_BUILD_ID: u32 = 4;
+
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+pub const GNU_PROPERTY_X86_FEATURE_2_NEEDED: u32 = 0xc000_8001;
+pub const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xc000_8002;
+pub const GNU_PROPERTY_X86_FEATURE_2_USED: u32 = 0xc001_0001;
+pub const GNU_PROPERTY_X86_ISA_1_USED: u32 = 0xc001_0002;
+
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 0x1;
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 0x2;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 0x1;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: u32 = 0x2;
+
+const ABI_TAG_OS_NAMES: &[&str] = &[
+    "Linux", "Hurd", "Solaris", "FreeBSD", "NetBSD", "Syllable", "NaCl"
+];
+
+const X86_FEATURE_1_NAMES: &[&str] = &["IBT", "SHSTK"];
+const AARCH64_FEATURE_1_NAMES: &[&str] = &["BTI", "PAC", "GCS"];
+const X86_FEATURE_2_NAMES: &[&str] = &[
+    "x86", "x87", "MMX", "XMM", "YMM", "ZMM", "FXSR", "XSAVE", "XSAVEOPT",
+    "XSAVEC", "TMM", "MASK"
+];
+const X86_ISA_1_NAMES: &[&str] = &[
+    "x86-64-baseline", "x86-64-v2", "x86-64-v3", "x86-64-v4"
+];
+
+/* names each set bit from its position in `names` */
+fn get_bit_names(value: u32, names: &[&str]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    for bit in 0..32 {
+        if value & (1 << bit) == 0 {
+            continue;
+        }
+
+        match names.get(bit) {
+            Some(t) => parts.push(String::from(*t)),
+            None => parts.push(format!("<unknown: {:x}>", 1u32 << bit))
+        }
+    }
+
+    match parts.is_empty() {
+        true => String::from("<None>"),
+        false => parts.join(", ")
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct GnuProperty {
+    pr_type: u32,       /* GNU_PROPERTY_* */
+    pr_data: Vec<u8>
+}
+
+impl GnuProperty {
+    pub fn get_type(&self) -> u32 {
+        self.pr_type
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.pr_data
+    }
+
+    /* the processor-specific range means different things per machine */
+    pub fn get_description(&self, machine: ElfMachine,
+        encoding: ElfEncoding) -> String {
+        let value: Option<u32> = encoding.read_u32(&self.pr_data, 0).ok();
+
+        match (machine, self.pr_type, value) {
+            (_, GNU_PROPERTY_STACK_SIZE, _) =>
+                match encoding.read_address(&self.pr_data, 0) {
+                    Ok(t) => format!("stack size: {:#x}", t),
+                    Err(_e) => String::from("stack size: <corrupt>")
+                },
+            (_, GNU_PROPERTY_NO_COPY_ON_PROTECTED, _) =>
+                String::from("no copy on protected"),
+            (ElfMachine::X86_64, GNU_PROPERTY_X86_FEATURE_1_AND, Some(t)) |
+            (ElfMachine::I386, GNU_PROPERTY_X86_FEATURE_1_AND, Some(t)) =>
+                format!("x86 feature: {}",
+                    get_bit_names(t, X86_FEATURE_1_NAMES)),
+            (ElfMachine::X86_64, GNU_PROPERTY_X86_FEATURE_2_NEEDED, Some(t)) |
+            (ElfMachine::I386, GNU_PROPERTY_X86_FEATURE_2_NEEDED, Some(t)) =>
+                format!("x86 feature needed: {}",
+                    get_bit_names(t, X86_FEATURE_2_NAMES)),
+            (ElfMachine::X86_64, GNU_PROPERTY_X86_FEATURE_2_USED, Some(t)) |
+            (ElfMachine::I386, GNU_PROPERTY_X86_FEATURE_2_USED, Some(t)) =>
+                format!("x86 feature used: {}",
+                    get_bit_names(t, X86_FEATURE_2_NAMES)),
+            (ElfMachine::X86_64, GNU_PROPERTY_X86_ISA_1_NEEDED, Some(t)) |
+            (ElfMachine::I386, GNU_PROPERTY_X86_ISA_1_NEEDED, Some(t)) =>
+                format!("x86 ISA needed: {}",
+                    get_bit_names(t, X86_ISA_1_NAMES)),
+            (ElfMachine::X86_64, GNU_PROPERTY_X86_ISA_1_USED, Some(t)) |
+            (ElfMachine::I386, GNU_PROPERTY_X86_ISA_1_USED, Some(t)) =>
+                format!("x86 ISA used: {}",
+                    get_bit_names(t, X86_ISA_1_NAMES)),
+            (ElfMachine::AArch64, GNU_PROPERTY_AARCH64_FEATURE_1_AND,
+                Some(t)) => format!("AArch64 feature: {}",
+                    get_bit_names(t, AARCH64_FEATURE_1_NAMES)),
+            (_, t, _) => format!("<unknown type {:#x}, datasz: {:#x}>", t,
+                self.pr_data.len())
+        }
+    }
+
+    /* tests a GNU_PROPERTY_*_FEATURE_1_* bit, such as IBT or BTI */
+    pub fn has_feature(&self, machine: ElfMachine, encoding: ElfEncoding,
+        feature: u32) -> bool {
+        let kind: u32 = match machine {
+            ElfMachine::X86_64 | ElfMachine::I386 =>
+                GNU_PROPERTY_X86_FEATURE_1_AND,
+            ElfMachine::AArch64 => GNU_PROPERTY_AARCH64_FEATURE_1_AND,
+            _ => return false
+        };
+
+        match encoding.read_u32(&self.pr_data, 0) {
+            Ok(t) => self.pr_type == kind && t & feature != 0,
+            Err(_e) => false
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Note {
+    name: String,       /* owner, such as "GNU" or "Go" */
+    n_type: u32,        /* meaning depends on the owner */
+    desc: Vec<u8>
+}
+
+impl Note {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_type(&self) -> u32 {
+        self.n_type
+    }
+
+    pub fn get_desc(&self) -> &[u8] {
+        &self.desc
+    }
+
+    pub fn get_type_name(&self) -> String {
+        match (self.name.as_str(), self.n_type) {
+            ("GNU", NT_GNU_ABI_TAG) =>
+                String::from("NT_GNU_ABI_TAG (ABI version tag)"),
+            ("GNU", NT_GNU_HWCAP) =>
+                String::from("NT_GNU_HWCAP (DSO-supplied software HWCAP info)"),
+            ("GNU", NT_GNU_BUILD_ID) =>
+                String::from("NT_GNU_BUILD_ID (unique build ID bitstring)"),
+            ("GNU", NT_GNU_GOLD_VERSION) =>
+                String::from("NT_GNU_GOLD_VERSION (gold version)"),
+            ("GNU", NT_GNU_PROPERTY_TYPE_0) =>
+                String::from("NT_GNU_PROPERTY_TYPE_0"),
+            ("Go", NT_GO_BUILD_ID) => String::from("GO BUILDID"),
+            (_, t) => format!("Unknown note type: ({:#010x})", t)
+        }
+    }
+
+    /* the build ID as the hex string debuggers look it up by */
+    pub fn get_build_id(&self) -> Option<String> {
+        match (self.name.as_str(), self.n_type) {
+            ("GNU", NT_GNU_BUILD_ID) => Some(self.desc.iter()
+                .map(|t| format!("{:02x}", t))
+                .collect()),
+            _ => None
+        }
+    }
+
+    pub fn get_go_build_id(&self) -> Option<String> {
+        match (self.name.as_str(), self.n_type) {
+            ("Go", NT_GO_BUILD_ID) =>
+                Some(String::from_utf8_lossy(&self.desc).into_owned()),
+            _ => None
+        }
+    }
+
+    /* "OS: Linux, ABI: 3.2.0" */
+    pub fn get_abi_tag(&self, encoding: ElfEncoding) -> Option<String> {
+        if self.name != "GNU" || self.n_type != NT_GNU_ABI_TAG {
+            return None;
+        }
+
+        let os: u32 = encoding.read_u32(&self.desc, 0).ok()?;
+        let os_name: String = match ABI_TAG_OS_NAMES.get(os as usize) {
+            Some(t) => String::from(*t),
+            None => format!("Unknown ({})", os)
+        };
+
+        Some(format!("OS: {}, ABI: {}.{}.{}", os_name,
+            encoding.read_u32(&self.desc, 4).ok()?,
+            encoding.read_u32(&self.desc, 8).ok()?,
+            encoding.read_u32(&self.desc, 12).ok()?))
+    }
+
+    /* the properties are padded to the address size */
+    pub fn get_properties(&self, encoding: ElfEncoding) ->
+        Result<Vec<GnuProperty>, ButylError> {
+        if self.name != "GNU" || self.n_type != NT_GNU_PROPERTY_TYPE_0 {
+            return Ok(Vec::new());
+        }
+
+        let mut properties: Vec<GnuProperty> = Vec::new();
+        let mut offset: usize = 0;
+
+        while offset + 8 <= self.desc.len() {
+            let pr_type: u32 = encoding.read_u32(&self.desc, offset)?;
+            let size: usize = encoding.read_u32(&self.desc, offset + 4)?
+                as usize;
+            let pr_data: &[u8] = read_bytes(&self.desc, offset + 8, size)?;
+
+            properties.push(GnuProperty {
+                pr_type,
+                pr_data: pr_data.to_vec()
+            });
+
+            offset += (8 + size)
+                .next_multiple_of(encoding.get_address_len());
+        }
+
+        Ok(properties)
+    }
+}
+
+impl<'a> ElfFile<'a> {
+    /* note entries pad the name and descriptor to 4 bytes, or to 8 when the
+     * containing section or segment is 8-byte aligned */
+    pub fn get_notes_from_bytes(&self, bytes: &[u8], align: u64) ->
+        Result<Vec<Note>, ButylError> {
+        let encoding: ElfEncoding = self.get_encoding();
+        let align: usize = if align == 8 { 8 } else { 4 };
+        let mut notes: Vec<Note> = Vec::new();
+        let mut offset: usize = 0;
+
+        while offset + ELF_NOTE_HEADER_LEN <= bytes.len() {
+            let namesz: usize = encoding.read_u32(bytes, offset)? as usize;
+            let descsz: usize = encoding.read_u32(bytes, offset + 4)? as usize;
+            let n_type: u32 = encoding.read_u32(bytes, offset + 8)?;
+
+            let name_offset: usize = offset + ELF_NOTE_HEADER_LEN;
+            let desc_offset: usize = name_offset
+                .checked_add(namesz)
+                .ok_or(ButylError::MalformedDataError)?
+                .next_multiple_of(align);
+
+            let name: &[u8] = read_bytes(bytes, name_offset, namesz)?;
+            let desc: &[u8] = read_bytes(bytes, desc_offset, descsz)?;
+
+            notes.push(Note {
+                name: String::from_utf8_lossy(name)
+                    .trim_end_matches('\0')
+                    .to_string(),
+                n_type,
+                desc: desc.to_vec()
+            });
+
+            offset = (desc_offset + descsz).next_multiple_of(align);
+        }
+
+        Ok(notes)
+    }
+
+    pub fn get_section_notes(&self, section: &SectionHeader) ->
+        Result<Vec<Note>, ButylError> {
+        self.get_notes_from_bytes(self.get_section_data(section)?,
+            section.get_addralign())
+    }
+
+    pub fn get_segment_notes(&self, segment: &ProgramHeader) ->
+        Result<Vec<Note>, ButylError> {
+        self.get_notes_from_bytes(self.get_segment_data(segment)?,
+            segment.get_align())
+    }
+
+    /* every note in the file, read from the SHT_NOTE sections or, in files
+     * without section headers, the PT_NOTE segments */
+    pub fn get_notes(&self) -> Result<Vec<Note>, ButylError> {
+        let mut notes: Vec<Note> = Vec::new();
+
+        for section in self.get_section_headers().iter()
+            .filter(|t| t.get_type_as_enum() == SectionType::Note) {
+            notes.append(&mut self.get_section_notes(section)?);
+        }
+
+        if !self.get_section_headers().is_empty() {
+            return Ok(notes);
+        }
+
+        for segment in self.get_program_headers().iter()
+            .filter(|t| t.get_type_as_enum() == ProgramHeaderType::Note) {
+            notes.append(&mut self.get_segment_notes(segment)?);
+        }
+
+        Ok(notes)
+    }
+
+    pub fn get_build_id(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_notes()?.iter().find_map(|t| t.get_build_id()))
+    }
+
+    pub fn get_go_build_id(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_notes()?.iter().find_map(|t| t.get_go_build_id()))
+    }
+
+    pub fn get_gnu_properties(&self) ->
+        Result<Vec<GnuProperty>, ButylError> {
+        let mut properties: Vec<GnuProperty> = Vec::new();
+
+        for note in self.get_notes()? {
+            properties.append(&mut note.get_properties(self.get_encoding())?);
+        }
+
+        Ok(properties)
+    }
+}
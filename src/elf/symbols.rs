@@ -0,0 +1,318 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::elf::{ElfFile, ElfEncoding, SectionHeader, SectionType, SHN_ABS,
+    SHN_COMMON, SHN_UNDEF, SHN_XINDEX};
+use crate::utils::*;
+
+pub const ELF_SYMBOL_LEN_32: usize = 16;
+pub const ELF_SYMBOL_LEN_64: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolBinding {
+    Local,
+    Global,
+    Weak,
+    GnuUnique,
+    Other(u8)
+}
+
+impl SymbolBinding {
+    pub fn to_value(binding: SymbolBinding) -> u8 {
+        match binding {
+            SymbolBinding::Local => 0,
+            SymbolBinding::Global => 1,
+            SymbolBinding::Weak => 2,
+            SymbolBinding::GnuUnique => 10,
+            SymbolBinding::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> SymbolBinding {
+        match value {
+            0 => SymbolBinding::Local,
+            1 => SymbolBinding::Global,
+            2 => SymbolBinding::Weak,
+            10 => SymbolBinding::GnuUnique,
+            t => SymbolBinding::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SymbolBinding::Local => String::from("LOCAL"),
+            SymbolBinding::Global => String::from("GLOBAL"),
+            SymbolBinding::Weak => String::from("WEAK"),
+            SymbolBinding::GnuUnique => String::from("UNIQUE"),
+            SymbolBinding::Other(t) => format!("<unknown>: {}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolType {
+    NoType,
+    Object,
+    Func,
+    Section,
+    File,
+    Common,
+    Tls,
+    GnuIfunc,
+    Other(u8)
+}
+
+impl SymbolType {
+    pub fn to_value(kind: SymbolType) -> u8 {
+        match kind {
+            SymbolType::NoType => 0,
+            SymbolType::Object => 1,
+            SymbolType::Func => 2,
+            SymbolType::Section => 3,
+            SymbolType::File => 4,
+            SymbolType::Common => 5,
+            SymbolType::Tls => 6,
+            SymbolType::GnuIfunc => 10,
+            SymbolType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> SymbolType {
+        match value {
+            0 => SymbolType::NoType,
+            1 => SymbolType::Object,
+            2 => SymbolType::Func,
+            3 => SymbolType::Section,
+            4 => SymbolType::File,
+            5 => SymbolType::Common,
+            6 => SymbolType::Tls,
+            10 => SymbolType::GnuIfunc,
+            t => SymbolType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SymbolType::NoType => String::from("NOTYPE"),
+            SymbolType::Object => String::from("OBJECT"),
+            SymbolType::Func => String::from("FUNC"),
+            SymbolType::Section => String::from("SECTION"),
+            SymbolType::File => String::from("FILE"),
+            SymbolType::Common => String::from("COMMON"),
+            SymbolType::Tls => String::from("TLS"),
+            SymbolType::GnuIfunc => String::from("IFUNC"),
+            SymbolType::Other(t) => format!("<unknown>: {}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolVisibility {
+    Default,
+    Internal,
+    Hidden,
+    Protected
+}
+
+impl SymbolVisibility {
+    pub fn to_value(visibility: SymbolVisibility) -> u8 {
+        match visibility {
+            SymbolVisibility::Default => 0,
+            SymbolVisibility::Internal => 1,
+            SymbolVisibility::Hidden => 2,
+            SymbolVisibility::Protected => 3
+        }
+    }
+
+    pub fn from_value(value: u8) -> SymbolVisibility {
+        match value & 0x3 {
+            1 => SymbolVisibility::Internal,
+            2 => SymbolVisibility::Hidden,
+            3 => SymbolVisibility::Protected,
+            _ => SymbolVisibility::Default
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SymbolVisibility::Default => String::from("DEFAULT"),
+            SymbolVisibility::Internal => String::from("INTERNAL"),
+            SymbolVisibility::Hidden => String::from("HIDDEN"),
+            SymbolVisibility::Protected => String::from("PROTECTED")
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Symbol {
+    name: String,       /* resolved from the linked string table */
+    st_name: u32,       /* offset of the name in that table */
+    st_value: u64,      /* value, usually an address */
+    st_size: u64,       /* size of the object or function */
+    st_info: u8,        /* binding (high nibble) and type (low nibble) */
+    st_other: u8,       /* visibility */
+    st_shndx: u16,      /* section the symbol is defined in */
+    section_index: u32  /* st_shndx after any SHN_XINDEX escape */
+}
+
+impl Symbol {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_name_offset(&self) -> u32 {
+        self.st_name
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.st_value
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.st_size
+    }
+
+    pub fn get_info(&self) -> u8 {
+        self.st_info
+    }
+
+    pub fn get_binding(&self) -> SymbolBinding {
+        SymbolBinding::from_value(self.st_info >> 4)
+    }
+
+    pub fn get_type(&self) -> SymbolType {
+        SymbolType::from_value(self.st_info & 0xf)
+    }
+
+    pub fn get_other(&self) -> u8 {
+        self.st_other
+    }
+
+    pub fn get_visibility(&self) -> SymbolVisibility {
+        SymbolVisibility::from_value(self.st_other)
+    }
+
+    pub fn get_shndx(&self) -> u16 {
+        self.st_shndx
+    }
+
+    pub fn get_section_index(&self) -> u32 {
+        self.section_index
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        self.st_shndx == SHN_UNDEF
+    }
+
+    /* the Ndx column as readelf shows it */
+    pub fn get_section_index_as_string(&self) -> String {
+        match self.st_shndx {
+            SHN_UNDEF => String::from("UND"),
+            SHN_ABS => String::from("ABS"),
+            SHN_COMMON => String::from("COM"),
+            _ => self.section_index.to_string()
+        }
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_bytes(bytes: &[u8], encoding: ElfEncoding) ->
+        Result<Symbol, ButylError> {
+        let mut symbol: Symbol = Symbol::default();
+
+        symbol.st_name = encoding.read_u32(bytes, 0)?;
+
+        /* as with program headers, ELF64 reorders the fields */
+        if encoding.is_64_bit() {
+            symbol.st_info = read_u8(bytes, 4)?;
+            symbol.st_other = read_u8(bytes, 5)?;
+            symbol.st_shndx = encoding.read_u16(bytes, 6)?;
+            symbol.st_value = encoding.read_u64(bytes, 8)?;
+            symbol.st_size = encoding.read_u64(bytes, 16)?;
+        } else {
+            symbol.st_value = encoding.read_u32(bytes, 4)? as u64;
+            symbol.st_size = encoding.read_u32(bytes, 8)? as u64;
+            symbol.st_info = read_u8(bytes, 12)?;
+            symbol.st_other = read_u8(bytes, 13)?;
+            symbol.st_shndx = encoding.read_u16(bytes, 14)?;
+        }
+
+        symbol.section_index = symbol.st_shndx as u32;
+
+        Ok(symbol)
+    }
+}
+
+impl<'a> ElfFile<'a> {
+    pub fn get_symbol_len(&self) -> usize {
+        if self.get_encoding().is_64_bit() {
+            ELF_SYMBOL_LEN_64
+        } else {
+            ELF_SYMBOL_LEN_32
+        }
+    }
+
+    /* decodes the SHT_SYMTAB or SHT_DYNSYM section at `index`, naming each
+     * symbol from the string table the section links to */
+    pub fn get_symbols(&self, index: usize) ->
+        Result<Vec<Symbol>, ButylError> {
+        let section: &SectionHeader = self.get_section(index)?;
+        let encoding: ElfEncoding = self.get_encoding();
+        let symbol_len: usize = self.get_symbol_len();
+        let strtab: &[u8] = self.get_section_data(
+            self.get_section(section.get_link() as usize)?)?;
+
+        /* section indices that overflow st_shndx live in a parallel table */
+        let extended: Option<&[u8]> = match self.get_section_headers().iter()
+            .position(|t| t.get_type_as_enum() == SectionType::SymtabShndx &&
+                t.get_link() as usize == index) {
+            Some(t) => Some(self.get_section_data(self.get_section(t)?)?),
+            None => None
+        };
+
+        let mut symbols: Vec<Symbol> = Vec::new();
+
+        for (i, entry) in self.get_section_data(section)?
+            .chunks_exact(symbol_len).enumerate() {
+            let mut symbol: Symbol = Symbol::from_bytes(entry, encoding)?;
+            symbol.name = read_cstring(strtab, symbol.st_name as usize)
+                .unwrap_or_default();
+
+            if let (SHN_XINDEX, Some(t)) = (symbol.st_shndx, extended) {
+                symbol.section_index = encoding.read_u32(t, i * 4)?;
+            }
+
+            symbols.push(symbol);
+        }
+
+        Ok(symbols)
+    }
+
+    /* section symbols are usually nameless and go by their section's name */
+    pub fn get_symbol_name(&self, symbol: &Symbol) -> String {
+        match symbol.get_type() {
+            SymbolType::Section if symbol.name.is_empty() =>
+                match self.get_section(symbol.section_index as usize) {
+                    Ok(t) => String::from(t.get_name()),
+                    Err(_e) => String::new()
+                },
+            _ => symbol.name.clone()
+        }
+    }
+
+    pub fn get_symbol_table(&self) -> Result<Vec<Symbol>, ButylError> {
+        self.get_symbols_of_type(SectionType::Symtab)
+    }
+
+    pub fn get_dynamic_symbols(&self) -> Result<Vec<Symbol>, ButylError> {
+        self.get_symbols_of_type(SectionType::Dynsym)
+    }
+
+    fn get_symbols_of_type(&self, kind: SectionType) ->
+        Result<Vec<Symbol>, ButylError> {
+        match self.get_section_headers().iter()
+            .position(|t| t.get_type_as_enum() == kind) {
+            Some(t) => self.get_symbols(t),
+            None => Ok(Vec::new())
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::elf::{ElfFile, ElfEncoding, SectionHeader, SectionType};
+use crate::utils::*;
+
+pub const VER_NDX_LOCAL: u16 = 0;
+pub const VER_NDX_GLOBAL: u16 = 1;
+pub const VERSYM_HIDDEN: u16 = 0x8000;
+pub const VERSYM_VERSION: u16 = 0x7fff;
+
+pub const VER_FLG_BASE: u16 = 0x1;
+pub const VER_FLG_WEAK: u16 = 0x2;
+pub const VER_FLG_INFO: u16 = 0x4;
+
+pub const ELF_VERDEF_LEN: usize = 20;
+pub const ELF_VERDAUX_LEN: usize = 8;
+pub const ELF_VERNEED_LEN: usize = 16;
+pub const ELF_VERNAUX_LEN: usize = 16;
+
+/* "BASE | WEAK", or "none" */
+pub fn get_version_flags_as_string(flags: u16) -> String {
+    let mut names: Vec<&str> = Vec::new();
+
+    if flags & VER_FLG_BASE != 0 {
+        names.push("BASE");
+    }
+
+    if flags & VER_FLG_WEAK != 0 {
+        names.push("WEAK");
+    }
+
+    if flags & VER_FLG_INFO != 0 {
+        names.push("INFO");
+    }
+
+    match names.is_empty() {
+        true => String::from("none"),
+        false => names.join(" | ")
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VersionDefinition {
+    vd_version: u16,    /* structure revision, always 1 */
+    vd_flags: u16,      /* VER_FLG_* */
+    vd_ndx: u16,        /* index used in .gnu.version */
+    vd_hash: u32,       /* ELF hash of the name */
+    names: Vec<String>  /* the version, followed by any parents */
+}
+
+impl VersionDefinition {
+    pub fn get_version(&self) -> u16 {
+        self.vd_version
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.vd_flags
+    }
+
+    pub fn get_index(&self) -> u16 {
+        self.vd_ndx
+    }
+
+    pub fn get_hash(&self) -> u32 {
+        self.vd_hash
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self.names.first() {
+            Some(t) => t,
+            None => ""
+        }
+    }
+
+    pub fn get_parents(&self) -> &[String] {
+        self.names.get(1..).unwrap_or(&[])
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VersionNeedAux {
+    vna_hash: u32,      /* ELF hash of the name */
+    vna_flags: u16,     /* VER_FLG_* */
+    vna_other: u16,     /* index used in .gnu.version */
+    name: String
+}
+
+impl VersionNeedAux {
+    pub fn get_hash(&self) -> u32 {
+        self.vna_hash
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.vna_flags
+    }
+
+    pub fn get_index(&self) -> u16 {
+        self.vna_other
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VersionNeed {
+    vn_version: u16,    /* structure revision, always 1 */
+    file: String,       /* the library the versions are needed from */
+    entries: Vec<VersionNeedAux>
+}
+
+impl VersionNeed {
+    pub fn get_version(&self) -> u16 {
+        self.vn_version
+    }
+
+    pub fn get_file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn get_entries(&self) -> &[VersionNeedAux] {
+        &self.entries
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SymbolVersion {
+    index: u16,         /* .gnu.version entry without the hidden bit */
+    hidden: bool,       /* not the default version of the symbol */
+    needed: bool,       /* required from another object, not defined */
+    name: String
+}
+
+impl SymbolVersion {
+    pub fn get_index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn is_needed(&self) -> bool {
+        self.needed
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /* "@@VERSION" for the default definition, "@VERSION" otherwise */
+    pub fn get_suffix(&self) -> String {
+        match self.needed || self.hidden {
+            true => format!("@{}", self.name),
+            false => format!("@@{}", self.name)
+        }
+    }
+}
+
+impl<'a> ElfFile<'a> {
+    fn get_section_of_type(&self, kind: SectionType) ->
+        Option<&SectionHeader> {
+        self.get_section_headers().iter()
+            .find(|t| t.get_type_as_enum() == kind)
+    }
+
+    /* .gnu.version, with one entry for each symbol in .dynsym */
+    pub fn get_version_symbols(&self) -> Result<Vec<u16>, ButylError> {
+        let encoding: ElfEncoding = self.get_encoding();
+
+        match self.get_section_of_type(SectionType::GnuVersym) {
+            Some(t) => self.get_section_data(t)?.chunks_exact(2)
+                .map(|t| encoding.read_u16(t, 0))
+                .collect(),
+            None => Ok(Vec::new())
+        }
+    }
+
+    /* .gnu.version_d, a chain of Verdef entries each with its own chain of
+     * Verdaux names */
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn get_version_definitions(&self) ->
+        Result<Vec<VersionDefinition>, ButylError> {
+        let section: &SectionHeader =
+            match self.get_section_of_type(SectionType::GnuVerdef) {
+                Some(t) => t,
+                None => return Ok(Vec::new())
+            };
+        let encoding: ElfEncoding = self.get_encoding();
+        let data: &[u8] = self.get_section_data(section)?;
+        let strtab: usize = section.get_link() as usize;
+
+        let mut definitions: Vec<VersionDefinition> = Vec::new();
+        let mut offset: usize = 0;
+
+        for _ in 0..section.get_info() {
+            let entry: &[u8] = read_bytes(data, offset, ELF_VERDEF_LEN)?;
+            let mut definition: VersionDefinition =
+                VersionDefinition::default();
+
+            definition.vd_version = encoding.read_u16(entry, 0)?;
+            definition.vd_flags = encoding.read_u16(entry, 2)?;
+            definition.vd_ndx = encoding.read_u16(entry, 4)?;
+            definition.vd_hash = encoding.read_u32(entry, 8)?;
+
+            let count: u16 = encoding.read_u16(entry, 6)?;
+            let mut aux: usize = offset + encoding.read_u32(entry, 12)?
+                as usize;
+
+            for _ in 0..count {
+                let verdaux: &[u8] = read_bytes(data, aux, ELF_VERDAUX_LEN)?;
+                definition.names.push(self.get_string(strtab,
+                    encoding.read_u32(verdaux, 0)? as usize)?);
+
+                match encoding.read_u32(verdaux, 4)? {
+                    0 => break,
+                    t => aux += t as usize
+                }
+            }
+
+            definitions.push(definition);
+
+            match encoding.read_u32(entry, 16)? {
+                0 => break,
+                t => offset += t as usize
+            }
+        }
+
+        Ok(definitions)
+    }
+
+    /* .gnu.version_r, a chain of Verneed entries per library each with a
+     * chain of Vernaux versions */
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn get_version_needs(&self) -> Result<Vec<VersionNeed>, ButylError> {
+        let section: &SectionHeader =
+            match self.get_section_of_type(SectionType::GnuVerneed) {
+                Some(t) => t,
+                None => return Ok(Vec::new())
+            };
+        let encoding: ElfEncoding = self.get_encoding();
+        let data: &[u8] = self.get_section_data(section)?;
+        let strtab: usize = section.get_link() as usize;
+
+        let mut needs: Vec<VersionNeed> = Vec::new();
+        let mut offset: usize = 0;
+
+        for _ in 0..section.get_info() {
+            let entry: &[u8] = read_bytes(data, offset, ELF_VERNEED_LEN)?;
+            let mut need: VersionNeed = VersionNeed::default();
+
+            need.vn_version = encoding.read_u16(entry, 0)?;
+            need.file = self.get_string(strtab,
+                encoding.read_u32(entry, 4)? as usize)?;
+
+            let count: u16 = encoding.read_u16(entry, 2)?;
+            let mut aux: usize = offset + encoding.read_u32(entry, 8)?
+                as usize;
+
+            for _ in 0..count {
+                let vernaux: &[u8] = read_bytes(data, aux, ELF_VERNAUX_LEN)?;
+
+                need.entries.push(VersionNeedAux {
+                    vna_hash: encoding.read_u32(vernaux, 0)?,
+                    vna_flags: encoding.read_u16(vernaux, 4)?,
+                    vna_other: encoding.read_u16(vernaux, 6)?,
+                    name: self.get_string(strtab,
+                        encoding.read_u32(vernaux, 8)? as usize)?
+                });
+
+                match encoding.read_u32(vernaux, 12)? {
+                    0 => break,
+                    t => aux += t as usize
+                }
+            }
+
+            needs.push(need);
+
+            match encoding.read_u32(entry, 12)? {
+                0 => break,
+                t => offset += t as usize
+            }
+        }
+
+        Ok(needs)
+    }
+
+    /* the version of each .dynsym symbol, None for the local and global
+     * indices that carry no name */
+    pub fn get_symbol_versions(&self) ->
+        Result<Vec<Option<SymbolVersion>>, ButylError> {
+        let definitions: Vec<VersionDefinition> =
+            self.get_version_definitions()?;
+        let needs: Vec<VersionNeed> = self.get_version_needs()?;
+
+        let versions = self.get_version_symbols()?.into_iter().map(|t| {
+            let index: u16 = t & VERSYM_VERSION;
+
+            if index == VER_NDX_LOCAL || index == VER_NDX_GLOBAL {
+                return None;
+            }
+
+            let needed: Option<&VersionNeedAux> = needs.iter()
+                .flat_map(|t| t.get_entries())
+                .find(|t| t.get_index() == index);
+            let defined: Option<&VersionDefinition> = definitions.iter()
+                .find(|t| t.get_index() == index);
+
+            let (name, needed): (&str, bool) = match (needed, defined) {
+                (Some(t), _) => (t.get_name(), true),
+                (None, Some(t)) => (t.get_name(), false),
+                (None, None) => return None
+            };
+
+            Some(SymbolVersion {
+                index,
+                hidden: t & VERSYM_HIDDEN != 0,
+                needed,
+                name: String::from(name)
+            })
+        });
+
+        Ok(versions.collect())
+    }
+}
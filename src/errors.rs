@@ -1,6 +1,30 @@
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum ButylError {
     InsufficientDataError,
-    ExcessiveDataError
+    ExcessiveDataError,
+    InvalidMagicError,
+    MalformedDataError,
+    UnmappedAddressError,
+    UnknownFieldError,
+    UnsupportedFeatureError,
+    NotFoundError
 } 
 
+/* why an address couldn't be translated; collapses to
+ * `ButylError::UnmappedAddressError` when propagated */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressError {
+    BelowImageBase,     /* VA lower than the image base */
+    OutsideImage,       /* past SizeOfImage */
+    NotInSection,       /* inside the image but in no section */
+    NotInFile,          /* mapped, but zero-filled rather than file-backed */
+    Overlay             /* file offset not mapped by any section */
+}
+
+impl From<AddressError> for ButylError {
+    fn from(_e: AddressError) -> ButylError {
+        ButylError::UnmappedAddressError
+    }
+}
+
@@ -1,10 +1,26 @@
+#![allow(dead_code)]
+
 use crate::dos;
+use crate::com;
 use crate::coff;
+use crate::pe;
+use crate::ne;
+use crate::le;
+use crate::elf;
+use crate::macho;
 
+#[allow(clippy::upper_case_acronyms)]
 pub enum Format {
     DOS,
+    COM,
     COFF,
     PE,
+    NE,
+    LE,
+    LX,
+    ELF,
+    MachO,
+    FAT,
     Unknown
 }
 
@@ -12,17 +28,33 @@ impl Format {
     pub fn from_string(string: String) -> Format {
         match string.as_ref() {
             "DOS" => Format::DOS,
+            "COM" => Format::COM,
             "COFF" => Format::COFF,
             "PE" => Format::PE,
+            "NE" => Format::NE,
+            "LE" => Format::LE,
+            "LX" => Format::LX,
+            "ELF" => Format::ELF,
+            "MachO" => Format::MachO,
+            "FAT" => Format::FAT,
             _ => Format::Unknown
         }
     }
 }
 
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum File<'a> {
     DOS(dos::DosFile<'a>),
+    COM(com::ComFile<'a>),
     COFF(coff::CoffFile<'a>),
+    PE(Box<pe::PeFile<'a>>),
+    NE(ne::NeFile<'a>),
+    LE(le::LeFile<'a>),
+    LX(le::LeFile<'a>),
+    ELF(elf::ElfFile<'a>),
+    MachO(macho::MachOFile<'a>),
+    FAT(macho::fat::FatFile<'a>),
     Unknown(())
 }
 
@@ -0,0 +1,786 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::DosHeader;
+use crate::ne::TargetOs;
+use crate::ne::names::{self, Name};
+use crate::utils::*;
+
+pub mod fixups;
+pub mod entries;
+
+pub const LE_SIGNATURE: u16 = 0x454c; /* "LE" */
+pub const LX_SIGNATURE: u16 = 0x584c; /* "LX" */
+pub const LE_HEADER_LEN: usize = 0xb0;
+pub const LE_OBJECT_LEN: usize = 24;
+pub const LE_PAGE_LEN: usize = 4;
+pub const LX_PAGE_LEN: usize = 8;
+
+pub const E32_PROCLIBINIT: u32 = 0x0000_0004;
+pub const E32_NOINTFIXUPS: u32 = 0x0000_0010;
+pub const E32_NOEXTFIXUPS: u32 = 0x0000_0020;
+pub const E32_NOLOAD: u32 = 0x0000_2000;
+pub const E32_PROCLIBTERM: u32 = 0x4000_0000;
+pub const E32_MODTYPE: u32 = 0x0003_8000;
+
+const E32_FLAG_NAMES: &[(u32, &str)] = &[
+    (E32_PROCLIBINIT, "PROCLIBINIT"), (E32_NOINTFIXUPS, "NOINTFIXUPS"),
+    (E32_NOEXTFIXUPS, "NOEXTFIXUPS"), (E32_NOLOAD, "NOLOAD"),
+    (E32_PROCLIBTERM, "PROCLIBTERM")
+];
+
+pub const OBJ_READ: u32 = 0x0001;
+pub const OBJ_WRITE: u32 = 0x0002;
+pub const OBJ_EXEC: u32 = 0x0004;
+pub const OBJ_RSRC: u32 = 0x0008;
+pub const OBJ_DISCARD: u32 = 0x0010;
+pub const OBJ_SHARED: u32 = 0x0020;
+pub const OBJ_PRELOAD: u32 = 0x0040;
+pub const OBJ_INVALID: u32 = 0x0080;
+pub const OBJ_ZEROFILL: u32 = 0x0100;
+pub const OBJ_RESIDENT: u32 = 0x0200;
+pub const OBJ_ALIAS16: u32 = 0x1000;
+pub const OBJ_BIGDEF: u32 = 0x2000;
+pub const OBJ_CONFORM: u32 = 0x4000;
+pub const OBJ_IOPL: u32 = 0x8000;
+
+const OBJ_FLAG_NAMES: &[(u32, &str)] = &[
+    (OBJ_RSRC, "RESOURCE"), (OBJ_DISCARD, "DISCARDABLE"),
+    (OBJ_SHARED, "SHARED"), (OBJ_PRELOAD, "PRELOAD"),
+    (OBJ_INVALID, "INVALID"), (OBJ_ZEROFILL, "ZEROFILL"),
+    (OBJ_RESIDENT, "RESIDENT"), (OBJ_ALIAS16, "ALIAS16"),
+    (OBJ_BIGDEF, "BIG"), (OBJ_CONFORM, "CONFORMING"), (OBJ_IOPL, "IOPL")
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModuleType {
+    Program,
+    Library,
+    ProtectedLibrary,
+    PhysicalDriver,
+    VirtualDriver,
+    Other(u32)
+}
+
+impl ModuleType {
+    pub fn to_value(module_type: ModuleType) -> u32 {
+        match module_type {
+            ModuleType::Program => 0x0_0000,
+            ModuleType::Library => 0x0_8000,
+            ModuleType::ProtectedLibrary => 0x1_8000,
+            ModuleType::PhysicalDriver => 0x2_0000,
+            ModuleType::VirtualDriver => 0x2_8000,
+            ModuleType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> ModuleType {
+        match value {
+            0x0_0000 => ModuleType::Program,
+            0x0_8000 => ModuleType::Library,
+            0x1_8000 => ModuleType::ProtectedLibrary,
+            0x2_0000 => ModuleType::PhysicalDriver,
+            0x2_8000 => ModuleType::VirtualDriver,
+            t => ModuleType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ModuleType::Program => String::from("Program"),
+            ModuleType::Library => String::from("Library"),
+            ModuleType::ProtectedLibrary =>
+                String::from("Protected memory library"),
+            ModuleType::PhysicalDriver =>
+                String::from("Physical device driver"),
+            ModuleType::VirtualDriver => String::from("Virtual device driver"),
+            ModuleType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CpuType {
+    I286,
+    I386,
+    I486,
+    Other(u16)
+}
+
+impl CpuType {
+    pub fn to_value(cpu_type: CpuType) -> u16 {
+        match cpu_type {
+            CpuType::I286 => 1,
+            CpuType::I386 => 2,
+            CpuType::I486 => 3,
+            CpuType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u16) -> CpuType {
+        match value {
+            1 => CpuType::I286,
+            2 => CpuType::I386,
+            3 => CpuType::I486,
+            t => CpuType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            CpuType::I286 => String::from("80286"),
+            CpuType::I386 => String::from("80386"),
+            CpuType::I486 => String::from("80486"),
+            CpuType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LeHeader {
+    e32_magic: u16,         /* "LE" or "LX" */
+    e32_border: u8,         /* byte order, 0 for little endian */
+    e32_worder: u8,         /* word order, 0 for little endian */
+    e32_level: u32,         /* format level */
+    e32_cpu: u16,           /* minimum CPU */
+    e32_os: u16,            /* target operating system, as for NE */
+    e32_ver: u32,           /* module version */
+    e32_mflags: u32,        /* E32_* flags and module type */
+    e32_mpages: u32,        /* number of pages in the module */
+    e32_startobj: u32,      /* object number of the initial EIP */
+    e32_eip: u32,           /* initial EIP, relative to that object */
+    e32_stackobj: u32,      /* object number of the initial ESP */
+    e32_esp: u32,           /* initial ESP, relative to that object */
+    e32_pagesize: u32,      /* page size */
+    e32_pageshift: u32,     /* LX page offset shift, LE last page size */
+    e32_fixupsize: u32,     /* size of the fixup section */
+    e32_fixupsum: u32,      /* checksum of the fixup section */
+    e32_ldrsize: u32,       /* size of the loader section */
+    e32_ldrsum: u32,        /* checksum of the loader section */
+    e32_objtab: u32,        /* offset of the object table */
+    e32_objcnt: u32,        /* number of objects */
+    e32_objmap: u32,        /* offset of the object page table */
+    e32_itermap: u32,       /* file offset of the iterated pages */
+    e32_rsrctab: u32,       /* offset of the resource table */
+    e32_rsrccnt: u32,       /* number of resources */
+    e32_restab: u32,        /* offset of the resident name table */
+    e32_enttab: u32,        /* offset of the entry table */
+    e32_dirtab: u32,        /* offset of the module directives */
+    e32_dircnt: u32,        /* number of module directives */
+    e32_fpagetab: u32,      /* offset of the fixup page table */
+    e32_frectab: u32,       /* offset of the fixup record table */
+    e32_impmod: u32,        /* offset of the import module table */
+    e32_impmodcnt: u32,     /* number of import modules */
+    e32_impproc: u32,       /* offset of the import procedure table */
+    e32_pagesum: u32,       /* offset of the per-page checksums */
+    e32_datapage: u32,      /* file offset of the data pages */
+    e32_preload: u32,       /* number of preload pages */
+    e32_nrestab: u32,       /* file offset of the non-resident names */
+    e32_cbnrestab: u32,     /* size of the non-resident name table */
+    e32_nressum: u32,       /* checksum of the non-resident name table */
+    e32_autodata: u32,      /* object number of the automatic data */
+    e32_debuginfo: u32,     /* file offset of the debug information */
+    e32_debuglen: u32,      /* size of the debug information */
+    e32_instpreload: u32,   /* instance pages in the preload section */
+    e32_instdemand: u32,    /* instance pages in the demand section */
+    e32_heapsize: u32,      /* heap size for 16-bit applications */
+    e32_stacksize: u32      /* stack size, LX only */
+}
+
+impl LeHeader {
+    pub fn get_magic(&self) -> u16 {
+        self.e32_magic
+    }
+
+    pub fn is_lx(&self) -> bool {
+        self.e32_magic == LX_SIGNATURE
+    }
+
+    pub fn get_border(&self) -> u8 {
+        self.e32_border
+    }
+
+    pub fn get_worder(&self) -> u8 {
+        self.e32_worder
+    }
+
+    pub fn get_level(&self) -> u32 {
+        self.e32_level
+    }
+
+    pub fn get_cpu(&self) -> u16 {
+        self.e32_cpu
+    }
+
+    pub fn get_cpu_as_enum(&self) -> CpuType {
+        CpuType::from_value(self.e32_cpu)
+    }
+
+    pub fn get_os(&self) -> u16 {
+        self.e32_os
+    }
+
+    /* the same values as the one-byte field in NE headers */
+    pub fn get_target_os(&self) -> TargetOs {
+        match self.e32_os {
+            t if t <= 0xff => TargetOs::from_value(t as u8),
+            _ => TargetOs::Unknown
+        }
+    }
+
+    pub fn get_ver(&self) -> u32 {
+        self.e32_ver
+    }
+
+    pub fn get_mflags(&self) -> u32 {
+        self.e32_mflags
+    }
+
+    pub fn get_mflags_as_strings(&self) -> Vec<String> {
+        E32_FLAG_NAMES.iter()
+            .filter(|(flag, _)| self.e32_mflags & flag != 0)
+            .map(|(_, name)| String::from(*name))
+            .collect()
+    }
+
+    pub fn get_module_type(&self) -> ModuleType {
+        ModuleType::from_value(self.e32_mflags & E32_MODTYPE)
+    }
+
+    pub fn get_mpages(&self) -> u32 {
+        self.e32_mpages
+    }
+
+    pub fn get_startobj(&self) -> u32 {
+        self.e32_startobj
+    }
+
+    pub fn get_eip(&self) -> u32 {
+        self.e32_eip
+    }
+
+    pub fn get_stackobj(&self) -> u32 {
+        self.e32_stackobj
+    }
+
+    pub fn get_esp(&self) -> u32 {
+        self.e32_esp
+    }
+
+    pub fn get_pagesize(&self) -> u32 {
+        self.e32_pagesize
+    }
+
+    pub fn get_pageshift(&self) -> u32 {
+        self.e32_pageshift
+    }
+
+    pub fn get_fixupsize(&self) -> u32 {
+        self.e32_fixupsize
+    }
+
+    pub fn get_fixupsum(&self) -> u32 {
+        self.e32_fixupsum
+    }
+
+    pub fn get_ldrsize(&self) -> u32 {
+        self.e32_ldrsize
+    }
+
+    pub fn get_ldrsum(&self) -> u32 {
+        self.e32_ldrsum
+    }
+
+    pub fn get_objtab(&self) -> u32 {
+        self.e32_objtab
+    }
+
+    pub fn get_objcnt(&self) -> u32 {
+        self.e32_objcnt
+    }
+
+    pub fn get_objmap(&self) -> u32 {
+        self.e32_objmap
+    }
+
+    pub fn get_itermap(&self) -> u32 {
+        self.e32_itermap
+    }
+
+    pub fn get_rsrctab(&self) -> u32 {
+        self.e32_rsrctab
+    }
+
+    pub fn get_rsrccnt(&self) -> u32 {
+        self.e32_rsrccnt
+    }
+
+    pub fn get_restab(&self) -> u32 {
+        self.e32_restab
+    }
+
+    pub fn get_enttab(&self) -> u32 {
+        self.e32_enttab
+    }
+
+    pub fn get_dirtab(&self) -> u32 {
+        self.e32_dirtab
+    }
+
+    pub fn get_dircnt(&self) -> u32 {
+        self.e32_dircnt
+    }
+
+    pub fn get_fpagetab(&self) -> u32 {
+        self.e32_fpagetab
+    }
+
+    pub fn get_frectab(&self) -> u32 {
+        self.e32_frectab
+    }
+
+    pub fn get_impmod(&self) -> u32 {
+        self.e32_impmod
+    }
+
+    pub fn get_impmodcnt(&self) -> u32 {
+        self.e32_impmodcnt
+    }
+
+    pub fn get_impproc(&self) -> u32 {
+        self.e32_impproc
+    }
+
+    pub fn get_pagesum(&self) -> u32 {
+        self.e32_pagesum
+    }
+
+    pub fn get_datapage(&self) -> u32 {
+        self.e32_datapage
+    }
+
+    pub fn get_preload(&self) -> u32 {
+        self.e32_preload
+    }
+
+    pub fn get_nrestab(&self) -> u32 {
+        self.e32_nrestab
+    }
+
+    pub fn get_cbnrestab(&self) -> u32 {
+        self.e32_cbnrestab
+    }
+
+    pub fn get_nressum(&self) -> u32 {
+        self.e32_nressum
+    }
+
+    pub fn get_autodata(&self) -> u32 {
+        self.e32_autodata
+    }
+
+    pub fn get_debuginfo(&self) -> u32 {
+        self.e32_debuginfo
+    }
+
+    pub fn get_debuglen(&self) -> u32 {
+        self.e32_debuglen
+    }
+
+    pub fn get_instpreload(&self) -> u32 {
+        self.e32_instpreload
+    }
+
+    pub fn get_instdemand(&self) -> u32 {
+        self.e32_instdemand
+    }
+
+    pub fn get_heapsize(&self) -> u32 {
+        self.e32_heapsize
+    }
+
+    pub fn get_stacksize(&self) -> u32 {
+        self.e32_stacksize
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<LeHeader, ButylError> {
+        let mut header: LeHeader = LeHeader::default();
+        let field = |i: usize| read_le_u32(bytes, 0x0c + i * 4);
+
+        header.e32_magic = read_le_u16(bytes, 0)?;
+
+        if header.e32_magic != LE_SIGNATURE &&
+            header.e32_magic != LX_SIGNATURE {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        header.e32_border = read_u8(bytes, 2)?;
+        header.e32_worder = read_u8(bytes, 3)?;
+
+        /* big-endian modules were allowed for but never produced */
+        if header.e32_border != 0 || header.e32_worder != 0 {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        header.e32_level = read_le_u32(bytes, 4)?;
+        header.e32_cpu = read_le_u16(bytes, 8)?;
+        header.e32_os = read_le_u16(bytes, 10)?;
+        header.e32_ver = field(0)?;
+        header.e32_mflags = field(1)?;
+        header.e32_mpages = field(2)?;
+        header.e32_startobj = field(3)?;
+        header.e32_eip = field(4)?;
+        header.e32_stackobj = field(5)?;
+        header.e32_esp = field(6)?;
+        header.e32_pagesize = field(7)?;
+        header.e32_pageshift = field(8)?;
+        header.e32_fixupsize = field(9)?;
+        header.e32_fixupsum = field(10)?;
+        header.e32_ldrsize = field(11)?;
+        header.e32_ldrsum = field(12)?;
+        header.e32_objtab = field(13)?;
+        header.e32_objcnt = field(14)?;
+        header.e32_objmap = field(15)?;
+        header.e32_itermap = field(16)?;
+        header.e32_rsrctab = field(17)?;
+        header.e32_rsrccnt = field(18)?;
+        header.e32_restab = field(19)?;
+        header.e32_enttab = field(20)?;
+        header.e32_dirtab = field(21)?;
+        header.e32_dircnt = field(22)?;
+        header.e32_fpagetab = field(23)?;
+        header.e32_frectab = field(24)?;
+        header.e32_impmod = field(25)?;
+        header.e32_impmodcnt = field(26)?;
+        header.e32_impproc = field(27)?;
+        header.e32_pagesum = field(28)?;
+        header.e32_datapage = field(29)?;
+        header.e32_preload = field(30)?;
+        header.e32_nrestab = field(31)?;
+        header.e32_cbnrestab = field(32)?;
+        header.e32_nressum = field(33)?;
+        header.e32_autodata = field(34)?;
+        header.e32_debuginfo = field(35)?;
+        header.e32_debuglen = field(36)?;
+        header.e32_instpreload = field(37)?;
+        header.e32_instdemand = field(38)?;
+        header.e32_heapsize = field(39)?;
+        header.e32_stacksize = field(40)?;
+
+        /* LX page offsets are shifted by this, so keep it sane */
+        if header.is_lx() && header.e32_pageshift >= 32 {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        Ok(header)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageType {
+    Legal,
+    Iterated,
+    Invalid,
+    ZeroFilled,
+    Range,
+    Compressed,
+    Other(u16)
+}
+
+impl PageType {
+    pub fn to_value(page_type: PageType) -> u16 {
+        match page_type {
+            PageType::Legal => 0,
+            PageType::Iterated => 1,
+            PageType::Invalid => 2,
+            PageType::ZeroFilled => 3,
+            PageType::Range => 4,
+            PageType::Compressed => 5,
+            PageType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u16) -> PageType {
+        match value {
+            0 => PageType::Legal,
+            1 => PageType::Iterated,
+            2 => PageType::Invalid,
+            3 => PageType::ZeroFilled,
+            4 => PageType::Range,
+            5 => PageType::Compressed,
+            t => PageType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            PageType::Legal => String::from("LEGAL"),
+            PageType::Iterated => String::from("ITERATED"),
+            PageType::Invalid => String::from("INVALID"),
+            PageType::ZeroFilled => String::from("ZEROFILL"),
+            PageType::Range => String::from("RANGE"),
+            PageType::Compressed => String::from("COMPRESSED"),
+            PageType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Page {
+    offset: usize,      /* file offset of the page data */
+    size: u32,          /* size of the page data in the file */
+    flags: u16          /* see PageType */
+}
+
+impl Page {
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn get_type_as_enum(&self) -> PageType {
+        PageType::from_value(self.flags)
+    }
+
+    /* LE entries hold a 24-bit big-endian page number, counting from one,
+     * into pages that are all of the same size but for the last */
+    pub fn from_le_entry(bytes: &[u8], header: &LeHeader) ->
+        Result<Page, ButylError> {
+        let raw: &[u8] = read_bytes(bytes, 0, LE_PAGE_LEN)?;
+        let number: u32 = (raw[0] as u32) << 16 | (raw[1] as u32) << 8 |
+            raw[2] as u32;
+        let size: u32 = match number == header.e32_mpages {
+            true => header.e32_pageshift,
+            false => header.e32_pagesize
+        };
+
+        Ok(Page {
+            offset: header.e32_datapage as usize + (number.saturating_sub(1)
+                as usize) * header.e32_pagesize as usize,
+            size,
+            flags: raw[3] as u16
+        })
+    }
+
+    /* LX entries give a shifted offset from the data pages, or from the
+     * iterated pages for iterated ones, and a size of their own */
+    pub fn from_lx_entry(bytes: &[u8], header: &LeHeader) ->
+        Result<Page, ButylError> {
+        let flags: u16 = read_le_u16(bytes, 6)?;
+        let base: u32 = match (PageType::from_value(flags),
+            header.e32_itermap) {
+            (PageType::Iterated, t) if t != 0 => t,
+            _ => header.e32_datapage
+        };
+
+        Ok(Page {
+            offset: base as usize + ((read_le_u32(bytes, 0)? as usize) <<
+                header.e32_pageshift),
+            size: read_le_u16(bytes, 4)? as u32,
+            flags
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Object {
+    o32_size: u32,      /* virtual size */
+    o32_base: u32,      /* relocation base address */
+    o32_flags: u32,     /* OBJ_* */
+    o32_pagemap: u32,   /* first entry in the page table, counting from 1 */
+    o32_mapsize: u32,   /* number of page table entries */
+    o32_reserved: u32
+}
+
+impl Object {
+    pub fn get_size(&self) -> u32 {
+        self.o32_size
+    }
+
+    pub fn get_base(&self) -> u32 {
+        self.o32_base
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.o32_flags
+    }
+
+    pub fn get_pagemap(&self) -> u32 {
+        self.o32_pagemap
+    }
+
+    pub fn get_mapsize(&self) -> u32 {
+        self.o32_mapsize
+    }
+
+    /* "rwx" */
+    pub fn get_protection_as_string(&self) -> String {
+        let mut string: String = String::new();
+
+        string.push(if self.o32_flags & OBJ_READ != 0 { 'r' } else { '-' });
+        string.push(if self.o32_flags & OBJ_WRITE != 0 { 'w' } else { '-' });
+        string.push(if self.o32_flags & OBJ_EXEC != 0 { 'x' } else { '-' });
+
+        string
+    }
+
+    pub fn get_flags_as_strings(&self) -> Vec<String> {
+        OBJ_FLAG_NAMES.iter()
+            .filter(|(flag, _)| self.o32_flags & flag != 0)
+            .map(|(_, name)| String::from(*name))
+            .collect()
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Object, ButylError> {
+        Ok(Object {
+            o32_size: read_le_u32(bytes, 0)?,
+            o32_base: read_le_u32(bytes, 4)?,
+            o32_flags: read_le_u32(bytes, 8)?,
+            o32_pagemap: read_le_u32(bytes, 12)?,
+            o32_mapsize: read_le_u32(bytes, 16)?,
+            o32_reserved: read_le_u32(bytes, 20)?
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct LeFile<'a> {
+    dos_header: DosHeader,
+    header: LeHeader,
+    objects: Vec<Object>,
+    pages: Vec<Page>,
+    data: &'a[u8]
+}
+
+impl<'a> LeFile<'a> {
+    pub fn get_dos_header(&self) -> &DosHeader {
+        &self.dos_header
+    }
+
+    pub fn get_header(&self) -> &LeHeader {
+        &self.header
+    }
+
+    /* most table offsets in the header are relative to the header itself */
+    pub fn get_header_offset(&self) -> usize {
+        self.dos_header.get_lfanew() as usize
+    }
+
+    pub fn is_lx(&self) -> bool {
+        self.header.is_lx()
+    }
+
+    pub fn get_objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    pub fn get_pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /* the pages of an object, which it names by their 1-based index */
+    pub fn get_object_pages(&self, object: &Object) -> &[Page] {
+        let start: usize = object.o32_pagemap.saturating_sub(1) as usize;
+        let end: usize = start.saturating_add(object.o32_mapsize as usize);
+
+        self.pages.get(start..end.min(self.pages.len())).unwrap_or(&[])
+    }
+
+    pub fn get_page_data(&self, page: &Page) -> Result<&'a[u8], ButylError> {
+        read_bytes(self.data, page.offset, page.size as usize)
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    /* the first entry is the module name itself */
+    pub fn get_resident_names(&self) -> Result<Vec<Name>, ButylError> {
+        names::get_names_from_bytes(self.data, self.get_header_offset() +
+            self.header.e32_restab as usize)
+    }
+
+    /* the first entry is the module description */
+    pub fn get_non_resident_names(&self) -> Result<Vec<Name>, ButylError> {
+        match self.header.e32_cbnrestab {
+            0 => Ok(Vec::new()),
+            t => names::get_names_from_bytes(read_bytes(self.data,
+                self.header.e32_nrestab as usize, t as usize)?, 0)
+        }
+    }
+
+    pub fn get_module_name(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_resident_names()?.into_iter().next()
+            .map(|t| String::from(t.get_name())))
+    }
+
+    /* the modules this one imports from, indexed from 1 by fixups */
+    pub fn get_import_modules(&self) -> Result<Vec<String>, ButylError> {
+        let mut modules: Vec<String> = Vec::new();
+        let mut offset: usize = self.get_header_offset() +
+            self.header.e32_impmod as usize;
+
+        for _ in 0..self.header.e32_impmodcnt {
+            let module: String = read_pascal_string(self.data, offset)?;
+            offset += 1 + module.len();
+            modules.push(module);
+        }
+
+        Ok(modules)
+    }
+
+    /* offsets are relative to the start of the import procedure table */
+    pub fn get_import_procedure(&self, offset: u32) ->
+        Result<String, ButylError> {
+        read_pascal_string(self.data, self.get_header_offset() +
+            self.header.e32_impproc as usize + offset as usize)
+    }
+
+    pub fn from_le_bytes(data: &[u8]) -> Result<LeFile<'_>, ButylError> {
+        let dos_header: DosHeader = DosHeader::from_le_bytes(data)?;
+        let le_offset: usize = dos_header.get_lfanew() as usize;
+        let header: LeHeader = LeHeader::from_le_bytes(
+            read_bytes(data, le_offset, LE_HEADER_LEN)?)?;
+
+        let mut objects: Vec<Object> = Vec::new();
+        let object_table: usize = le_offset + header.e32_objtab as usize;
+
+        for i in 0..header.e32_objcnt as usize {
+            objects.push(Object::from_le_bytes(read_bytes(data,
+                object_table + i * LE_OBJECT_LEN, LE_OBJECT_LEN)?)?);
+        }
+
+        let mut pages: Vec<Page> = Vec::new();
+        let page_table: usize = le_offset + header.e32_objmap as usize;
+        let len: usize = match header.is_lx() {
+            true => LX_PAGE_LEN,
+            false => LE_PAGE_LEN
+        };
+
+        for i in 0..header.e32_mpages as usize {
+            let entry: &[u8] = read_bytes(data, page_table + i * len, len)?;
+
+            pages.push(match header.is_lx() {
+                true => Page::from_lx_entry(entry, &header)?,
+                false => Page::from_le_entry(entry, &header)?
+            });
+        }
+
+        Ok(LeFile {
+            dos_header,
+            header,
+            objects,
+            pages,
+            data
+        })
+    }
+}
@@ -0,0 +1,198 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::le::LeFile;
+use crate::utils::*;
+
+pub const ENTRY_EXPORTED: u8 = 0x01;
+pub const ENTRY_SHARED: u8 = 0x02;
+
+/* for a forwarder, the same bit says it imports by ordinal */
+pub const ENTRY_FORWARD_ORDINAL: u8 = 0x01;
+
+/* set on the bundle type when parameter typing information follows, which
+ * no linker is known to emit */
+pub const ENTRY_TYPEINFO: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryType {
+    Unused,
+    Entry16,
+    CallGate,
+    Entry32,
+    Forwarder,
+    Other(u8)
+}
+
+impl EntryType {
+    pub fn to_value(entry_type: EntryType) -> u8 {
+        match entry_type {
+            EntryType::Unused => 0,
+            EntryType::Entry16 => 1,
+            EntryType::CallGate => 2,
+            EntryType::Entry32 => 3,
+            EntryType::Forwarder => 4,
+            EntryType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> EntryType {
+        match value {
+            0 => EntryType::Unused,
+            1 => EntryType::Entry16,
+            2 => EntryType::CallGate,
+            3 => EntryType::Entry32,
+            4 => EntryType::Forwarder,
+            t => EntryType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            EntryType::Unused => String::from("UNUSED"),
+            EntryType::Entry16 => String::from("ENTRY16"),
+            EntryType::CallGate => String::from("GATE16"),
+            EntryType::Entry32 => String::from("ENTRY32"),
+            EntryType::Forwarder => String::from("FORWARDER"),
+            EntryType::Other(t) => format!("{:#x}", t)
+        }
+    }
+
+    /* the size of each entry after the bundle header */
+    fn get_entry_len(&self) -> Option<usize> {
+        match self {
+            EntryType::Unused => Some(0),
+            EntryType::Entry16 => Some(3),
+            EntryType::CallGate => Some(5),
+            EntryType::Entry32 => Some(5),
+            EntryType::Forwarder => Some(7),
+            EntryType::Other(_) => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryTarget {
+    Object { object: u16, offset: u32 },
+    ImportOrdinal { module: u16, ordinal: u32 },
+    ImportName { module: u16, offset: u32 }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Entry {
+    ordinal: u16,
+    kind: u8,           /* see EntryType */
+    object: u16,        /* object number, or module ordinal if forwarded */
+    flags: u8,          /* ENTRY_*, parameter word count in the top bits */
+    offset: u32         /* offset in the object, or the forwarded import */
+}
+
+impl Entry {
+    pub fn get_ordinal(&self) -> u16 {
+        self.ordinal
+    }
+
+    pub fn get_type(&self) -> u8 {
+        self.kind
+    }
+
+    pub fn get_type_as_enum(&self) -> EntryType {
+        EntryType::from_value(self.kind)
+    }
+
+    pub fn get_object(&self) -> u16 {
+        self.object
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn is_exported(&self) -> bool {
+        self.flags & ENTRY_EXPORTED != 0
+    }
+
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+
+    /* a forwarder names an import module and a procedure within it rather
+     * than an object and offset */
+    pub fn get_target(&self) -> EntryTarget {
+        match (self.get_type_as_enum(), self.flags & ENTRY_FORWARD_ORDINAL) {
+            (EntryType::Forwarder, 0) => EntryTarget::ImportName {
+                module: self.object,
+                offset: self.offset
+            },
+            (EntryType::Forwarder, _) => EntryTarget::ImportOrdinal {
+                module: self.object,
+                ordinal: self.offset
+            },
+            _ => EntryTarget::Object {
+                object: self.object,
+                offset: self.offset
+            }
+        }
+    }
+}
+
+impl<'a> LeFile<'a> {
+    /* bundles of entries sharing a type and object, each bundle numbering
+     * its entries on from the last, ending with a zero count */
+    pub fn get_entries(&self) -> Result<Vec<Entry>, ButylError> {
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut offset: usize = self.get_header_offset() +
+            self.header.get_enttab() as usize;
+        let mut ordinal: u16 = 1;
+
+        loop {
+            let count: u8 = read_u8(self.data, offset)?;
+
+            if count == 0 {
+                break;
+            }
+
+            let kind: u8 = read_u8(self.data, offset + 1)? & !ENTRY_TYPEINFO;
+            let entry_type: EntryType = EntryType::from_value(kind);
+            let len: usize = match entry_type.get_entry_len() {
+                Some(t) => t,
+                None => return Err(ButylError::MalformedDataError)
+            };
+
+            if entry_type == EntryType::Unused {
+                ordinal = ordinal.wrapping_add(count as u16);
+                offset += 2;
+                continue;
+            }
+
+            let object: u16 = read_le_u16(self.data, offset + 2)?;
+            offset += 4;
+
+            for _ in 0..count {
+                let bytes: &[u8] = read_bytes(self.data, offset, len)?;
+                let flags: u8 = read_u8(bytes, 0)?;
+
+                let (object, target): (u16, u32) = match entry_type {
+                    EntryType::Entry16 | EntryType::CallGate =>
+                        (object, read_le_u16(bytes, 1)? as u32),
+                    EntryType::Forwarder => (read_le_u16(bytes, 1)?,
+                        read_le_u32(bytes, 3)?),
+                    _ => (object, read_le_u32(bytes, 1)?)
+                };
+
+                entries.push(Entry {
+                    ordinal,
+                    kind,
+                    object,
+                    flags,
+                    offset: target
+                });
+
+                ordinal = ordinal.wrapping_add(1);
+                offset += len;
+            }
+        }
+
+        Ok(entries)
+    }
+}
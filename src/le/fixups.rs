@@ -0,0 +1,231 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::le::LeFile;
+use crate::utils::*;
+
+pub const NRSTYP: u8 = 0x0f;        /* source type mask */
+pub const NRALIAS: u8 = 0x10;       /* fixup to a 16:16 alias */
+pub const NRCHAIN: u8 = 0x20;       /* a list of source offsets follows */
+
+pub const NRRTYP: u8 = 0x03;        /* target type mask */
+pub const NRADD: u8 = 0x04;         /* additive fixup */
+pub const NR32BITOFF: u8 = 0x10;    /* 32-bit target offset */
+pub const NR32BITADD: u8 = 0x20;    /* 32-bit additive value */
+pub const NR16OBJMOD: u8 = 0x40;    /* 16-bit object or module number */
+pub const NR8BITORD: u8 = 0x80;     /* 8-bit import ordinal */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceType {
+    Byte,
+    Selector16,
+    Pointer16,
+    Offset16,
+    Pointer32,
+    Offset32,
+    Relative32,
+    Other(u8)
+}
+
+impl SourceType {
+    pub fn to_value(source: SourceType) -> u8 {
+        match source {
+            SourceType::Byte => 0x00,
+            SourceType::Selector16 => 0x02,
+            SourceType::Pointer16 => 0x03,
+            SourceType::Offset16 => 0x05,
+            SourceType::Pointer32 => 0x06,
+            SourceType::Offset32 => 0x07,
+            SourceType::Relative32 => 0x08,
+            SourceType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> SourceType {
+        match value {
+            0x00 => SourceType::Byte,
+            0x02 => SourceType::Selector16,
+            0x03 => SourceType::Pointer16,
+            0x05 => SourceType::Offset16,
+            0x06 => SourceType::Pointer32,
+            0x07 => SourceType::Offset32,
+            0x08 => SourceType::Relative32,
+            t => SourceType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SourceType::Byte => String::from("BYTE"),
+            SourceType::Selector16 => String::from("SEL16"),
+            SourceType::Pointer16 => String::from("PTR16:16"),
+            SourceType::Offset16 => String::from("OFF16"),
+            SourceType::Pointer32 => String::from("PTR16:32"),
+            SourceType::Offset32 => String::from("OFF32"),
+            SourceType::Relative32 => String::from("REL32"),
+            SourceType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixupTarget {
+    Internal { object: u16, offset: u32 },
+    ImportOrdinal { module: u16, ordinal: u32 },
+    ImportName { module: u16, offset: u32 },
+    Entry { ordinal: u16 }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fixup {
+    source: u8,             /* source type and NRALIAS/NRCHAIN */
+    flags: u8,              /* target type and size flags */
+    offsets: Vec<i16>,      /* source offsets within the page */
+    target: FixupTarget,
+    additive: Option<u32>   /* added to the target, if NRADD */
+}
+
+impl Fixup {
+    pub fn get_source(&self) -> u8 {
+        self.source
+    }
+
+    pub fn get_source_as_enum(&self) -> SourceType {
+        SourceType::from_value(self.source & NRSTYP)
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    /* a source offset may be negative when the location begins on the
+     * previous page */
+    pub fn get_offsets(&self) -> &[i16] {
+        &self.offsets
+    }
+
+    pub fn get_target(&self) -> &FixupTarget {
+        &self.target
+    }
+
+    pub fn get_additive(&self) -> Option<u32> {
+        self.additive
+    }
+
+    /* records are variable-length, so this also returns how much of `bytes`
+     * the one at `offset` took up */
+    pub fn from_le_bytes(bytes: &[u8], offset: usize) ->
+        Result<(Fixup, usize), ButylError> {
+        let source: u8 = read_u8(bytes, offset)?;
+        let flags: u8 = read_u8(bytes, offset + 1)?;
+        let mut position: usize = offset + 2;
+
+        /* with a source list, the offsets come after the target */
+        let count: usize = match source & NRCHAIN {
+            0 => {
+                position += 2;
+                0
+            },
+            _ => {
+                position += 1;
+                read_u8(bytes, offset + 2)? as usize
+            }
+        };
+
+        let mut read = |len: usize| -> Result<u32, ButylError> {
+            let value: u32 = match len {
+                1 => read_u8(bytes, position)? as u32,
+                2 => read_le_u16(bytes, position)? as u32,
+                _ => read_le_u32(bytes, position)?
+            };
+
+            position += len;
+            Ok(value)
+        };
+
+        let number_len: usize = if flags & NR16OBJMOD != 0 { 2 } else { 1 };
+        let offset_len: usize = if flags & NR32BITOFF != 0 { 4 } else { 2 };
+
+        let target: FixupTarget = match flags & NRRTYP {
+            0 => {
+                let object: u16 = read(number_len)? as u16;
+
+                /* a selector fixup has no use for an offset */
+                let offset: u32 = match SourceType::from_value(source &
+                    NRSTYP) {
+                    SourceType::Selector16 => 0,
+                    _ => read(offset_len)?
+                };
+
+                FixupTarget::Internal { object, offset }
+            },
+            1 => FixupTarget::ImportOrdinal {
+                module: read(number_len)? as u16,
+                ordinal: match flags & NR8BITORD {
+                    0 => read(offset_len)?,
+                    _ => read(1)?
+                }
+            },
+            2 => FixupTarget::ImportName {
+                module: read(number_len)? as u16,
+                offset: read(offset_len)?
+            },
+            _ => FixupTarget::Entry { ordinal: read(number_len)? as u16 }
+        };
+
+        let additive: Option<u32> = match flags & NRADD {
+            0 => None,
+            _ => Some(read(if flags & NR32BITADD != 0 { 4 } else { 2 })?)
+        };
+
+        let offsets: Vec<i16> = match source & NRCHAIN {
+            0 => vec![read_le_u16(bytes, offset + 2)? as i16],
+            _ => (0..count)
+                .map(|_| read(2).map(|t| t as u16 as i16))
+                .collect::<Result<Vec<i16>, ButylError>>()?
+        };
+
+        Ok((Fixup {
+            source,
+            flags,
+            offsets,
+            target,
+            additive
+        }, position - offset))
+    }
+}
+
+impl<'a> LeFile<'a> {
+    /* the fixup page table holds an offset into the record table for each
+     * page, plus one for the end of the last page's records */
+    pub fn get_fixups(&self, page: usize) -> Result<Vec<Fixup>, ButylError> {
+        let page_table: usize = self.get_header_offset() +
+            self.header.get_fpagetab() as usize;
+        let record_table: usize = self.get_header_offset() +
+            self.header.get_frectab() as usize;
+
+        let start: usize = read_le_u32(self.data, page_table + page * 4)?
+            as usize;
+        let end: usize = read_le_u32(self.data, page_table + page * 4 + 4)?
+            as usize;
+
+        if end < start {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let records: &[u8] = read_bytes(self.data, record_table + start,
+            end - start)?;
+        let mut fixups: Vec<Fixup> = Vec::new();
+        let mut offset: usize = 0;
+
+        while offset < records.len() {
+            let (fixup, len): (Fixup, usize) =
+                Fixup::from_le_bytes(records, offset)?;
+
+            fixups.push(fixup);
+            offset += len;
+        }
+
+        Ok(fixups)
+    }
+}
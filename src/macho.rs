@@ -0,0 +1,740 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::utils::*;
+
+pub mod commands;
+pub mod fat;
+
+pub const MH_MAGIC: u32 = 0xfeed_face;
+pub const MH_CIGAM: u32 = 0xcefa_edfe;
+pub const MH_MAGIC_64: u32 = 0xfeed_facf;
+pub const MH_CIGAM_64: u32 = 0xcffa_edfe;
+pub const MACHO_HEADER_LEN_32: usize = 28;
+pub const MACHO_HEADER_LEN_64: usize = 32;
+pub const LOAD_COMMAND_HEADER_LEN: usize = 8;
+
+pub const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+pub const CPU_ARCH_ABI64_32: u32 = 0x0200_0000;
+pub const CPU_SUBTYPE_MASK: u32 = 0xff00_0000;
+
+pub const MH_NOUNDEFS: u32 = 0x0000_0001;
+pub const MH_INCRLINK: u32 = 0x0000_0002;
+pub const MH_DYLDLINK: u32 = 0x0000_0004;
+pub const MH_BINDATLOAD: u32 = 0x0000_0008;
+pub const MH_PREBOUND: u32 = 0x0000_0010;
+pub const MH_SPLIT_SEGS: u32 = 0x0000_0020;
+pub const MH_LAZY_INIT: u32 = 0x0000_0040;
+pub const MH_TWOLEVEL: u32 = 0x0000_0080;
+pub const MH_FORCE_FLAT: u32 = 0x0000_0100;
+pub const MH_NOMULTIDEFS: u32 = 0x0000_0200;
+pub const MH_NOFIXPREBINDING: u32 = 0x0000_0400;
+pub const MH_PREBINDABLE: u32 = 0x0000_0800;
+pub const MH_ALLMODSBOUND: u32 = 0x0000_1000;
+pub const MH_SUBSECTIONS_VIA_SYMBOLS: u32 = 0x0000_2000;
+pub const MH_CANONICAL: u32 = 0x0000_4000;
+pub const MH_WEAK_DEFINES: u32 = 0x0000_8000;
+pub const MH_BINDS_TO_WEAK: u32 = 0x0001_0000;
+pub const MH_ALLOW_STACK_EXECUTION: u32 = 0x0002_0000;
+pub const MH_ROOT_SAFE: u32 = 0x0004_0000;
+pub const MH_SETUID_SAFE: u32 = 0x0008_0000;
+pub const MH_NO_REEXPORTED_DYLIBS: u32 = 0x0010_0000;
+pub const MH_PIE: u32 = 0x0020_0000;
+pub const MH_DEAD_STRIPPABLE_DYLIB: u32 = 0x0040_0000;
+pub const MH_HAS_TLV_DESCRIPTORS: u32 = 0x0080_0000;
+pub const MH_NO_HEAP_EXECUTION: u32 = 0x0100_0000;
+pub const MH_APP_EXTENSION_SAFE: u32 = 0x0200_0000;
+pub const MH_NLIST_OUTOFSYNC_WITH_DYLDINFO: u32 = 0x0400_0000;
+pub const MH_SIM_SUPPORT: u32 = 0x0800_0000;
+pub const MH_DYLIB_IN_CACHE: u32 = 0x8000_0000;
+
+const MH_FLAG_NAMES: &[(u32, &str)] = &[
+    (MH_NOUNDEFS, "NOUNDEFS"), (MH_INCRLINK, "INCRLINK"),
+    (MH_DYLDLINK, "DYLDLINK"), (MH_BINDATLOAD, "BINDATLOAD"),
+    (MH_PREBOUND, "PREBOUND"), (MH_SPLIT_SEGS, "SPLIT_SEGS"),
+    (MH_LAZY_INIT, "LAZY_INIT"), (MH_TWOLEVEL, "TWOLEVEL"),
+    (MH_FORCE_FLAT, "FORCE_FLAT"), (MH_NOMULTIDEFS, "NOMULTIDEFS"),
+    (MH_NOFIXPREBINDING, "NOFIXPREBINDING"),
+    (MH_PREBINDABLE, "PREBINDABLE"), (MH_ALLMODSBOUND, "ALLMODSBOUND"),
+    (MH_SUBSECTIONS_VIA_SYMBOLS, "SUBSECTIONS_VIA_SYMBOLS"),
+    (MH_CANONICAL, "CANONICAL"), (MH_WEAK_DEFINES, "WEAK_DEFINES"),
+    (MH_BINDS_TO_WEAK, "BINDS_TO_WEAK"),
+    (MH_ALLOW_STACK_EXECUTION, "ALLOW_STACK_EXECUTION"),
+    (MH_ROOT_SAFE, "ROOT_SAFE"), (MH_SETUID_SAFE, "SETUID_SAFE"),
+    (MH_NO_REEXPORTED_DYLIBS, "NO_REEXPORTED_DYLIBS"), (MH_PIE, "PIE"),
+    (MH_DEAD_STRIPPABLE_DYLIB, "DEAD_STRIPPABLE_DYLIB"),
+    (MH_HAS_TLV_DESCRIPTORS, "HAS_TLV_DESCRIPTORS"),
+    (MH_NO_HEAP_EXECUTION, "NO_HEAP_EXECUTION"),
+    (MH_APP_EXTENSION_SAFE, "APP_EXTENSION_SAFE"),
+    (MH_NLIST_OUTOFSYNC_WITH_DYLDINFO, "NLIST_OUTOFSYNC_WITH_DYLDINFO"),
+    (MH_SIM_SUPPORT, "SIM_SUPPORT"), (MH_DYLIB_IN_CACHE, "DYLIB_IN_CACHE")
+];
+
+/* fixed-width names such as segname are NUL-padded, not NUL-terminated */
+fn read_fixed_string(bytes: &[u8], offset: usize, len: usize) ->
+    Result<String, ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, len)?;
+    let len: usize = raw.iter().position(|t| *t == 0).unwrap_or(len);
+
+    Ok(String::from_utf8_lossy(&raw[..len]).into_owned())
+}
+
+/* packed xxxx.yy.zz as used by dylib, minos and sdk versions */
+pub fn get_version_as_string(version: u32) -> String {
+    format!("{}.{}.{}", version >> 16, (version >> 8) & 0xff, version & 0xff)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MachOEncoding {
+    is_64_bit: bool,
+    is_big_endian: bool
+}
+
+impl MachOEncoding {
+    pub fn new(is_64_bit: bool, is_big_endian: bool) -> MachOEncoding {
+        MachOEncoding {
+            is_64_bit,
+            is_big_endian
+        }
+    }
+
+    /* the magic as read little-endian also tells us the byte order */
+    pub fn from_magic(magic: u32) -> Option<MachOEncoding> {
+        match magic {
+            MH_MAGIC => Some(MachOEncoding::new(false, false)),
+            MH_CIGAM => Some(MachOEncoding::new(false, true)),
+            MH_MAGIC_64 => Some(MachOEncoding::new(true, false)),
+            MH_CIGAM_64 => Some(MachOEncoding::new(true, true)),
+            _ => None
+        }
+    }
+
+    pub fn is_64_bit(&self) -> bool {
+        self.is_64_bit
+    }
+
+    pub fn is_big_endian(&self) -> bool {
+        self.is_big_endian
+    }
+
+    pub fn get_address_len(&self) -> usize {
+        if self.is_64_bit { 8 } else { 4 }
+    }
+
+    pub fn read_u16(&self, bytes: &[u8], offset: usize) ->
+        Result<u16, ButylError> {
+        match self.is_big_endian {
+            false => read_le_u16(bytes, offset),
+            true => read_be_u16(bytes, offset)
+        }
+    }
+
+    pub fn read_u32(&self, bytes: &[u8], offset: usize) ->
+        Result<u32, ButylError> {
+        match self.is_big_endian {
+            false => read_le_u32(bytes, offset),
+            true => read_be_u32(bytes, offset)
+        }
+    }
+
+    pub fn read_u64(&self, bytes: &[u8], offset: usize) ->
+        Result<u64, ButylError> {
+        match self.is_big_endian {
+            false => read_le_u64(bytes, offset),
+            true => read_be_u64(bytes, offset)
+        }
+    }
+
+    /* a vmaddr, size or offset, which is 64 bits wide in 64-bit files */
+    pub fn read_address(&self, bytes: &[u8], offset: usize) ->
+        Result<u64, ButylError> {
+        match self.is_64_bit {
+            false => Ok(self.read_u32(bytes, offset)? as u64),
+            true => self.read_u64(bytes, offset)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CpuType {
+    X86,
+    X86_64,
+    ARM,
+    ARM64,
+    ARM64_32,
+    PowerPC,
+    PowerPC64,
+    Other(u32)
+}
+
+impl CpuType {
+    pub fn to_value(cpu_type: CpuType) -> u32 {
+        match cpu_type {
+            CpuType::X86 => 7,
+            CpuType::X86_64 => 7 | CPU_ARCH_ABI64,
+            CpuType::ARM => 12,
+            CpuType::ARM64 => 12 | CPU_ARCH_ABI64,
+            CpuType::ARM64_32 => 12 | CPU_ARCH_ABI64_32,
+            CpuType::PowerPC => 18,
+            CpuType::PowerPC64 => 18 | CPU_ARCH_ABI64,
+            CpuType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> CpuType {
+        match value {
+            0x0000_0007 => CpuType::X86,
+            0x0100_0007 => CpuType::X86_64,
+            0x0000_000c => CpuType::ARM,
+            0x0100_000c => CpuType::ARM64,
+            0x0200_000c => CpuType::ARM64_32,
+            0x0000_0012 => CpuType::PowerPC,
+            0x0100_0012 => CpuType::PowerPC64,
+            t => CpuType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            CpuType::X86 => String::from("i386"),
+            CpuType::X86_64 => String::from("x86_64"),
+            CpuType::ARM => String::from("arm"),
+            CpuType::ARM64 => String::from("arm64"),
+            CpuType::ARM64_32 => String::from("arm64_32"),
+            CpuType::PowerPC => String::from("ppc"),
+            CpuType::PowerPC64 => String::from("ppc64"),
+            CpuType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+/* the architecture name lipo and friends use, which refines the CPU type by
+ * its subtype where that matters */
+pub fn get_arch_name(cpu_type: u32, cpu_subtype: u32) -> String {
+    let cpu: CpuType = CpuType::from_value(cpu_type);
+
+    match (cpu, cpu_subtype & !CPU_SUBTYPE_MASK) {
+        (CpuType::X86_64, 8) => String::from("x86_64h"),
+        (CpuType::ARM, 5) => String::from("armv4t"),
+        (CpuType::ARM, 6) => String::from("armv6"),
+        (CpuType::ARM, 7) => String::from("armv5e"),
+        (CpuType::ARM, 9) => String::from("armv7"),
+        (CpuType::ARM, 10) => String::from("armv7f"),
+        (CpuType::ARM, 11) => String::from("armv7s"),
+        (CpuType::ARM, 12) => String::from("armv7k"),
+        (CpuType::ARM, 14) => String::from("armv6m"),
+        (CpuType::ARM, 15) => String::from("armv7m"),
+        (CpuType::ARM, 16) => String::from("armv7em"),
+        (CpuType::ARM64, 2) => String::from("arm64e"),
+        (t, _) => t.get_name()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileType {
+    Object,
+    Execute,
+    FvmLib,
+    Core,
+    Preload,
+    Dylib,
+    Dylinker,
+    Bundle,
+    DylibStub,
+    Dsym,
+    KextBundle,
+    FileSet,
+    Other(u32)
+}
+
+impl FileType {
+    pub fn to_value(file_type: FileType) -> u32 {
+        match file_type {
+            FileType::Object => 1,
+            FileType::Execute => 2,
+            FileType::FvmLib => 3,
+            FileType::Core => 4,
+            FileType::Preload => 5,
+            FileType::Dylib => 6,
+            FileType::Dylinker => 7,
+            FileType::Bundle => 8,
+            FileType::DylibStub => 9,
+            FileType::Dsym => 10,
+            FileType::KextBundle => 11,
+            FileType::FileSet => 12,
+            FileType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> FileType {
+        match value {
+            1 => FileType::Object,
+            2 => FileType::Execute,
+            3 => FileType::FvmLib,
+            4 => FileType::Core,
+            5 => FileType::Preload,
+            6 => FileType::Dylib,
+            7 => FileType::Dylinker,
+            8 => FileType::Bundle,
+            9 => FileType::DylibStub,
+            10 => FileType::Dsym,
+            11 => FileType::KextBundle,
+            12 => FileType::FileSet,
+            t => FileType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            FileType::Object => String::from("OBJECT"),
+            FileType::Execute => String::from("EXECUTE"),
+            FileType::FvmLib => String::from("FVMLIB"),
+            FileType::Core => String::from("CORE"),
+            FileType::Preload => String::from("PRELOAD"),
+            FileType::Dylib => String::from("DYLIB"),
+            FileType::Dylinker => String::from("DYLINKER"),
+            FileType::Bundle => String::from("BUNDLE"),
+            FileType::DylibStub => String::from("DYLIB_STUB"),
+            FileType::Dsym => String::from("DSYM"),
+            FileType::KextBundle => String::from("KEXT_BUNDLE"),
+            FileType::FileSet => String::from("FILESET"),
+            FileType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadCommandType {
+    Segment,
+    Symtab,
+    Symseg,
+    Thread,
+    UnixThread,
+    Dysymtab,
+    LoadDylib,
+    IdDylib,
+    LoadDylinker,
+    IdDylinker,
+    PreboundDylib,
+    Routines,
+    SubFramework,
+    SubUmbrella,
+    SubClient,
+    SubLibrary,
+    TwolevelHints,
+    PrebindCksum,
+    LoadWeakDylib,
+    Segment64,
+    Routines64,
+    Uuid,
+    Rpath,
+    CodeSignature,
+    SegmentSplitInfo,
+    ReexportDylib,
+    LazyLoadDylib,
+    EncryptionInfo,
+    DyldInfo,
+    DyldInfoOnly,
+    LoadUpwardDylib,
+    VersionMinMacOSX,
+    VersionMinIPhoneOS,
+    FunctionStarts,
+    DyldEnvironment,
+    Main,
+    DataInCode,
+    SourceVersion,
+    DylibCodeSignDrs,
+    EncryptionInfo64,
+    LinkerOption,
+    LinkerOptimizationHint,
+    VersionMinTvOS,
+    VersionMinWatchOS,
+    Note,
+    BuildVersion,
+    DyldExportsTrie,
+    DyldChainedFixups,
+    FilesetEntry,
+    Other(u32)
+}
+
+impl LoadCommandType {
+    pub fn to_value(kind: LoadCommandType) -> u32 {
+        match kind {
+            LoadCommandType::Segment => 0x01,
+            LoadCommandType::Symtab => 0x02,
+            LoadCommandType::Symseg => 0x03,
+            LoadCommandType::Thread => 0x04,
+            LoadCommandType::UnixThread => 0x05,
+            LoadCommandType::Dysymtab => 0x0b,
+            LoadCommandType::LoadDylib => 0x0c,
+            LoadCommandType::IdDylib => 0x0d,
+            LoadCommandType::LoadDylinker => 0x0e,
+            LoadCommandType::IdDylinker => 0x0f,
+            LoadCommandType::PreboundDylib => 0x10,
+            LoadCommandType::Routines => 0x11,
+            LoadCommandType::SubFramework => 0x12,
+            LoadCommandType::SubUmbrella => 0x13,
+            LoadCommandType::SubClient => 0x14,
+            LoadCommandType::SubLibrary => 0x15,
+            LoadCommandType::TwolevelHints => 0x16,
+            LoadCommandType::PrebindCksum => 0x17,
+            LoadCommandType::LoadWeakDylib => 0x8000_0018,
+            LoadCommandType::Segment64 => 0x19,
+            LoadCommandType::Routines64 => 0x1a,
+            LoadCommandType::Uuid => 0x1b,
+            LoadCommandType::Rpath => 0x8000_001c,
+            LoadCommandType::CodeSignature => 0x1d,
+            LoadCommandType::SegmentSplitInfo => 0x1e,
+            LoadCommandType::ReexportDylib => 0x8000_001f,
+            LoadCommandType::LazyLoadDylib => 0x20,
+            LoadCommandType::EncryptionInfo => 0x21,
+            LoadCommandType::DyldInfo => 0x22,
+            LoadCommandType::DyldInfoOnly => 0x8000_0022,
+            LoadCommandType::LoadUpwardDylib => 0x8000_0023,
+            LoadCommandType::VersionMinMacOSX => 0x24,
+            LoadCommandType::VersionMinIPhoneOS => 0x25,
+            LoadCommandType::FunctionStarts => 0x26,
+            LoadCommandType::DyldEnvironment => 0x27,
+            LoadCommandType::Main => 0x8000_0028,
+            LoadCommandType::DataInCode => 0x29,
+            LoadCommandType::SourceVersion => 0x2a,
+            LoadCommandType::DylibCodeSignDrs => 0x2b,
+            LoadCommandType::EncryptionInfo64 => 0x2c,
+            LoadCommandType::LinkerOption => 0x2d,
+            LoadCommandType::LinkerOptimizationHint => 0x2e,
+            LoadCommandType::VersionMinTvOS => 0x2f,
+            LoadCommandType::VersionMinWatchOS => 0x30,
+            LoadCommandType::Note => 0x31,
+            LoadCommandType::BuildVersion => 0x32,
+            LoadCommandType::DyldExportsTrie => 0x8000_0033,
+            LoadCommandType::DyldChainedFixups => 0x8000_0034,
+            LoadCommandType::FilesetEntry => 0x8000_0035,
+            LoadCommandType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> LoadCommandType {
+        match value {
+            0x01 => LoadCommandType::Segment,
+            0x02 => LoadCommandType::Symtab,
+            0x03 => LoadCommandType::Symseg,
+            0x04 => LoadCommandType::Thread,
+            0x05 => LoadCommandType::UnixThread,
+            0x0b => LoadCommandType::Dysymtab,
+            0x0c => LoadCommandType::LoadDylib,
+            0x0d => LoadCommandType::IdDylib,
+            0x0e => LoadCommandType::LoadDylinker,
+            0x0f => LoadCommandType::IdDylinker,
+            0x10 => LoadCommandType::PreboundDylib,
+            0x11 => LoadCommandType::Routines,
+            0x12 => LoadCommandType::SubFramework,
+            0x13 => LoadCommandType::SubUmbrella,
+            0x14 => LoadCommandType::SubClient,
+            0x15 => LoadCommandType::SubLibrary,
+            0x16 => LoadCommandType::TwolevelHints,
+            0x17 => LoadCommandType::PrebindCksum,
+            0x8000_0018 => LoadCommandType::LoadWeakDylib,
+            0x19 => LoadCommandType::Segment64,
+            0x1a => LoadCommandType::Routines64,
+            0x1b => LoadCommandType::Uuid,
+            0x8000_001c => LoadCommandType::Rpath,
+            0x1d => LoadCommandType::CodeSignature,
+            0x1e => LoadCommandType::SegmentSplitInfo,
+            0x8000_001f => LoadCommandType::ReexportDylib,
+            0x20 => LoadCommandType::LazyLoadDylib,
+            0x21 => LoadCommandType::EncryptionInfo,
+            0x22 => LoadCommandType::DyldInfo,
+            0x8000_0022 => LoadCommandType::DyldInfoOnly,
+            0x8000_0023 => LoadCommandType::LoadUpwardDylib,
+            0x24 => LoadCommandType::VersionMinMacOSX,
+            0x25 => LoadCommandType::VersionMinIPhoneOS,
+            0x26 => LoadCommandType::FunctionStarts,
+            0x27 => LoadCommandType::DyldEnvironment,
+            0x8000_0028 => LoadCommandType::Main,
+            0x29 => LoadCommandType::DataInCode,
+            0x2a => LoadCommandType::SourceVersion,
+            0x2b => LoadCommandType::DylibCodeSignDrs,
+            0x2c => LoadCommandType::EncryptionInfo64,
+            0x2d => LoadCommandType::LinkerOption,
+            0x2e => LoadCommandType::LinkerOptimizationHint,
+            0x2f => LoadCommandType::VersionMinTvOS,
+            0x30 => LoadCommandType::VersionMinWatchOS,
+            0x31 => LoadCommandType::Note,
+            0x32 => LoadCommandType::BuildVersion,
+            0x8000_0033 => LoadCommandType::DyldExportsTrie,
+            0x8000_0034 => LoadCommandType::DyldChainedFixups,
+            0x8000_0035 => LoadCommandType::FilesetEntry,
+            t => LoadCommandType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            LoadCommandType::Segment => String::from("LC_SEGMENT"),
+            LoadCommandType::Symtab => String::from("LC_SYMTAB"),
+            LoadCommandType::Symseg => String::from("LC_SYMSEG"),
+            LoadCommandType::Thread => String::from("LC_THREAD"),
+            LoadCommandType::UnixThread => String::from("LC_UNIXTHREAD"),
+            LoadCommandType::Dysymtab => String::from("LC_DYSYMTAB"),
+            LoadCommandType::LoadDylib => String::from("LC_LOAD_DYLIB"),
+            LoadCommandType::IdDylib => String::from("LC_ID_DYLIB"),
+            LoadCommandType::LoadDylinker => String::from("LC_LOAD_DYLINKER"),
+            LoadCommandType::IdDylinker => String::from("LC_ID_DYLINKER"),
+            LoadCommandType::PreboundDylib =>
+                String::from("LC_PREBOUND_DYLIB"),
+            LoadCommandType::Routines => String::from("LC_ROUTINES"),
+            LoadCommandType::SubFramework => String::from("LC_SUB_FRAMEWORK"),
+            LoadCommandType::SubUmbrella => String::from("LC_SUB_UMBRELLA"),
+            LoadCommandType::SubClient => String::from("LC_SUB_CLIENT"),
+            LoadCommandType::SubLibrary => String::from("LC_SUB_LIBRARY"),
+            LoadCommandType::TwolevelHints =>
+                String::from("LC_TWOLEVEL_HINTS"),
+            LoadCommandType::PrebindCksum => String::from("LC_PREBIND_CKSUM"),
+            LoadCommandType::LoadWeakDylib =>
+                String::from("LC_LOAD_WEAK_DYLIB"),
+            LoadCommandType::Segment64 => String::from("LC_SEGMENT_64"),
+            LoadCommandType::Routines64 => String::from("LC_ROUTINES_64"),
+            LoadCommandType::Uuid => String::from("LC_UUID"),
+            LoadCommandType::Rpath => String::from("LC_RPATH"),
+            LoadCommandType::CodeSignature =>
+                String::from("LC_CODE_SIGNATURE"),
+            LoadCommandType::SegmentSplitInfo =>
+                String::from("LC_SEGMENT_SPLIT_INFO"),
+            LoadCommandType::ReexportDylib =>
+                String::from("LC_REEXPORT_DYLIB"),
+            LoadCommandType::LazyLoadDylib =>
+                String::from("LC_LAZY_LOAD_DYLIB"),
+            LoadCommandType::EncryptionInfo =>
+                String::from("LC_ENCRYPTION_INFO"),
+            LoadCommandType::DyldInfo => String::from("LC_DYLD_INFO"),
+            LoadCommandType::DyldInfoOnly =>
+                String::from("LC_DYLD_INFO_ONLY"),
+            LoadCommandType::LoadUpwardDylib =>
+                String::from("LC_LOAD_UPWARD_DYLIB"),
+            LoadCommandType::VersionMinMacOSX =>
+                String::from("LC_VERSION_MIN_MACOSX"),
+            LoadCommandType::VersionMinIPhoneOS =>
+                String::from("LC_VERSION_MIN_IPHONEOS"),
+            LoadCommandType::FunctionStarts =>
+                String::from("LC_FUNCTION_STARTS"),
+            LoadCommandType::DyldEnvironment =>
+                String::from("LC_DYLD_ENVIRONMENT"),
+            LoadCommandType::Main => String::from("LC_MAIN"),
+            LoadCommandType::DataInCode => String::from("LC_DATA_IN_CODE"),
+            LoadCommandType::SourceVersion =>
+                String::from("LC_SOURCE_VERSION"),
+            LoadCommandType::DylibCodeSignDrs =>
+                String::from("LC_DYLIB_CODE_SIGN_DRS"),
+            LoadCommandType::EncryptionInfo64 =>
+                String::from("LC_ENCRYPTION_INFO_64"),
+            LoadCommandType::LinkerOption => String::from("LC_LINKER_OPTION"),
+            LoadCommandType::LinkerOptimizationHint =>
+                String::from("LC_LINKER_OPTIMIZATION_HINT"),
+            LoadCommandType::VersionMinTvOS =>
+                String::from("LC_VERSION_MIN_TVOS"),
+            LoadCommandType::VersionMinWatchOS =>
+                String::from("LC_VERSION_MIN_WATCHOS"),
+            LoadCommandType::Note => String::from("LC_NOTE"),
+            LoadCommandType::BuildVersion => String::from("LC_BUILD_VERSION"),
+            LoadCommandType::DyldExportsTrie =>
+                String::from("LC_DYLD_EXPORTS_TRIE"),
+            LoadCommandType::DyldChainedFixups =>
+                String::from("LC_DYLD_CHAINED_FIXUPS"),
+            LoadCommandType::FilesetEntry =>
+                String::from("LC_FILESET_ENTRY"),
+            LoadCommandType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MachOHeader {
+    magic: u32,         /* MH_MAGIC or MH_MAGIC_64, as stored */
+    cputype: u32,       /* CPU_TYPE_* */
+    cpusubtype: u32,    /* CPU_SUBTYPE_*, capability bits in the top byte */
+    filetype: u32,      /* MH_EXECUTE, MH_DYLIB, ... */
+    ncmds: u32,         /* number of load commands */
+    sizeofcmds: u32,    /* size of the load commands in bytes */
+    flags: u32,         /* MH_* flags */
+    reserved: u32       /* 64-bit only */
+}
+
+impl MachOHeader {
+    pub fn get_magic(&self) -> u32 {
+        self.magic
+    }
+
+    pub fn get_encoding(&self) -> MachOEncoding {
+        match MachOEncoding::from_magic(self.magic) {
+            Some(t) => t,
+            None => MachOEncoding::new(false, false)
+        }
+    }
+
+    pub fn get_cpu_type(&self) -> u32 {
+        self.cputype
+    }
+
+    pub fn get_cpu_type_as_enum(&self) -> CpuType {
+        CpuType::from_value(self.cputype)
+    }
+
+    pub fn get_cpu_subtype(&self) -> u32 {
+        self.cpusubtype
+    }
+
+    pub fn get_arch_name(&self) -> String {
+        get_arch_name(self.cputype, self.cpusubtype)
+    }
+
+    pub fn get_file_type(&self) -> u32 {
+        self.filetype
+    }
+
+    pub fn get_file_type_as_enum(&self) -> FileType {
+        FileType::from_value(self.filetype)
+    }
+
+    pub fn get_ncmds(&self) -> u32 {
+        self.ncmds
+    }
+
+    pub fn get_sizeofcmds(&self) -> u32 {
+        self.sizeofcmds
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_flags_as_strings(&self) -> Vec<String> {
+        MH_FLAG_NAMES.iter()
+            .filter(|(flag, _)| self.flags & flag != 0)
+            .map(|(_, name)| String::from(*name))
+            .collect()
+    }
+
+    pub fn get_len(&self) -> usize {
+        match self.get_encoding().is_64_bit() {
+            true => MACHO_HEADER_LEN_64,
+            false => MACHO_HEADER_LEN_32
+        }
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<MachOHeader, ButylError> {
+        let mut header: MachOHeader = MachOHeader::default();
+
+        header.magic = read_le_u32(bytes, 0)?;
+        let encoding: MachOEncoding = match MachOEncoding::from_magic(
+            header.magic) {
+            Some(t) => t,
+            None => return Err(ButylError::InvalidMagicError)
+        };
+
+        header.cputype = encoding.read_u32(bytes, 4)?;
+        header.cpusubtype = encoding.read_u32(bytes, 8)?;
+        header.filetype = encoding.read_u32(bytes, 12)?;
+        header.ncmds = encoding.read_u32(bytes, 16)?;
+        header.sizeofcmds = encoding.read_u32(bytes, 20)?;
+        header.flags = encoding.read_u32(bytes, 24)?;
+
+        if encoding.is_64_bit() {
+            header.reserved = encoding.read_u32(bytes, 28)?;
+        }
+
+        Ok(header)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LoadCommand {
+    cmd: u32,           /* LC_* */
+    cmdsize: u32,       /* size including this header */
+    offset: usize       /* file offset of the command */
+}
+
+impl LoadCommand {
+    pub fn get_cmd(&self) -> u32 {
+        self.cmd
+    }
+
+    pub fn get_type_as_enum(&self) -> LoadCommandType {
+        LoadCommandType::from_value(self.cmd)
+    }
+
+    pub fn get_cmdsize(&self) -> u32 {
+        self.cmdsize
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(Debug)]
+pub struct MachOFile<'a> {
+    header: MachOHeader,
+    load_commands: Vec<LoadCommand>,
+    data: &'a[u8]
+}
+
+impl<'a> MachOFile<'a> {
+    pub fn get_header(&self) -> &MachOHeader {
+        &self.header
+    }
+
+    pub fn get_encoding(&self) -> MachOEncoding {
+        self.header.get_encoding()
+    }
+
+    pub fn get_load_commands(&self) -> &[LoadCommand] {
+        &self.load_commands
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    /* the whole command, header included */
+    pub fn get_load_command_data(&self, command: &LoadCommand) ->
+        Result<&'a[u8], ButylError> {
+        read_bytes(self.data, command.offset, command.cmdsize as usize)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<MachOFile<'_>, ButylError> {
+        let header: MachOHeader = MachOHeader::from_bytes(data)?;
+        let encoding: MachOEncoding = header.get_encoding();
+
+        let mut load_commands: Vec<LoadCommand> = Vec::new();
+        let mut offset: usize = header.get_len();
+        let end: usize = offset + header.get_sizeofcmds() as usize;
+
+        for _ in 0..header.get_ncmds() {
+            let command: LoadCommand = LoadCommand {
+                cmd: encoding.read_u32(data, offset)?,
+                cmdsize: encoding.read_u32(data, offset + 4)?,
+                offset
+            };
+
+            /* a command must at least cover its own header and stay within
+             * sizeofcmds, or we would never get to the next one */
+            if (command.cmdsize as usize) < LOAD_COMMAND_HEADER_LEN ||
+                offset + command.cmdsize as usize > end {
+                return Err(ButylError::MalformedDataError);
+            }
+
+            offset += command.cmdsize as usize;
+            load_commands.push(command);
+        }
+
+        Ok(MachOFile {
+            header,
+            load_commands,
+            data
+        })
+    }
+}
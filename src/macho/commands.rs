@@ -0,0 +1,803 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::macho::{self, MachOFile, MachOEncoding, LoadCommand,
+    LoadCommandType};
+use crate::utils::*;
+
+pub const SEGMENT_COMMAND_LEN_32: usize = 56;
+pub const SEGMENT_COMMAND_LEN_64: usize = 72;
+pub const SECTION_LEN_32: usize = 68;
+pub const SECTION_LEN_64: usize = 80;
+pub const MACHO_NAME_LEN: usize = 16;
+pub const UUID_LEN: usize = 16;
+
+pub const VM_PROT_READ: u32 = 0x1;
+pub const VM_PROT_WRITE: u32 = 0x2;
+pub const VM_PROT_EXECUTE: u32 = 0x4;
+
+pub const SECTION_TYPE: u32 = 0x0000_00ff;
+
+/* "r-x" */
+pub fn get_protection_as_string(protection: u32) -> String {
+    let mut string: String = String::new();
+
+    string.push(if protection & VM_PROT_READ != 0 { 'r' } else { '-' });
+    string.push(if protection & VM_PROT_WRITE != 0 { 'w' } else { '-' });
+    string.push(if protection & VM_PROT_EXECUTE != 0 { 'x' } else { '-' });
+
+    string
+}
+
+/* the usual 8-4-4-4-12 form, in upper case as Apple's tools print it */
+pub fn get_uuid_as_string(uuid: &[u8; UUID_LEN]) -> String {
+    let hex: Vec<String> = uuid.iter().map(|t| format!("{:02X}", t))
+        .collect();
+
+    format!("{}-{}-{}-{}-{}", hex[0..4].concat(), hex[4..6].concat(),
+        hex[6..8].concat(), hex[8..10].concat(), hex[10..16].concat())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Platform {
+    MacOS,
+    IOS,
+    TvOS,
+    WatchOS,
+    BridgeOS,
+    MacCatalyst,
+    IOSSimulator,
+    TvOSSimulator,
+    WatchOSSimulator,
+    DriverKit,
+    VisionOS,
+    VisionOSSimulator,
+    Other(u32)
+}
+
+impl Platform {
+    pub fn to_value(platform: Platform) -> u32 {
+        match platform {
+            Platform::MacOS => 1,
+            Platform::IOS => 2,
+            Platform::TvOS => 3,
+            Platform::WatchOS => 4,
+            Platform::BridgeOS => 5,
+            Platform::MacCatalyst => 6,
+            Platform::IOSSimulator => 7,
+            Platform::TvOSSimulator => 8,
+            Platform::WatchOSSimulator => 9,
+            Platform::DriverKit => 10,
+            Platform::VisionOS => 11,
+            Platform::VisionOSSimulator => 12,
+            Platform::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> Platform {
+        match value {
+            1 => Platform::MacOS,
+            2 => Platform::IOS,
+            3 => Platform::TvOS,
+            4 => Platform::WatchOS,
+            5 => Platform::BridgeOS,
+            6 => Platform::MacCatalyst,
+            7 => Platform::IOSSimulator,
+            8 => Platform::TvOSSimulator,
+            9 => Platform::WatchOSSimulator,
+            10 => Platform::DriverKit,
+            11 => Platform::VisionOS,
+            12 => Platform::VisionOSSimulator,
+            t => Platform::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Platform::MacOS => String::from("MACOS"),
+            Platform::IOS => String::from("IOS"),
+            Platform::TvOS => String::from("TVOS"),
+            Platform::WatchOS => String::from("WATCHOS"),
+            Platform::BridgeOS => String::from("BRIDGEOS"),
+            Platform::MacCatalyst => String::from("MACCATALYST"),
+            Platform::IOSSimulator => String::from("IOSSIMULATOR"),
+            Platform::TvOSSimulator => String::from("TVOSSIMULATOR"),
+            Platform::WatchOSSimulator => String::from("WATCHOSSIMULATOR"),
+            Platform::DriverKit => String::from("DRIVERKIT"),
+            Platform::VisionOS => String::from("VISIONOS"),
+            Platform::VisionOSSimulator => String::from("VISIONOSSIMULATOR"),
+            Platform::Other(t) => format!("{}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildTool {
+    Clang,
+    Swift,
+    Ld,
+    Lld,
+    Other(u32)
+}
+
+impl BuildTool {
+    pub fn to_value(tool: BuildTool) -> u32 {
+        match tool {
+            BuildTool::Clang => 1,
+            BuildTool::Swift => 2,
+            BuildTool::Ld => 3,
+            BuildTool::Lld => 4,
+            BuildTool::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> BuildTool {
+        match value {
+            1 => BuildTool::Clang,
+            2 => BuildTool::Swift,
+            3 => BuildTool::Ld,
+            4 => BuildTool::Lld,
+            t => BuildTool::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            BuildTool::Clang => String::from("CLANG"),
+            BuildTool::Swift => String::from("SWIFT"),
+            BuildTool::Ld => String::from("LD"),
+            BuildTool::Lld => String::from("LLD"),
+            BuildTool::Other(t) => format!("{}", t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Section {
+    sectname: String,   /* e.g. __text */
+    segname: String,    /* the segment it belongs to */
+    addr: u64,          /* virtual address */
+    size: u64,          /* size in bytes */
+    offset: u32,        /* file offset, zero for zerofill sections */
+    align: u32,         /* alignment as a power of two */
+    reloff: u32,        /* file offset of the relocation entries */
+    nreloc: u32,        /* number of relocation entries */
+    flags: u32,         /* section type (low byte) and attributes */
+    reserved1: u32,     /* index or count, depending on the type */
+    reserved2: u32
+}
+
+impl Section {
+    pub fn get_name(&self) -> &str {
+        &self.sectname
+    }
+
+    pub fn get_segment_name(&self) -> &str {
+        &self.segname
+    }
+
+    pub fn get_addr(&self) -> u64 {
+        self.addr
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn get_align(&self) -> u32 {
+        self.align
+    }
+
+    pub fn get_reloff(&self) -> u32 {
+        self.reloff
+    }
+
+    pub fn get_nreloc(&self) -> u32 {
+        self.nreloc
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_type(&self) -> u32 {
+        self.flags & SECTION_TYPE
+    }
+
+    pub fn get_reserved1(&self) -> u32 {
+        self.reserved1
+    }
+
+    pub fn get_reserved2(&self) -> u32 {
+        self.reserved2
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<Section, ButylError> {
+        let mut section: Section = Section::default();
+        let address_len: usize = encoding.get_address_len();
+        let fields: usize = 32 + address_len * 2;
+
+        section.sectname = macho::read_fixed_string(bytes, 0,
+            MACHO_NAME_LEN)?;
+        section.segname = macho::read_fixed_string(bytes, MACHO_NAME_LEN,
+            MACHO_NAME_LEN)?;
+        section.addr = encoding.read_address(bytes, 32)?;
+        section.size = encoding.read_address(bytes, 32 + address_len)?;
+        section.offset = encoding.read_u32(bytes, fields)?;
+        section.align = encoding.read_u32(bytes, fields + 4)?;
+        section.reloff = encoding.read_u32(bytes, fields + 8)?;
+        section.nreloc = encoding.read_u32(bytes, fields + 12)?;
+        section.flags = encoding.read_u32(bytes, fields + 16)?;
+        section.reserved1 = encoding.read_u32(bytes, fields + 20)?;
+        section.reserved2 = encoding.read_u32(bytes, fields + 24)?;
+
+        Ok(section)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Segment {
+    segname: String,    /* e.g. __TEXT */
+    vmaddr: u64,        /* virtual address */
+    vmsize: u64,        /* size in memory */
+    fileoff: u64,       /* file offset of the contents */
+    filesize: u64,      /* size in the file */
+    maxprot: u32,       /* maximum VM protection */
+    initprot: u32,      /* initial VM protection */
+    flags: u32,         /* SG_* */
+    sections: Vec<Section>
+}
+
+impl Segment {
+    pub fn get_name(&self) -> &str {
+        &self.segname
+    }
+
+    pub fn get_vmaddr(&self) -> u64 {
+        self.vmaddr
+    }
+
+    pub fn get_vmsize(&self) -> u64 {
+        self.vmsize
+    }
+
+    pub fn get_fileoff(&self) -> u64 {
+        self.fileoff
+    }
+
+    pub fn get_filesize(&self) -> u64 {
+        self.filesize
+    }
+
+    pub fn get_maxprot(&self) -> u32 {
+        self.maxprot
+    }
+
+    pub fn get_initprot(&self) -> u32 {
+        self.initprot
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /* LC_SEGMENT and LC_SEGMENT_64 differ only in the width of the address
+     * and size fields, in the command and in each section after it */
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<Segment, ButylError> {
+        let mut segment: Segment = Segment::default();
+        let address_len: usize = encoding.get_address_len();
+        let fields: usize = 24 + address_len * 4;
+
+        segment.segname = macho::read_fixed_string(bytes, 8, MACHO_NAME_LEN)?;
+        segment.vmaddr = encoding.read_address(bytes, 24)?;
+        segment.vmsize = encoding.read_address(bytes, 24 + address_len)?;
+        segment.fileoff = encoding.read_address(bytes, 24 + address_len * 2)?;
+        segment.filesize =
+            encoding.read_address(bytes, 24 + address_len * 3)?;
+        segment.maxprot = encoding.read_u32(bytes, fields)?;
+        segment.initprot = encoding.read_u32(bytes, fields + 4)?;
+        segment.flags = encoding.read_u32(bytes, fields + 12)?;
+
+        let nsects: u32 = encoding.read_u32(bytes, fields + 8)?;
+        let (offset, len): (usize, usize) = match encoding.is_64_bit() {
+            true => (SEGMENT_COMMAND_LEN_64, SECTION_LEN_64),
+            false => (SEGMENT_COMMAND_LEN_32, SECTION_LEN_32)
+        };
+
+        for i in 0..nsects as usize {
+            segment.sections.push(Section::from_bytes(
+                read_bytes(bytes, offset + i * len, len)?, encoding)?);
+        }
+
+        Ok(segment)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SymtabCommand {
+    symoff: u32,        /* file offset of the nlist entries */
+    nsyms: u32,         /* number of nlist entries */
+    stroff: u32,        /* file offset of the string table */
+    strsize: u32        /* size of the string table */
+}
+
+impl SymtabCommand {
+    pub fn get_symoff(&self) -> u32 {
+        self.symoff
+    }
+
+    pub fn get_nsyms(&self) -> u32 {
+        self.nsyms
+    }
+
+    pub fn get_stroff(&self) -> u32 {
+        self.stroff
+    }
+
+    pub fn get_strsize(&self) -> u32 {
+        self.strsize
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<SymtabCommand, ButylError> {
+        Ok(SymtabCommand {
+            symoff: encoding.read_u32(bytes, 8)?,
+            nsyms: encoding.read_u32(bytes, 12)?,
+            stroff: encoding.read_u32(bytes, 16)?,
+            strsize: encoding.read_u32(bytes, 20)?
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DysymtabCommand {
+    ilocalsym: u32,     /* index and count of local symbols */
+    nlocalsym: u32,
+    iextdefsym: u32,    /* index and count of defined external symbols */
+    nextdefsym: u32,
+    iundefsym: u32,     /* index and count of undefined symbols */
+    nundefsym: u32,
+    tocoff: u32,        /* table of contents, for dynamic libraries */
+    ntoc: u32,
+    modtaboff: u32,     /* module table */
+    nmodtab: u32,
+    extrefsymoff: u32,  /* referenced symbol table */
+    nextrefsyms: u32,
+    indirectsymoff: u32,/* indirect symbol table */
+    nindirectsyms: u32,
+    extreloff: u32,     /* external relocation entries */
+    nextrel: u32,
+    locreloff: u32,     /* local relocation entries */
+    nlocrel: u32
+}
+
+impl DysymtabCommand {
+    pub fn get_ilocalsym(&self) -> u32 {
+        self.ilocalsym
+    }
+
+    pub fn get_nlocalsym(&self) -> u32 {
+        self.nlocalsym
+    }
+
+    pub fn get_iextdefsym(&self) -> u32 {
+        self.iextdefsym
+    }
+
+    pub fn get_nextdefsym(&self) -> u32 {
+        self.nextdefsym
+    }
+
+    pub fn get_iundefsym(&self) -> u32 {
+        self.iundefsym
+    }
+
+    pub fn get_nundefsym(&self) -> u32 {
+        self.nundefsym
+    }
+
+    pub fn get_tocoff(&self) -> u32 {
+        self.tocoff
+    }
+
+    pub fn get_ntoc(&self) -> u32 {
+        self.ntoc
+    }
+
+    pub fn get_modtaboff(&self) -> u32 {
+        self.modtaboff
+    }
+
+    pub fn get_nmodtab(&self) -> u32 {
+        self.nmodtab
+    }
+
+    pub fn get_extrefsymoff(&self) -> u32 {
+        self.extrefsymoff
+    }
+
+    pub fn get_nextrefsyms(&self) -> u32 {
+        self.nextrefsyms
+    }
+
+    pub fn get_indirectsymoff(&self) -> u32 {
+        self.indirectsymoff
+    }
+
+    pub fn get_nindirectsyms(&self) -> u32 {
+        self.nindirectsyms
+    }
+
+    pub fn get_extreloff(&self) -> u32 {
+        self.extreloff
+    }
+
+    pub fn get_nextrel(&self) -> u32 {
+        self.nextrel
+    }
+
+    pub fn get_locreloff(&self) -> u32 {
+        self.locreloff
+    }
+
+    pub fn get_nlocrel(&self) -> u32 {
+        self.nlocrel
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<DysymtabCommand, ButylError> {
+        let field = |i: usize| encoding.read_u32(bytes, 8 + i * 4);
+
+        Ok(DysymtabCommand {
+            ilocalsym: field(0)?,
+            nlocalsym: field(1)?,
+            iextdefsym: field(2)?,
+            nextdefsym: field(3)?,
+            iundefsym: field(4)?,
+            nundefsym: field(5)?,
+            tocoff: field(6)?,
+            ntoc: field(7)?,
+            modtaboff: field(8)?,
+            nmodtab: field(9)?,
+            extrefsymoff: field(10)?,
+            nextrefsyms: field(11)?,
+            indirectsymoff: field(12)?,
+            nindirectsyms: field(13)?,
+            extreloff: field(14)?,
+            nextrel: field(15)?,
+            locreloff: field(16)?,
+            nlocrel: field(17)?
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DylibCommand {
+    cmd: u32,           /* LC_LOAD_DYLIB, LC_ID_DYLIB, ... */
+    name: String,       /* install name */
+    timestamp: u32,     /* build time stamp */
+    current_version: u32,
+    compatibility_version: u32
+}
+
+impl DylibCommand {
+    pub fn get_cmd(&self) -> u32 {
+        self.cmd
+    }
+
+    pub fn get_type_as_enum(&self) -> LoadCommandType {
+        LoadCommandType::from_value(self.cmd)
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    pub fn get_current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    pub fn get_compatibility_version(&self) -> u32 {
+        self.compatibility_version
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<DylibCommand, ButylError> {
+        Ok(DylibCommand {
+            cmd: encoding.read_u32(bytes, 0)?,
+            name: read_lc_str(bytes, encoding, 8)?,
+            timestamp: encoding.read_u32(bytes, 12)?,
+            current_version: encoding.read_u32(bytes, 16)?,
+            compatibility_version: encoding.read_u32(bytes, 20)?
+        })
+    }
+}
+
+/* an lc_str is an offset from the start of the command to a string stored
+ * within it */
+fn read_lc_str(bytes: &[u8], encoding: MachOEncoding, offset: usize) ->
+    Result<String, ButylError> {
+    let start: usize = encoding.read_u32(bytes, offset)? as usize;
+    let raw: &[u8] = match bytes.get(start..) {
+        Some(t) => t,
+        None => return Err(ButylError::MalformedDataError)
+    };
+    let len: usize = raw.iter().position(|t| *t == 0).unwrap_or(raw.len());
+
+    Ok(String::from_utf8_lossy(&raw[..len]).into_owned())
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BuildVersion {
+    platform: u32,      /* PLATFORM_* */
+    minos: u32,         /* minimum OS version, xxxx.yy.zz */
+    sdk: u32,           /* SDK version, xxxx.yy.zz */
+    tools: Vec<(u32, u32)>  /* tool and its version */
+}
+
+impl BuildVersion {
+    pub fn get_platform(&self) -> u32 {
+        self.platform
+    }
+
+    pub fn get_platform_as_enum(&self) -> Platform {
+        Platform::from_value(self.platform)
+    }
+
+    pub fn get_minos(&self) -> u32 {
+        self.minos
+    }
+
+    pub fn get_sdk(&self) -> u32 {
+        self.sdk
+    }
+
+    pub fn get_tools(&self) -> &[(u32, u32)] {
+        &self.tools
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<BuildVersion, ButylError> {
+        let mut build_version: BuildVersion = BuildVersion::default();
+
+        build_version.platform = encoding.read_u32(bytes, 8)?;
+        build_version.minos = encoding.read_u32(bytes, 12)?;
+        build_version.sdk = encoding.read_u32(bytes, 16)?;
+
+        for i in 0..encoding.read_u32(bytes, 20)? as usize {
+            build_version.tools.push((encoding.read_u32(bytes, 24 + i * 8)?,
+                encoding.read_u32(bytes, 28 + i * 8)?));
+        }
+
+        Ok(build_version)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LinkeditDataCommand {
+    cmd: u32,           /* LC_CODE_SIGNATURE, LC_FUNCTION_STARTS, ... */
+    dataoff: u32,       /* file offset of the data in __LINKEDIT */
+    datasize: u32
+}
+
+impl LinkeditDataCommand {
+    pub fn get_cmd(&self) -> u32 {
+        self.cmd
+    }
+
+    pub fn get_type_as_enum(&self) -> LoadCommandType {
+        LoadCommandType::from_value(self.cmd)
+    }
+
+    pub fn get_dataoff(&self) -> u32 {
+        self.dataoff
+    }
+
+    pub fn get_datasize(&self) -> u32 {
+        self.datasize
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<LinkeditDataCommand, ButylError> {
+        Ok(LinkeditDataCommand {
+            cmd: encoding.read_u32(bytes, 0)?,
+            dataoff: encoding.read_u32(bytes, 8)?,
+            datasize: encoding.read_u32(bytes, 12)?
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct EntryPointCommand {
+    entryoff: u64,      /* file offset of main(), relative to __TEXT */
+    stacksize: u64      /* initial stack size, if not the default */
+}
+
+impl EntryPointCommand {
+    pub fn get_entryoff(&self) -> u64 {
+        self.entryoff
+    }
+
+    pub fn get_stacksize(&self) -> u64 {
+        self.stacksize
+    }
+
+    pub fn from_bytes(bytes: &[u8], encoding: MachOEncoding) ->
+        Result<EntryPointCommand, ButylError> {
+        Ok(EntryPointCommand {
+            entryoff: encoding.read_u64(bytes, 8)?,
+            stacksize: encoding.read_u64(bytes, 16)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LoadCommandInfo {
+    Segment(Segment),
+    Symtab(SymtabCommand),
+    Dysymtab(DysymtabCommand),
+    Dylib(DylibCommand),
+    Path(String),       /* dylinker, rpath or dyld environment string */
+    Uuid([u8; UUID_LEN]),
+    BuildVersion(BuildVersion),
+    LinkeditData(LinkeditDataCommand),
+    EntryPoint(EntryPointCommand)
+}
+
+impl<'a> MachOFile<'a> {
+    /* decodes the payload of the command types we understand */
+    pub fn get_load_command_info(&self, command: &LoadCommand) ->
+        Result<Option<LoadCommandInfo>, ButylError> {
+        let bytes: &[u8] = self.get_load_command_data(command)?;
+        let encoding: MachOEncoding = self.get_encoding();
+
+        Ok(match command.get_type_as_enum() {
+            LoadCommandType::Segment | LoadCommandType::Segment64 =>
+                Some(LoadCommandInfo::Segment(
+                    Segment::from_bytes(bytes, encoding)?)),
+            LoadCommandType::Symtab => Some(LoadCommandInfo::Symtab(
+                SymtabCommand::from_bytes(bytes, encoding)?)),
+            LoadCommandType::Dysymtab => Some(LoadCommandInfo::Dysymtab(
+                DysymtabCommand::from_bytes(bytes, encoding)?)),
+            LoadCommandType::LoadDylib | LoadCommandType::IdDylib |
+            LoadCommandType::LoadWeakDylib | LoadCommandType::ReexportDylib |
+            LoadCommandType::LazyLoadDylib |
+            LoadCommandType::LoadUpwardDylib => Some(LoadCommandInfo::Dylib(
+                DylibCommand::from_bytes(bytes, encoding)?)),
+            LoadCommandType::LoadDylinker | LoadCommandType::IdDylinker |
+            LoadCommandType::Rpath | LoadCommandType::DyldEnvironment =>
+                Some(LoadCommandInfo::Path(read_lc_str(bytes, encoding, 8)?)),
+            LoadCommandType::Uuid => {
+                let mut uuid: [u8; UUID_LEN] = [0; UUID_LEN];
+                uuid.copy_from_slice(read_bytes(bytes, 8, UUID_LEN)?);
+                Some(LoadCommandInfo::Uuid(uuid))
+            },
+            LoadCommandType::BuildVersion => Some(
+                LoadCommandInfo::BuildVersion(
+                    BuildVersion::from_bytes(bytes, encoding)?)),
+            LoadCommandType::CodeSignature |
+            LoadCommandType::SegmentSplitInfo |
+            LoadCommandType::FunctionStarts | LoadCommandType::DataInCode |
+            LoadCommandType::DylibCodeSignDrs |
+            LoadCommandType::LinkerOptimizationHint |
+            LoadCommandType::DyldExportsTrie |
+            LoadCommandType::DyldChainedFixups =>
+                Some(LoadCommandInfo::LinkeditData(
+                    LinkeditDataCommand::from_bytes(bytes, encoding)?)),
+            LoadCommandType::Main => Some(LoadCommandInfo::EntryPoint(
+                EntryPointCommand::from_bytes(bytes, encoding)?)),
+            _ => None
+        })
+    }
+
+    fn get_load_command_infos(&self) ->
+        Result<Vec<LoadCommandInfo>, ButylError> {
+        let mut infos: Vec<LoadCommandInfo> = Vec::new();
+
+        for command in self.get_load_commands() {
+            if let Some(t) = self.get_load_command_info(command)? {
+                infos.push(t);
+            }
+        }
+
+        Ok(infos)
+    }
+
+    pub fn get_segments(&self) -> Result<Vec<Segment>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .filter_map(|t| match t {
+                LoadCommandInfo::Segment(u) => Some(u),
+                _ => None
+            })
+            .collect())
+    }
+
+    pub fn get_symtab(&self) -> Result<Option<SymtabCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::Symtab(u) => Some(u),
+                _ => None
+            }))
+    }
+
+    pub fn get_dysymtab(&self) ->
+        Result<Option<DysymtabCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::Dysymtab(u) => Some(u),
+                _ => None
+            }))
+    }
+
+    /* the libraries this file depends on, leaving out its own LC_ID_DYLIB */
+    pub fn get_dylibs(&self) -> Result<Vec<DylibCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .filter_map(|t| match t {
+                LoadCommandInfo::Dylib(u) if u.get_type_as_enum() !=
+                    LoadCommandType::IdDylib => Some(u),
+                _ => None
+            })
+            .collect())
+    }
+
+    pub fn get_id_dylib(&self) -> Result<Option<DylibCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::Dylib(u) if u.get_type_as_enum() ==
+                    LoadCommandType::IdDylib => Some(u),
+                _ => None
+            }))
+    }
+
+    pub fn get_uuid(&self) -> Result<Option<[u8; UUID_LEN]>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::Uuid(u) => Some(u),
+                _ => None
+            }))
+    }
+
+    pub fn get_build_version(&self) ->
+        Result<Option<BuildVersion>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::BuildVersion(u) => Some(u),
+                _ => None
+            }))
+    }
+
+    pub fn get_code_signature(&self) ->
+        Result<Option<LinkeditDataCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::LinkeditData(u) if u.get_type_as_enum() ==
+                    LoadCommandType::CodeSignature => Some(u),
+                _ => None
+            }))
+    }
+
+    pub fn get_entry_point(&self) ->
+        Result<Option<EntryPointCommand>, ButylError> {
+        Ok(self.get_load_command_infos()?.into_iter()
+            .find_map(|t| match t {
+                LoadCommandInfo::EntryPoint(u) => Some(u),
+                _ => None
+            }))
+    }
+}
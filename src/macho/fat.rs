@@ -0,0 +1,146 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::macho::{self, MachOFile};
+use crate::utils::*;
+
+pub const FAT_MAGIC: u32 = 0xcafe_babe;
+pub const FAT_MAGIC_64: u32 = 0xcafe_babf;
+pub const FAT_HEADER_LEN: usize = 8;
+pub const FAT_ARCH_LEN_32: usize = 20;
+pub const FAT_ARCH_LEN_64: usize = 32;
+
+/* Java class files share 0xcafebabe, but their next field is the class file
+ * version, which is never this small */
+pub const FAT_MAX_ARCHES: u32 = 32;
+
+#[derive(Debug, Default, Clone)]
+pub struct FatArch {
+    cputype: u32,       /* CPU_TYPE_* */
+    cpusubtype: u32,    /* CPU_SUBTYPE_* */
+    offset: u64,        /* file offset of the slice */
+    size: u64,          /* size of the slice */
+    align: u32          /* alignment of the slice as a power of two */
+}
+
+impl FatArch {
+    pub fn get_cpu_type(&self) -> u32 {
+        self.cputype
+    }
+
+    pub fn get_cpu_subtype(&self) -> u32 {
+        self.cpusubtype
+    }
+
+    pub fn get_arch_name(&self) -> String {
+        macho::get_arch_name(self.cputype, self.cpusubtype)
+    }
+
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn get_align(&self) -> u32 {
+        self.align
+    }
+
+    /* fat headers are always big-endian, whatever the slices are */
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_bytes(bytes: &[u8], is_64_bit: bool) ->
+        Result<FatArch, ButylError> {
+        let mut arch: FatArch = FatArch::default();
+
+        arch.cputype = read_be_u32(bytes, 0)?;
+        arch.cpusubtype = read_be_u32(bytes, 4)?;
+
+        match is_64_bit {
+            false => {
+                arch.offset = read_be_u32(bytes, 8)? as u64;
+                arch.size = read_be_u32(bytes, 12)? as u64;
+                arch.align = read_be_u32(bytes, 16)?;
+            },
+            true => {
+                arch.offset = read_be_u64(bytes, 8)?;
+                arch.size = read_be_u64(bytes, 16)?;
+                arch.align = read_be_u32(bytes, 24)?;
+            }
+        }
+
+        Ok(arch)
+    }
+}
+
+#[derive(Debug)]
+pub struct FatFile<'a> {
+    magic: u32,         /* FAT_MAGIC or FAT_MAGIC_64 */
+    arches: Vec<FatArch>,
+    data: &'a[u8]
+}
+
+impl<'a> FatFile<'a> {
+    pub fn get_magic(&self) -> u32 {
+        self.magic
+    }
+
+    pub fn is_64_bit(&self) -> bool {
+        self.magic == FAT_MAGIC_64
+    }
+
+    pub fn get_arches(&self) -> &[FatArch] {
+        &self.arches
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn get_arch(&self, name: &str) -> Option<&FatArch> {
+        self.arches.iter().find(|t| t.get_arch_name() == name)
+    }
+
+    pub fn get_slice(&self, arch: &FatArch) -> Result<&'a[u8], ButylError> {
+        read_bytes(self.data, arch.offset as usize, arch.size as usize)
+    }
+
+    pub fn get_macho(&self, arch: &FatArch) ->
+        Result<MachOFile<'a>, ButylError> {
+        MachOFile::from_bytes(self.get_slice(arch)?)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<FatFile<'_>, ButylError> {
+        let magic: u32 = read_be_u32(data, 0)?;
+        let len: usize = match magic {
+            FAT_MAGIC => FAT_ARCH_LEN_32,
+            FAT_MAGIC_64 => FAT_ARCH_LEN_64,
+            _ => return Err(ButylError::InvalidMagicError)
+        };
+
+        let nfat_arch: u32 = read_be_u32(data, 4)?;
+
+        if nfat_arch > FAT_MAX_ARCHES {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let mut arches: Vec<FatArch> = Vec::new();
+
+        for i in 0..nfat_arch as usize {
+            let arch: FatArch = FatArch::from_bytes(
+                read_bytes(data, FAT_HEADER_LEN + i * len, len)?,
+                magic == FAT_MAGIC_64)?;
+
+            /* make sure get_slice cannot fail later on */
+            read_bytes(data, arch.offset as usize, arch.size as usize)?;
+            arches.push(arch);
+        }
+
+        Ok(FatFile {
+            magic,
+            arches,
+            data
+        })
+    }
+}
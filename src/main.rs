@@ -1,45 +1,334 @@
 extern crate structopt;
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process;
 
 use structopt::StructOpt;
 
+use crate::pe::address::AddressKind;
+
 mod errors;
 mod dos;
+mod com;
 mod coff;
+mod pe;
+mod ne;
+mod le;
+mod elf;
+mod macho;
 mod formats;
+mod der;
+mod display;
 mod utils;
 
 #[derive(Debug, StructOpt)]
 pub struct Cli {
     path: std::path::PathBuf,
-    
+
     #[structopt(short="f", long="format")]
     format: Option<String>,
 
     #[structopt(short, long)]
     interactive: bool,
-    
+
     #[structopt(short="s", long="show")]
-    field: Option<String>
+    field: Option<String>,
+
+    #[structopt(short="j", long="json")]
+    json: bool,
+
+    #[structopt(long="arch")]
+    arch: Option<String>,
+
+    #[structopt(long="extract-resource")]
+    extract_resource: Option<String>,
+
+    #[structopt(short="o", long="output", parse(from_os_str), global=true)]
+    output: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Lists the resources embedded in a PE or NE file
+    Resources,
+    /// Removes the Authenticode certificate table from a PE file, writing
+    /// to --output if given and otherwise in place
+    SignStrip,
+    /// Lays a PE file out as it would be mapped in memory, optionally
+    /// relocated to --base, or a DOS .COM file as it would be loaded at
+    /// CS:0000, writing to --output if given
+    Map {
+        #[structopt(long, parse(try_from_str = utils::parse_address))]
+        base: Option<u64>
+    },
+    /// Rebuilds a PE file from a dump of its mapped image, writing to
+    /// --output if given
+    Unmap,
+    /// Writes the DOS stub of a PE file to --output if given, or with
+    /// --replace or --minimal rewrites the file with a new stub, writing to
+    /// --output if given and otherwise in place
+    Stub {
+        #[structopt(long, parse(from_os_str))]
+        replace: Option<PathBuf>,
+        #[structopt(long, conflicts_with = "replace")]
+        minimal: bool
+    },
+    /// Wraps a DOS .COM file in an MZ executable, writing to --output if
+    /// given and otherwise in place
+    Wrap,
+    /// Translates a relative virtual address to a file offset
+    Rva2off {
+        #[structopt(parse(try_from_str = utils::parse_address))]
+        address: u64
+    },
+    /// Translates a virtual address to a file offset
+    Va2off {
+        #[structopt(parse(try_from_str = utils::parse_address))]
+        address: u64
+    },
+    /// Translates a file offset to a relative virtual address
+    Off2rva {
+        #[structopt(parse(try_from_str = utils::parse_address))]
+        address: u64
+    },
+    /// Translates a file offset to a virtual address
+    Off2va {
+        #[structopt(parse(try_from_str = utils::parse_address))]
+        address: u64
+    }
 }
 
 fn main() {
     let args = Cli::from_args();
 
-    let mut file: File = File::open(args.path).unwrap();
+    let mut file: File = File::open(&args.path).unwrap();
     let mut file_contents: Vec<u8> = Vec::new();
     file.read_to_end(&mut file_contents).unwrap();
 
-    if args.format.is_none() {
-        let file_format: formats::Format = utils::infer_format(&mut file_contents);
-    } else {
-        let file_format: formats::Format = formats::Format::from_string(
-            args.format.unwrap());
+    let file_format: formats::Format = match args.format {
+        Some(format) => formats::Format::from_string(format),
+        None => utils::infer_format_from_name(&args.path, &file_contents)
+    };
+
+    let parsed_file: formats::File =
+        match utils::get_file_as(&file_contents, file_format) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("butyl: failed to parse file: {:?}", e);
+                process::exit(1);
+            }
+        };
+
+    /* picks a slice out of a fat file, which otherwise shows its first */
+    let parsed_file: formats::File = match (&args.arch, parsed_file) {
+        (Some(name), formats::File::FAT(fat)) => {
+            let macho = match fat.get_arch(name) {
+                Some(t) => fat.get_macho(t),
+                None => Err(errors::ButylError::NotFoundError)
+            };
+
+            match macho {
+                Ok(t) => formats::File::MachO(t),
+                Err(e) => {
+                    eprintln!("butyl: unable to select {}: {:?}", name, e);
+                    process::exit(1);
+                }
+            }
+        },
+        (Some(_), _) => {
+            eprintln!("butyl: --arch requires a fat Mach-O file");
+            process::exit(1);
+        },
+        (None, t) => t
+    };
+
+    if args.interactive {
+        eprintln!("butyl: interactive mode is not supported");
+        process::exit(1);
+    }
+
+    if let Some(Command::Resources) = args.command {
+        if let Err(e) = display::show_resources(&parsed_file) {
+            eprintln!("butyl: unable to list resources: {:?}", e);
+            process::exit(1);
+        }
+    }
+
+    if let Some(Command::SignStrip) = args.command {
+        let stripped: Vec<u8> = match &parsed_file {
+            formats::File::PE(pe) => match pe.strip_signatures() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("butyl: unable to strip signatures: {:?}", e);
+                    process::exit(1);
+                }
+            },
+            _ => {
+                eprintln!("butyl: sign-strip requires a PE file");
+                process::exit(1);
+            }
+        };
+
+        std::fs::write(args.output.as_ref().unwrap_or(&args.path), stripped)
+            .unwrap();
+        return;
+    }
+
+    if let Some(Command::Wrap) = args.command {
+        let wrapped: Vec<u8> = match &parsed_file {
+            formats::File::COM(com) => match com.to_mz() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("butyl: unable to wrap file: {:?}", e);
+                    process::exit(1);
+                }
+            },
+            _ => {
+                eprintln!("butyl: wrap requires a .COM file");
+                process::exit(1);
+            }
+        };
+
+        std::fs::write(args.output.as_ref().unwrap_or(&args.path), wrapped)
+            .unwrap();
+        return;
+    }
+
+    if let Some(Command::Stub { replace, minimal }) = &args.command {
+        let pe = match &parsed_file {
+            formats::File::PE(pe) => pe,
+            _ => {
+                eprintln!("butyl: stub requires a PE file");
+                process::exit(1);
+            }
+        };
+
+        let rebuilt = match (replace, minimal) {
+            (Some(path), _) => pe.replace_dos_stub(&std::fs::read(path)
+                .unwrap()),
+            (None, true) => pe.minimise_dos_stub(),
+            (None, false) => {
+                match &args.output {
+                    Some(path) => File::create(path).unwrap()
+                        .write_all(pe.get_dos_stub()).unwrap(),
+                    None => std::io::stdout().write_all(pe.get_dos_stub())
+                        .unwrap()
+                }
+
+                return;
+            }
+        };
+
+        match rebuilt {
+            Ok(t) => std::fs::write(args.output.as_ref()
+                .unwrap_or(&args.path), t).unwrap(),
+            Err(e) => {
+                eprintln!("butyl: unable to replace stub: {:?}", e);
+                process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let layout: Option<Result<Vec<u8>, errors::ButylError>> =
+        match (&args.command, &parsed_file) {
+            (Some(Command::Map { base }), formats::File::PE(pe)) =>
+                Some(pe.map_image(*base)),
+            (Some(Command::Unmap), formats::File::PE(_)) =>
+                Some(pe::image::rebuild_from_memory(&file_contents)),
+            (Some(Command::Map { base: None }), formats::File::COM(com)) =>
+                Some(Ok(com.get_image())),
+            (Some(Command::Map { .. }), formats::File::COM(_)) => {
+                eprintln!("butyl: a .COM file cannot be relocated");
+                process::exit(1);
+            },
+            (Some(Command::Map { .. }), _) | (Some(Command::Unmap), _) => {
+                eprintln!("butyl: map and unmap require a PE file");
+                process::exit(1);
+            },
+            _ => None
+        };
+
+    if let Some(result) = layout {
+        let contents: Vec<u8> = match result {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("butyl: unable to rebuild image: {:?}", e);
+                process::exit(1);
+            }
+        };
+
+        match args.output {
+            Some(path) => File::create(path).unwrap()
+                .write_all(&contents).unwrap(),
+            None => std::io::stdout().write_all(&contents).unwrap()
+        }
+
+        return;
+    }
+
+    let translation: Option<(AddressKind, AddressKind, u64)> =
+        match args.command {
+            Some(Command::Rva2off { address }) =>
+                Some((AddressKind::Rva, AddressKind::Offset, address)),
+            Some(Command::Va2off { address }) =>
+                Some((AddressKind::Va, AddressKind::Offset, address)),
+            Some(Command::Off2rva { address }) =>
+                Some((AddressKind::Offset, AddressKind::Rva, address)),
+            Some(Command::Off2va { address }) =>
+                Some((AddressKind::Offset, AddressKind::Va, address)),
+            _ => None
+        };
+
+    if let Some((from, to, address)) = translation {
+        let translated = match &parsed_file {
+            formats::File::PE(pe) =>
+                pe.get_address_space().translate(address, from, to),
+            _ => {
+                eprintln!("butyl: address translation requires a PE file");
+                process::exit(1);
+            }
+        };
+
+        match translated {
+            Ok(t) => println!("{:#x}", t),
+            Err(e) => {
+                eprintln!("butyl: unable to translate {:#x}: {:?}", address, e);
+                process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    if let Some(spec) = args.extract_resource {
+        let contents: Vec<u8> =
+            match display::get_resource_data(&parsed_file, &spec) {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("butyl: unable to extract {}: {:?}", spec, e);
+                    process::exit(1);
+                }
+            };
+
+        match args.output {
+            Some(path) => File::create(path).unwrap()
+                .write_all(&contents).unwrap(),
+            None => std::io::stdout().write_all(&contents).unwrap()
+        }
     }
 
-    if args.field.is_some() {
-        unimplemented!();
+    if let Some(field) = args.field {
+        if let Err(e) = display::show_field(&parsed_file, &field,
+            args.json) {
+            eprintln!("butyl: unable to show {}: {:?}", field, e);
+            process::exit(1);
+        }
     }
 }
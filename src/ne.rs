@@ -0,0 +1,520 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::DosHeader;
+use crate::utils::*;
+
+pub mod names;
+pub mod resources;
+pub mod relocs;
+
+pub const NE_SIGNATURE: u16 = 0x454e; /* "NE" */
+pub const NE_HEADER_LEN: usize = 64;
+pub const NE_SEGMENT_LEN: usize = 8;
+
+pub const NE_FLAG_SINGLEDATA: u16 = 0x0001;
+pub const NE_FLAG_MULTIPLEDATA: u16 = 0x0002;
+pub const NE_FLAG_GLOBALINIT: u16 = 0x0004;
+pub const NE_FLAG_PROTMODE: u16 = 0x0008;
+pub const NE_FLAG_I86: u16 = 0x0010;
+pub const NE_FLAG_I286: u16 = 0x0020;
+pub const NE_FLAG_I386: u16 = 0x0040;
+pub const NE_FLAG_I87: u16 = 0x0080;
+pub const NE_FLAG_APPTYPE: u16 = 0x0700;
+pub const NE_FLAG_SELFLOAD: u16 = 0x0800;
+pub const NE_FLAG_LINKERROR: u16 = 0x2000;
+pub const NE_FLAG_LIBMODULE: u16 = 0x8000;
+
+const NE_FLAG_NAMES: &[(u16, &str)] = &[
+    (NE_FLAG_SINGLEDATA, "SINGLEDATA"), (NE_FLAG_MULTIPLEDATA, "MULTIPLEDATA"),
+    (NE_FLAG_GLOBALINIT, "GLOBALINIT"), (NE_FLAG_PROTMODE, "PROTMODE"),
+    (NE_FLAG_I86, "I86"), (NE_FLAG_I286, "I286"), (NE_FLAG_I386, "I386"),
+    (NE_FLAG_I87, "I87"), (NE_FLAG_SELFLOAD, "SELFLOAD"),
+    (NE_FLAG_LINKERROR, "LINKERROR"), (NE_FLAG_LIBMODULE, "LIBMODULE")
+];
+
+pub const NE_SEGMENT_DATA: u16 = 0x0001;
+pub const NE_SEGMENT_MOVEABLE: u16 = 0x0010;
+pub const NE_SEGMENT_SHAREABLE: u16 = 0x0020;
+pub const NE_SEGMENT_PRELOAD: u16 = 0x0040;
+pub const NE_SEGMENT_READONLY: u16 = 0x0080; /* execute-only for code */
+pub const NE_SEGMENT_RELOCINFO: u16 = 0x0100;
+pub const NE_SEGMENT_DPL: u16 = 0x0c00;
+pub const NE_SEGMENT_DISCARDABLE: u16 = 0x1000;
+
+/* a length or allocation of zero stands for the full 64K */
+pub const NE_SEGMENT_MAX_LEN: u32 = 0x1_0000;
+
+/* an alignment shift of zero means the default 512-byte sectors */
+pub const NE_DEFAULT_ALIGN: u16 = 9;
+pub const NE_MAX_ALIGN: u16 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetOs {
+    Unknown,
+    OS2,
+    Windows,
+    Dos4,
+    Windows386,
+    Boss,
+    Other(u8)
+}
+
+impl TargetOs {
+    pub fn to_value(target: TargetOs) -> u8 {
+        match target {
+            TargetOs::Unknown => 0,
+            TargetOs::OS2 => 1,
+            TargetOs::Windows => 2,
+            TargetOs::Dos4 => 3,
+            TargetOs::Windows386 => 4,
+            TargetOs::Boss => 5,
+            TargetOs::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> TargetOs {
+        match value {
+            0 => TargetOs::Unknown,
+            1 => TargetOs::OS2,
+            2 => TargetOs::Windows,
+            3 => TargetOs::Dos4,
+            4 => TargetOs::Windows386,
+            5 => TargetOs::Boss,
+            t => TargetOs::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            TargetOs::Unknown => String::from("Unknown"),
+            TargetOs::OS2 => String::from("OS/2"),
+            TargetOs::Windows => String::from("Windows"),
+            TargetOs::Dos4 => String::from("European MS-DOS 4.x"),
+            TargetOs::Windows386 => String::from("Windows 386"),
+            TargetOs::Boss => String::from("BOSS"),
+            TargetOs::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NeHeader {
+    ne_magic: u16,          /* "NE" */
+    ne_ver: u8,             /* linker version */
+    ne_rev: u8,             /* linker revision */
+    ne_enttab: u16,         /* offset of the entry table */
+    ne_cbenttab: u16,       /* size of the entry table */
+    ne_crc: u32,            /* checksum of the whole file */
+    ne_flags: u16,          /* NE_FLAG_* */
+    ne_autodata: u16,       /* automatic data segment number */
+    ne_heap: u16,           /* initial heap size */
+    ne_stack: u16,          /* initial stack size */
+    ne_csip: u32,           /* initial CS:IP, CS being a segment number */
+    ne_sssp: u32,           /* initial SS:SP, SS being a segment number */
+    ne_cseg: u16,           /* number of segments */
+    ne_cmod: u16,           /* number of module references */
+    ne_cbnrestab: u16,      /* size of the non-resident name table */
+    ne_segtab: u16,         /* offset of the segment table */
+    ne_rsrctab: u16,        /* offset of the resource table */
+    ne_restab: u16,         /* offset of the resident name table */
+    ne_modtab: u16,         /* offset of the module reference table */
+    ne_imptab: u16,         /* offset of the imported names table */
+    ne_nrestab: u32,        /* file offset of the non-resident name table */
+    ne_cmovent: u16,        /* number of movable entry points */
+    ne_align: u16,          /* segment alignment shift count */
+    ne_cres: u16,           /* number of resource segments */
+    ne_exetyp: u8,          /* target operating system */
+    ne_flagsothers: u8,     /* other flags, e.g. the gangload area */
+    ne_pretthunks: u16,     /* offset of the return thunks */
+    ne_psegrefbytes: u16,   /* offset of the segment reference bytes */
+    ne_swaparea: u16,       /* minimum code swap area size */
+    ne_expver: u16          /* expected Windows version */
+}
+
+impl NeHeader {
+    pub fn get_magic(&self) -> u16 {
+        self.ne_magic
+    }
+
+    pub fn get_linker_version(&self) -> u8 {
+        self.ne_ver
+    }
+
+    pub fn get_linker_revision(&self) -> u8 {
+        self.ne_rev
+    }
+
+    pub fn get_enttab(&self) -> u16 {
+        self.ne_enttab
+    }
+
+    pub fn get_cbenttab(&self) -> u16 {
+        self.ne_cbenttab
+    }
+
+    pub fn get_crc(&self) -> u32 {
+        self.ne_crc
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.ne_flags
+    }
+
+    pub fn get_flags_as_strings(&self) -> Vec<String> {
+        let mut names: Vec<String> = NE_FLAG_NAMES.iter()
+            .filter(|(flag, _)| self.ne_flags & flag != 0)
+            .map(|(_, name)| String::from(*name))
+            .collect();
+
+        /* the application type is a small field rather than a flag */
+        match (self.ne_flags & NE_FLAG_APPTYPE) >> 8 {
+            0 => (),
+            1 => names.push(String::from("NOTWINCOMPAT")),
+            2 => names.push(String::from("WINCOMPAT")),
+            3 => names.push(String::from("WINAPI")),
+            t => names.push(format!("APPTYPE({})", t))
+        }
+
+        names
+    }
+
+    pub fn is_library(&self) -> bool {
+        self.ne_flags & NE_FLAG_LIBMODULE != 0
+    }
+
+    pub fn get_autodata(&self) -> u16 {
+        self.ne_autodata
+    }
+
+    pub fn get_heap(&self) -> u16 {
+        self.ne_heap
+    }
+
+    pub fn get_stack(&self) -> u16 {
+        self.ne_stack
+    }
+
+    pub fn get_csip(&self) -> u32 {
+        self.ne_csip
+    }
+
+    /* (segment number, offset) */
+    pub fn get_entry_point(&self) -> (u16, u16) {
+        ((self.ne_csip >> 16) as u16, self.ne_csip as u16)
+    }
+
+    pub fn get_sssp(&self) -> u32 {
+        self.ne_sssp
+    }
+
+    pub fn get_cseg(&self) -> u16 {
+        self.ne_cseg
+    }
+
+    pub fn get_cmod(&self) -> u16 {
+        self.ne_cmod
+    }
+
+    pub fn get_cbnrestab(&self) -> u16 {
+        self.ne_cbnrestab
+    }
+
+    pub fn get_segtab(&self) -> u16 {
+        self.ne_segtab
+    }
+
+    pub fn get_rsrctab(&self) -> u16 {
+        self.ne_rsrctab
+    }
+
+    pub fn get_restab(&self) -> u16 {
+        self.ne_restab
+    }
+
+    pub fn get_modtab(&self) -> u16 {
+        self.ne_modtab
+    }
+
+    pub fn get_imptab(&self) -> u16 {
+        self.ne_imptab
+    }
+
+    pub fn get_nrestab(&self) -> u32 {
+        self.ne_nrestab
+    }
+
+    pub fn get_cmovent(&self) -> u16 {
+        self.ne_cmovent
+    }
+
+    pub fn get_align(&self) -> u16 {
+        self.ne_align
+    }
+
+    pub fn get_align_shift(&self) -> u16 {
+        match self.ne_align {
+            0 => NE_DEFAULT_ALIGN,
+            t => t
+        }
+    }
+
+    pub fn get_cres(&self) -> u16 {
+        self.ne_cres
+    }
+
+    pub fn get_exetyp(&self) -> u8 {
+        self.ne_exetyp
+    }
+
+    pub fn get_target_os(&self) -> TargetOs {
+        TargetOs::from_value(self.ne_exetyp)
+    }
+
+    pub fn get_flagsothers(&self) -> u8 {
+        self.ne_flagsothers
+    }
+
+    pub fn get_pretthunks(&self) -> u16 {
+        self.ne_pretthunks
+    }
+
+    pub fn get_psegrefbytes(&self) -> u16 {
+        self.ne_psegrefbytes
+    }
+
+    pub fn get_swaparea(&self) -> u16 {
+        self.ne_swaparea
+    }
+
+    pub fn get_expver(&self) -> u16 {
+        self.ne_expver
+    }
+
+    /* stored as minor in the low byte, major in the high byte */
+    pub fn get_expver_as_string(&self) -> String {
+        format!("{}.{}", self.ne_expver >> 8, self.ne_expver & 0xff)
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<NeHeader, ButylError> {
+        let mut header: NeHeader = NeHeader::default();
+
+        header.ne_magic = read_le_u16(bytes, 0)?;
+
+        if header.ne_magic != NE_SIGNATURE {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        header.ne_ver = read_u8(bytes, 2)?;
+        header.ne_rev = read_u8(bytes, 3)?;
+        header.ne_enttab = read_le_u16(bytes, 4)?;
+        header.ne_cbenttab = read_le_u16(bytes, 6)?;
+        header.ne_crc = read_le_u32(bytes, 8)?;
+        header.ne_flags = read_le_u16(bytes, 12)?;
+        header.ne_autodata = read_le_u16(bytes, 14)?;
+        header.ne_heap = read_le_u16(bytes, 16)?;
+        header.ne_stack = read_le_u16(bytes, 18)?;
+        header.ne_csip = read_le_u32(bytes, 20)?;
+        header.ne_sssp = read_le_u32(bytes, 24)?;
+        header.ne_cseg = read_le_u16(bytes, 28)?;
+        header.ne_cmod = read_le_u16(bytes, 30)?;
+        header.ne_cbnrestab = read_le_u16(bytes, 32)?;
+        header.ne_segtab = read_le_u16(bytes, 34)?;
+        header.ne_rsrctab = read_le_u16(bytes, 36)?;
+        header.ne_restab = read_le_u16(bytes, 38)?;
+        header.ne_modtab = read_le_u16(bytes, 40)?;
+        header.ne_imptab = read_le_u16(bytes, 42)?;
+        header.ne_nrestab = read_le_u32(bytes, 44)?;
+        header.ne_cmovent = read_le_u16(bytes, 48)?;
+        header.ne_align = read_le_u16(bytes, 50)?;
+        header.ne_cres = read_le_u16(bytes, 52)?;
+        header.ne_exetyp = read_u8(bytes, 54)?;
+        header.ne_flagsothers = read_u8(bytes, 55)?;
+        header.ne_pretthunks = read_le_u16(bytes, 56)?;
+        header.ne_psegrefbytes = read_le_u16(bytes, 58)?;
+        header.ne_swaparea = read_le_u16(bytes, 60)?;
+        header.ne_expver = read_le_u16(bytes, 62)?;
+
+        if header.ne_align > NE_MAX_ALIGN {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        Ok(header)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Segment {
+    sector: u16,            /* file offset in alignment units, 0 if none */
+    length: u16,            /* length in the file, 0 meaning 64K */
+    flags: u16,             /* NE_SEGMENT_* */
+    min_alloc: u16,         /* size in memory, 0 meaning 64K */
+    align: u16              /* shift count from the NE header */
+}
+
+impl Segment {
+    pub fn get_sector(&self) -> u16 {
+        self.sector
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.length
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn get_min_alloc(&self) -> u16 {
+        self.min_alloc
+    }
+
+    pub fn get_offset(&self) -> usize {
+        (self.sector as usize) << self.align
+    }
+
+    /* segments with no file data at all are allocated in memory only */
+    pub fn get_size(&self) -> u32 {
+        match (self.sector, self.length) {
+            (0, _) => 0,
+            (_, 0) => NE_SEGMENT_MAX_LEN,
+            (_, t) => t as u32
+        }
+    }
+
+    pub fn get_alloc_size(&self) -> u32 {
+        match self.min_alloc {
+            0 => NE_SEGMENT_MAX_LEN,
+            t => t as u32
+        }
+    }
+
+    pub fn is_data(&self) -> bool {
+        self.flags & NE_SEGMENT_DATA != 0
+    }
+
+    pub fn has_relocations(&self) -> bool {
+        self.flags & NE_SEGMENT_RELOCINFO != 0
+    }
+
+    /* "CODE MOVEABLE PRELOAD", in the style of exehdr */
+    pub fn get_flags_as_string(&self) -> String {
+        let mut names: Vec<&str> = vec![match self.is_data() {
+            true => "DATA",
+            false => "CODE"
+        }];
+
+        if self.flags & NE_SEGMENT_MOVEABLE != 0 {
+            names.push("MOVEABLE");
+        }
+
+        if self.flags & NE_SEGMENT_SHAREABLE != 0 {
+            names.push("SHAREABLE");
+        }
+
+        if self.flags & NE_SEGMENT_PRELOAD != 0 {
+            names.push("PRELOAD");
+        }
+
+        if self.flags & NE_SEGMENT_READONLY != 0 {
+            names.push(match self.is_data() {
+                true => "READONLY",
+                false => "EXECUTEONLY"
+            });
+        }
+
+        if self.flags & NE_SEGMENT_RELOCINFO != 0 {
+            names.push("RELOCINFO");
+        }
+
+        if self.flags & NE_SEGMENT_DISCARDABLE != 0 {
+            names.push("DISCARDABLE");
+        }
+
+        names.join(" ")
+    }
+
+    pub fn from_le_bytes(bytes: &[u8], align: u16) ->
+        Result<Segment, ButylError> {
+        Ok(Segment {
+            sector: read_le_u16(bytes, 0)?,
+            length: read_le_u16(bytes, 2)?,
+            flags: read_le_u16(bytes, 4)?,
+            min_alloc: read_le_u16(bytes, 6)?,
+            align
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct NeFile<'a> {
+    dos_header: DosHeader,
+    header: NeHeader,
+    segments: Vec<Segment>,
+    data: &'a[u8]
+}
+
+impl<'a> NeFile<'a> {
+    pub fn get_dos_header(&self) -> &DosHeader {
+        &self.dos_header
+    }
+
+    pub fn get_header(&self) -> &NeHeader {
+        &self.header
+    }
+
+    /* the table offsets in the header are relative to the header itself */
+    pub fn get_header_offset(&self) -> usize {
+        self.dos_header.get_lfanew() as usize
+    }
+
+    pub fn get_segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /* segment numbers start at 1 */
+    pub fn get_segment(&self, number: u16) -> Result<&Segment, ButylError> {
+        match (number as usize).checked_sub(1)
+            .and_then(|t| self.segments.get(t)) {
+            Some(t) => Ok(t),
+            None => Err(ButylError::NotFoundError)
+        }
+    }
+
+    pub fn get_segment_data(&self, segment: &Segment) ->
+        Result<&'a[u8], ButylError> {
+        read_bytes(self.data, segment.get_offset(),
+            segment.get_size() as usize)
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn from_le_bytes(data: &[u8]) -> Result<NeFile<'_>, ButylError> {
+        let dos_header: DosHeader = DosHeader::from_le_bytes(data)?;
+        let ne_offset: usize = dos_header.get_lfanew() as usize;
+        let header: NeHeader = NeHeader::from_le_bytes(
+            read_bytes(data, ne_offset, NE_HEADER_LEN)?)?;
+
+        let mut segments: Vec<Segment> = Vec::new();
+        let segment_table_offset: usize =
+            ne_offset + header.get_segtab() as usize;
+
+        for i in 0..header.get_cseg() as usize {
+            segments.push(Segment::from_le_bytes(read_bytes(data,
+                segment_table_offset + i * NE_SEGMENT_LEN, NE_SEGMENT_LEN)?,
+                header.get_align_shift())?);
+        }
+
+        Ok(NeFile {
+            dos_header,
+            header,
+            segments,
+            data
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::ne::NeFile;
+use crate::utils::*;
+
+#[derive(Debug, Default, Clone)]
+pub struct Name {
+    name: String,
+    ordinal: u16        /* entry table ordinal, 0 for the module entry */
+}
+
+impl Name {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_ordinal(&self) -> u16 {
+        self.ordinal
+    }
+}
+
+/* both name tables, which LE and LX files share, are runs of
+ * length-prefixed strings each followed by an ordinal, ending with a zero
+ * length */
+pub fn get_names_from_bytes(bytes: &[u8], offset: usize) ->
+    Result<Vec<Name>, ButylError> {
+    let mut names: Vec<Name> = Vec::new();
+    let mut offset: usize = offset;
+
+    loop {
+        let len: usize = read_u8(bytes, offset)? as usize;
+
+        if len == 0 {
+            break;
+        }
+
+        names.push(Name {
+            name: read_pascal_string(bytes, offset)?,
+            ordinal: read_le_u16(bytes, offset + 1 + len)?
+        });
+
+        offset += 1 + len + 2;
+    }
+
+    Ok(names)
+}
+
+impl<'a> NeFile<'a> {
+    /* the first entry is the module name itself */
+    pub fn get_resident_names(&self) -> Result<Vec<Name>, ButylError> {
+        get_names_from_bytes(self.data, self.get_header_offset() +
+            self.header.get_restab() as usize)
+    }
+
+    /* the first entry is the module description */
+    pub fn get_non_resident_names(&self) -> Result<Vec<Name>, ButylError> {
+        match self.header.get_cbnrestab() {
+            0 => Ok(Vec::new()),
+            t => get_names_from_bytes(read_bytes(self.data,
+                self.header.get_nrestab() as usize, t as usize)?, 0)
+        }
+    }
+
+    pub fn get_module_name(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_resident_names()?.into_iter().next().map(|t| t.name))
+    }
+
+    pub fn get_description(&self) -> Result<Option<String>, ButylError> {
+        Ok(self.get_non_resident_names()?.into_iter().next().map(|t| t.name))
+    }
+
+    /* offsets are relative to the start of the imported names table */
+    pub fn get_imported_name(&self, offset: u16) ->
+        Result<String, ButylError> {
+        read_pascal_string(self.data, self.get_header_offset() +
+            self.header.get_imptab() as usize + offset as usize)
+    }
+
+    /* the modules this one imports from, indexed from 1 by relocations */
+    pub fn get_module_references(&self) -> Result<Vec<String>, ButylError> {
+        let table: usize = self.get_header_offset() +
+            self.header.get_modtab() as usize;
+
+        (0..self.header.get_cmod() as usize)
+            .map(|i| self.get_imported_name(read_le_u16(self.data,
+                table + i * 2)?))
+            .collect()
+    }
+}
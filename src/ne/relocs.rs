@@ -0,0 +1,155 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::ne::{NeFile, Segment};
+use crate::utils::*;
+
+pub const NE_RELOCATION_LEN: usize = 8;
+
+pub const NE_RELOC_TARGET_MASK: u8 = 0x03;
+pub const NE_RELOC_ADDITIVE: u8 = 0x04;
+
+/* an internal reference to this segment number goes through the entry
+ * table instead */
+pub const NE_RELOC_MOVEABLE_SEGMENT: u8 = 0xff;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceType {
+    LoByte,
+    Selector,
+    FarPointer,
+    Offset,
+    FarPointer48,
+    Offset32,
+    Other(u8)
+}
+
+impl SourceType {
+    pub fn to_value(source: SourceType) -> u8 {
+        match source {
+            SourceType::LoByte => 0,
+            SourceType::Selector => 2,
+            SourceType::FarPointer => 3,
+            SourceType::Offset => 5,
+            SourceType::FarPointer48 => 11,
+            SourceType::Offset32 => 13,
+            SourceType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> SourceType {
+        match value {
+            0 => SourceType::LoByte,
+            2 => SourceType::Selector,
+            3 => SourceType::FarPointer,
+            5 => SourceType::Offset,
+            11 => SourceType::FarPointer48,
+            13 => SourceType::Offset32,
+            t => SourceType::Other(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            SourceType::LoByte => String::from("LOBYTE"),
+            SourceType::Selector => String::from("SELECTOR"),
+            SourceType::FarPointer => String::from("FARPTR"),
+            SourceType::Offset => String::from("OFFSET"),
+            SourceType::FarPointer48 => String::from("FARPTR48"),
+            SourceType::Offset32 => String::from("OFFSET32"),
+            SourceType::Other(t) => format!("{:#x}", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelocationTarget {
+    Internal { segment: u8, offset: u16 },
+    Moveable { ordinal: u16 },
+    ImportOrdinal { module: u16, ordinal: u16 },
+    ImportName { module: u16, offset: u16 },
+    OsFixup { kind: u16 }
+}
+
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    source: u8,         /* what is patched, see SourceType */
+    flags: u8,          /* target kind and NE_RELOC_ADDITIVE */
+    offset: u16,        /* offset in the segment of the first location */
+    target: RelocationTarget
+}
+
+impl Relocation {
+    pub fn get_source(&self) -> u8 {
+        self.source
+    }
+
+    pub fn get_source_as_enum(&self) -> SourceType {
+        SourceType::from_value(self.source)
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    /* otherwise the locations form a chain through the patched words */
+    pub fn is_additive(&self) -> bool {
+        self.flags & NE_RELOC_ADDITIVE != 0
+    }
+
+    pub fn get_offset(&self) -> u16 {
+        self.offset
+    }
+
+    pub fn get_target(&self) -> &RelocationTarget {
+        &self.target
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Relocation, ButylError> {
+        let flags: u8 = read_u8(bytes, 1)?;
+        let first: u16 = read_le_u16(bytes, 4)?;
+        let second: u16 = read_le_u16(bytes, 6)?;
+
+        let target: RelocationTarget = match flags & NE_RELOC_TARGET_MASK {
+            0 => match read_u8(bytes, 4)? {
+                NE_RELOC_MOVEABLE_SEGMENT =>
+                    RelocationTarget::Moveable { ordinal: second },
+                t => RelocationTarget::Internal { segment: t, offset: second }
+            },
+            1 => RelocationTarget::ImportOrdinal {
+                module: first,
+                ordinal: second
+            },
+            2 => RelocationTarget::ImportName {
+                module: first,
+                offset: second
+            },
+            _ => RelocationTarget::OsFixup { kind: first }
+        };
+
+        Ok(Relocation {
+            source: read_u8(bytes, 0)?,
+            flags,
+            offset: read_le_u16(bytes, 2)?,
+            target
+        })
+    }
+}
+
+impl<'a> NeFile<'a> {
+    /* the relocation records follow the segment data, led by their count */
+    pub fn get_relocations(&self, segment: &Segment) ->
+        Result<Vec<Relocation>, ButylError> {
+        if !segment.has_relocations() || segment.get_sector() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let offset: usize = segment.get_offset() + segment.get_size() as usize;
+        let count: u16 = read_le_u16(self.data, offset)?;
+
+        (0..count as usize)
+            .map(|i| Relocation::from_le_bytes(read_bytes(self.data,
+                offset + 2 + i * NE_RELOCATION_LEN, NE_RELOCATION_LEN)?))
+            .collect()
+    }
+}
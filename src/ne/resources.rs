@@ -0,0 +1,120 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::ne::{self, NeFile};
+use crate::pe::resources::ResourceId;
+use crate::utils::*;
+
+pub const NE_TYPEINFO_LEN: usize = 8;
+pub const NE_NAMEINFO_LEN: usize = 12;
+pub const NE_RESOURCE_ID_IS_INTEGER: u16 = 0x8000;
+
+pub const NE_RESOURCE_MOVEABLE: u16 = 0x0010;
+pub const NE_RESOURCE_PURE: u16 = 0x0020;
+pub const NE_RESOURCE_PRELOAD: u16 = 0x0040;
+
+#[derive(Debug, Clone)]
+pub struct Resource<'a> {
+    resource_type: ResourceId,
+    name: ResourceId,
+    offset: usize,      /* file offset, already shifted */
+    flags: u16,         /* NE_RESOURCE_* */
+    data: &'a[u8]
+}
+
+impl<'a> Resource<'a> {
+    pub fn get_type(&self) -> &ResourceId {
+        &self.resource_type
+    }
+
+    pub fn get_name(&self) -> &ResourceId {
+        &self.name
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+}
+
+impl<'a> NeFile<'a> {
+    /* an ID with the top bit set is an integer, otherwise it is the offset of
+     * a length-prefixed name from the start of the resource table */
+    fn get_resource_id(&self, table: usize, id: u16) ->
+        Result<ResourceId, ButylError> {
+        match id & NE_RESOURCE_ID_IS_INTEGER {
+            0 => Ok(ResourceId::Name(read_pascal_string(self.data,
+                table + id as usize)?)),
+            _ => Ok(ResourceId::Id(id & !NE_RESOURCE_ID_IS_INTEGER))
+        }
+    }
+
+    /* the table starts with an alignment shift, followed by a TYPEINFO for
+     * each type with the NAMEINFO of each of its resources, ending with a
+     * zero type ID */
+    pub fn get_resources(&self) -> Result<Vec<Resource<'a>>, ButylError> {
+        let mut resources: Vec<Resource<'a>> = Vec::new();
+
+        /* the table is absent when it would start where the resident names
+         * do */
+        if self.header.get_rsrctab() == self.header.get_restab() {
+            return Ok(resources);
+        }
+
+        let table: usize = self.get_header_offset() +
+            self.header.get_rsrctab() as usize;
+        let shift: u16 = read_le_u16(self.data, table)?;
+
+        if shift > ne::NE_MAX_ALIGN {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let mut offset: usize = table + 2;
+
+        loop {
+            let type_id: u16 = read_le_u16(self.data, offset)?;
+
+            if type_id == 0 {
+                break;
+            }
+
+            let resource_type: ResourceId =
+                self.get_resource_id(table, type_id)?;
+            let count: u16 = read_le_u16(self.data, offset + 2)?;
+            offset += NE_TYPEINFO_LEN;
+
+            for _ in 0..count {
+                let info: &[u8] = read_bytes(self.data, offset,
+                    NE_NAMEINFO_LEN)?;
+                let start: usize = (read_le_u16(info, 0)? as usize) << shift;
+                let len: usize = (read_le_u16(info, 2)? as usize) << shift;
+
+                resources.push(Resource {
+                    resource_type: resource_type.clone(),
+                    name: self.get_resource_id(table,
+                        read_le_u16(info, 6)?)?,
+                    offset: start,
+                    flags: read_le_u16(info, 4)?,
+                    data: read_bytes(self.data, start, len)?
+                });
+
+                offset += NE_NAMEINFO_LEN;
+            }
+        }
+
+        Ok(resources)
+    }
+
+    pub fn find_resource(&self, resource_type: &ResourceId,
+        name: &ResourceId) -> Result<Option<Resource<'a>>, ButylError> {
+        Ok(self.get_resources()?.into_iter()
+            .find(|t| &t.resource_type == resource_type && &t.name == name))
+    }
+}
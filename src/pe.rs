@@ -0,0 +1,792 @@
+#![allow(dead_code)]
+
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::DosHeader;
+use crate::coff::CoffHeader;
+use crate::pe::address::AddressSpace;
+use crate::utils::*;
+
+pub mod imports;
+pub mod relocs;
+pub mod resources;
+pub mod version;
+pub mod manifest;
+pub mod authenticode;
+pub mod debug;
+pub mod tls;
+pub mod loadconfig;
+pub mod exceptions;
+pub mod clr;
+pub mod address;
+pub mod image;
+pub mod rich;
+pub mod stub;
+
+pub const PE_SIGNATURE: u32 = 0x0000_4550;                /* "PE\0\0" */
+pub const PE_SIGNATURE_LEN: usize = 4;
+pub const PE_COFF_HEADER_LEN: usize = 20;
+pub const PE_OPTIONAL_HEADER_MAGIC_PE32: u16 = 0x010b;
+pub const PE_OPTIONAL_HEADER_MAGIC_PE32_PLUS: u16 = 0x020b;
+pub const PE_DATA_DIRECTORY_LEN: usize = 8;
+pub const PE_SECTION_HEADER_LEN: usize = 40;
+pub const PE_SECTION_NAME_LEN: usize = 8;
+pub const PE_MAX_DATA_DIRECTORIES: usize = 16;
+pub const PE_OPTIONAL_HEADER_CHECK_SUM_OFFSET: usize = 64;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DataDirectory {
+    virtual_address: u32,   /* RVA of the table */
+    size: u32               /* size of the table in bytes */
+}
+
+impl DataDirectory {
+    pub fn get_virtual_address(&self) -> u32 {
+        self.virtual_address
+    }
+
+    pub fn set_virtual_address(&mut self, virtual_address: u32) {
+        self.virtual_address = virtual_address;
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+
+    pub fn is_present(&self) -> bool {
+        self.virtual_address != 0 && self.size != 0
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<DataDirectory, ButylError> {
+        Ok(DataDirectory {
+            virtual_address: read_le_u32(bytes, 0)?,
+            size: read_le_u32(bytes, 4)?
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataDirectoryType {
+    Export,
+    Import,
+    Resource,
+    Exception,
+    Security,
+    BaseReloc,
+    Debug,
+    Architecture,
+    GlobalPtr,
+    Tls,
+    LoadConfig,
+    BoundImport,
+    Iat,
+    DelayImport,
+    ComDescriptor,
+    Reserved
+}
+
+impl DataDirectoryType {
+    pub fn to_value(directory: DataDirectoryType) -> usize {
+        match directory {
+            DataDirectoryType::Export => 0,
+            DataDirectoryType::Import => 1,
+            DataDirectoryType::Resource => 2,
+            DataDirectoryType::Exception => 3,
+            DataDirectoryType::Security => 4,
+            DataDirectoryType::BaseReloc => 5,
+            DataDirectoryType::Debug => 6,
+            DataDirectoryType::Architecture => 7,
+            DataDirectoryType::GlobalPtr => 8,
+            DataDirectoryType::Tls => 9,
+            DataDirectoryType::LoadConfig => 10,
+            DataDirectoryType::BoundImport => 11,
+            DataDirectoryType::Iat => 12,
+            DataDirectoryType::DelayImport => 13,
+            DataDirectoryType::ComDescriptor => 14,
+            DataDirectoryType::Reserved => 15
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PeOptionalHeader {
+    magic: u16,                         /* PE32 or PE32+ */
+    major_linker_version: u8,           /* linker major version */
+    minor_linker_version: u8,           /* linker minor version */
+    size_of_code: u32,                  /* size of all code sections */
+    size_of_initialized_data: u32,      /* size of all data sections */
+    size_of_uninitialized_data: u32,    /* size of all BSS sections */
+    address_of_entry_point: u32,        /* RVA of entry point */
+    base_of_code: u32,                  /* RVA of start of code */
+    base_of_data: u32,                  /* RVA of start of data (PE32) */
+    image_base: u64,                    /* preferred load address */
+    section_alignment: u32,             /* in-memory section alignment */
+    file_alignment: u32,                /* on-disk section alignment */
+    major_os_version: u16,              /* required OS major version */
+    minor_os_version: u16,              /* required OS minor version */
+    major_image_version: u16,           /* image major version */
+    minor_image_version: u16,           /* image minor version */
+    major_subsystem_version: u16,       /* subsystem major version */
+    minor_subsystem_version: u16,       /* subsystem minor version */
+    win32_version_value: u32,           /* (reserved) */
+    size_of_image: u32,                 /* size of image in memory */
+    size_of_headers: u32,               /* size of all headers on disk */
+    check_sum: u32,                     /* image checksum */
+    subsystem: u16,                     /* required subsystem */
+    dll_characteristics: u16,           /* DLL flags */
+    size_of_stack_reserve: u64,         /* stack reservation size */
+    size_of_stack_commit: u64,          /* stack commit size */
+    size_of_heap_reserve: u64,          /* heap reservation size */
+    size_of_heap_commit: u64,           /* heap commit size */
+    loader_flags: u32,                  /* (reserved) */
+    number_of_rva_and_sizes: u32,       /* number of data directories */
+    data_directories: Vec<DataDirectory>
+}
+
+impl PeOptionalHeader {
+    pub fn get_magic(&self) -> u16 {
+        self.magic
+    }
+
+    pub fn set_magic(&mut self, magic: u16) {
+        self.magic = magic;
+    }
+
+    pub fn is_pe32_plus(&self) -> bool {
+        self.magic == PE_OPTIONAL_HEADER_MAGIC_PE32_PLUS
+    }
+
+    pub fn get_major_linker_version(&self) -> u8 {
+        self.major_linker_version
+    }
+
+    pub fn set_major_linker_version(&mut self, major_linker_version: u8) {
+        self.major_linker_version = major_linker_version;
+    }
+
+    pub fn get_minor_linker_version(&self) -> u8 {
+        self.minor_linker_version
+    }
+
+    pub fn set_minor_linker_version(&mut self, minor_linker_version: u8) {
+        self.minor_linker_version = minor_linker_version;
+    }
+
+    pub fn get_size_of_code(&self) -> u32 {
+        self.size_of_code
+    }
+
+    pub fn set_size_of_code(&mut self, size_of_code: u32) {
+        self.size_of_code = size_of_code;
+    }
+
+    pub fn get_size_of_initialized_data(&self) -> u32 {
+        self.size_of_initialized_data
+    }
+
+    pub fn set_size_of_initialized_data(&mut self,
+        size_of_initialized_data: u32) {
+        self.size_of_initialized_data = size_of_initialized_data;
+    }
+
+    pub fn get_size_of_uninitialized_data(&self) -> u32 {
+        self.size_of_uninitialized_data
+    }
+
+    pub fn set_size_of_uninitialized_data(&mut self,
+        size_of_uninitialized_data: u32) {
+        self.size_of_uninitialized_data = size_of_uninitialized_data;
+    }
+
+    pub fn get_address_of_entry_point(&self) -> u32 {
+        self.address_of_entry_point
+    }
+
+    pub fn set_address_of_entry_point(&mut self, address_of_entry_point: u32) {
+        self.address_of_entry_point = address_of_entry_point;
+    }
+
+    pub fn get_base_of_code(&self) -> u32 {
+        self.base_of_code
+    }
+
+    pub fn set_base_of_code(&mut self, base_of_code: u32) {
+        self.base_of_code = base_of_code;
+    }
+
+    pub fn get_base_of_data(&self) -> u32 {
+        self.base_of_data
+    }
+
+    pub fn set_base_of_data(&mut self, base_of_data: u32) {
+        self.base_of_data = base_of_data;
+    }
+
+    pub fn get_image_base(&self) -> u64 {
+        self.image_base
+    }
+
+    pub fn set_image_base(&mut self, image_base: u64) {
+        self.image_base = image_base;
+    }
+
+    pub fn get_section_alignment(&self) -> u32 {
+        self.section_alignment
+    }
+
+    pub fn set_section_alignment(&mut self, section_alignment: u32) {
+        self.section_alignment = section_alignment;
+    }
+
+    pub fn get_file_alignment(&self) -> u32 {
+        self.file_alignment
+    }
+
+    pub fn set_file_alignment(&mut self, file_alignment: u32) {
+        self.file_alignment = file_alignment;
+    }
+
+    pub fn get_major_os_version(&self) -> u16 {
+        self.major_os_version
+    }
+
+    pub fn set_major_os_version(&mut self, major_os_version: u16) {
+        self.major_os_version = major_os_version;
+    }
+
+    pub fn get_minor_os_version(&self) -> u16 {
+        self.minor_os_version
+    }
+
+    pub fn set_minor_os_version(&mut self, minor_os_version: u16) {
+        self.minor_os_version = minor_os_version;
+    }
+
+    pub fn get_major_image_version(&self) -> u16 {
+        self.major_image_version
+    }
+
+    pub fn set_major_image_version(&mut self, major_image_version: u16) {
+        self.major_image_version = major_image_version;
+    }
+
+    pub fn get_minor_image_version(&self) -> u16 {
+        self.minor_image_version
+    }
+
+    pub fn set_minor_image_version(&mut self, minor_image_version: u16) {
+        self.minor_image_version = minor_image_version;
+    }
+
+    pub fn get_major_subsystem_version(&self) -> u16 {
+        self.major_subsystem_version
+    }
+
+    pub fn set_major_subsystem_version(&mut self,
+        major_subsystem_version: u16) {
+        self.major_subsystem_version = major_subsystem_version;
+    }
+
+    pub fn get_minor_subsystem_version(&self) -> u16 {
+        self.minor_subsystem_version
+    }
+
+    pub fn set_minor_subsystem_version(&mut self,
+        minor_subsystem_version: u16) {
+        self.minor_subsystem_version = minor_subsystem_version;
+    }
+
+    pub fn get_win32_version_value(&self) -> u32 {
+        self.win32_version_value
+    }
+
+    pub fn set_win32_version_value(&mut self, win32_version_value: u32) {
+        self.win32_version_value = win32_version_value;
+    }
+
+    pub fn get_size_of_image(&self) -> u32 {
+        self.size_of_image
+    }
+
+    pub fn set_size_of_image(&mut self, size_of_image: u32) {
+        self.size_of_image = size_of_image;
+    }
+
+    pub fn get_size_of_headers(&self) -> u32 {
+        self.size_of_headers
+    }
+
+    pub fn set_size_of_headers(&mut self, size_of_headers: u32) {
+        self.size_of_headers = size_of_headers;
+    }
+
+    pub fn get_check_sum(&self) -> u32 {
+        self.check_sum
+    }
+
+    pub fn set_check_sum(&mut self, check_sum: u32) {
+        self.check_sum = check_sum;
+    }
+
+    pub fn get_subsystem(&self) -> u16 {
+        self.subsystem
+    }
+
+    pub fn set_subsystem(&mut self, subsystem: u16) {
+        self.subsystem = subsystem;
+    }
+
+    pub fn get_dll_characteristics(&self) -> u16 {
+        self.dll_characteristics
+    }
+
+    pub fn set_dll_characteristics(&mut self, dll_characteristics: u16) {
+        self.dll_characteristics = dll_characteristics;
+    }
+
+    pub fn get_size_of_stack_reserve(&self) -> u64 {
+        self.size_of_stack_reserve
+    }
+
+    pub fn set_size_of_stack_reserve(&mut self, size_of_stack_reserve: u64) {
+        self.size_of_stack_reserve = size_of_stack_reserve;
+    }
+
+    pub fn get_size_of_stack_commit(&self) -> u64 {
+        self.size_of_stack_commit
+    }
+
+    pub fn set_size_of_stack_commit(&mut self, size_of_stack_commit: u64) {
+        self.size_of_stack_commit = size_of_stack_commit;
+    }
+
+    pub fn get_size_of_heap_reserve(&self) -> u64 {
+        self.size_of_heap_reserve
+    }
+
+    pub fn set_size_of_heap_reserve(&mut self, size_of_heap_reserve: u64) {
+        self.size_of_heap_reserve = size_of_heap_reserve;
+    }
+
+    pub fn get_size_of_heap_commit(&self) -> u64 {
+        self.size_of_heap_commit
+    }
+
+    pub fn set_size_of_heap_commit(&mut self, size_of_heap_commit: u64) {
+        self.size_of_heap_commit = size_of_heap_commit;
+    }
+
+    pub fn get_loader_flags(&self) -> u32 {
+        self.loader_flags
+    }
+
+    pub fn set_loader_flags(&mut self, loader_flags: u32) {
+        self.loader_flags = loader_flags;
+    }
+
+    pub fn get_number_of_rva_and_sizes(&self) -> u32 {
+        self.number_of_rva_and_sizes
+    }
+
+    pub fn set_number_of_rva_and_sizes(&mut self,
+        number_of_rva_and_sizes: u32) {
+        self.number_of_rva_and_sizes = number_of_rva_and_sizes;
+    }
+
+    pub fn get_data_directories(&self) -> &[DataDirectory] {
+        &self.data_directories
+    }
+
+    pub fn get_data_directory(&self, directory: DataDirectoryType) ->
+        Option<DataDirectory> {
+        self.data_directories
+            .get(DataDirectoryType::to_value(directory))
+            .copied()
+    }
+
+    pub fn set_data_directory(&mut self, directory: DataDirectoryType,
+        entry: DataDirectory) -> Result<(), ButylError> {
+        match self.data_directories.get_mut(
+            DataDirectoryType::to_value(directory)) {
+            Some(t) => {
+                *t = entry;
+                Ok(())
+            },
+            None => Err(ButylError::InsufficientDataError)
+        }
+    }
+
+    /* file offset of the given data directory entry relative to the start
+     * of the optional header */
+    pub fn get_data_directory_offset(&self, directory: DataDirectoryType) ->
+        usize {
+        let base: usize = if self.is_pe32_plus() { 112 } else { 96 };
+        base + DataDirectoryType::to_value(directory) * PE_DATA_DIRECTORY_LEN
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8]) ->
+        Result<PeOptionalHeader, ButylError> {
+        let mut optional_header: PeOptionalHeader =
+            PeOptionalHeader::default();
+
+        optional_header.magic = read_le_u16(bytes, 0)?;
+
+        let pe32_plus: bool = match optional_header.magic {
+            PE_OPTIONAL_HEADER_MAGIC_PE32 => false,
+            PE_OPTIONAL_HEADER_MAGIC_PE32_PLUS => true,
+            _ => return Err(ButylError::InvalidMagicError)
+        };
+
+        optional_header.major_linker_version = read_u8(bytes, 2)?;
+        optional_header.minor_linker_version = read_u8(bytes, 3)?;
+        optional_header.size_of_code = read_le_u32(bytes, 4)?;
+        optional_header.size_of_initialized_data = read_le_u32(bytes, 8)?;
+        optional_header.size_of_uninitialized_data = read_le_u32(bytes, 12)?;
+        optional_header.address_of_entry_point = read_le_u32(bytes, 16)?;
+        optional_header.base_of_code = read_le_u32(bytes, 20)?;
+
+        /* PE32+ drops BaseOfData and widens ImageBase to 64 bits */
+        if pe32_plus {
+            optional_header.image_base = read_le_u64(bytes, 24)?;
+        } else {
+            optional_header.base_of_data = read_le_u32(bytes, 24)?;
+            optional_header.image_base = read_le_u32(bytes, 28)? as u64;
+        }
+
+        optional_header.section_alignment = read_le_u32(bytes, 32)?;
+        optional_header.file_alignment = read_le_u32(bytes, 36)?;
+        optional_header.major_os_version = read_le_u16(bytes, 40)?;
+        optional_header.minor_os_version = read_le_u16(bytes, 42)?;
+        optional_header.major_image_version = read_le_u16(bytes, 44)?;
+        optional_header.minor_image_version = read_le_u16(bytes, 46)?;
+        optional_header.major_subsystem_version = read_le_u16(bytes, 48)?;
+        optional_header.minor_subsystem_version = read_le_u16(bytes, 50)?;
+        optional_header.win32_version_value = read_le_u32(bytes, 52)?;
+        optional_header.size_of_image = read_le_u32(bytes, 56)?;
+        optional_header.size_of_headers = read_le_u32(bytes, 60)?;
+        optional_header.check_sum = read_le_u32(bytes, 64)?;
+        optional_header.subsystem = read_le_u16(bytes, 68)?;
+        optional_header.dll_characteristics = read_le_u16(bytes, 70)?;
+
+        let directories_offset: usize = if pe32_plus {
+            optional_header.size_of_stack_reserve = read_le_u64(bytes, 72)?;
+            optional_header.size_of_stack_commit = read_le_u64(bytes, 80)?;
+            optional_header.size_of_heap_reserve = read_le_u64(bytes, 88)?;
+            optional_header.size_of_heap_commit = read_le_u64(bytes, 96)?;
+            optional_header.loader_flags = read_le_u32(bytes, 104)?;
+            optional_header.number_of_rva_and_sizes = read_le_u32(bytes, 108)?;
+            112
+        } else {
+            optional_header.size_of_stack_reserve =
+                read_le_u32(bytes, 72)? as u64;
+            optional_header.size_of_stack_commit =
+                read_le_u32(bytes, 76)? as u64;
+            optional_header.size_of_heap_reserve =
+                read_le_u32(bytes, 80)? as u64;
+            optional_header.size_of_heap_commit =
+                read_le_u32(bytes, 84)? as u64;
+            optional_header.loader_flags = read_le_u32(bytes, 88)?;
+            optional_header.number_of_rva_and_sizes = read_le_u32(bytes, 92)?;
+            96
+        };
+
+        /* the loader ignores anything beyond the sixteen known entries */
+        let count: usize = (optional_header.number_of_rva_and_sizes as usize)
+            .min(PE_MAX_DATA_DIRECTORIES);
+
+        for i in 0..count {
+            let offset: usize = directories_offset + i * PE_DATA_DIRECTORY_LEN;
+
+            optional_header.data_directories.push(
+                DataDirectory::from_le_bytes(
+                    read_bytes(bytes, offset, PE_DATA_DIRECTORY_LEN)?)?);
+        }
+
+        Ok(optional_header)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SectionHeader {
+    name: [u8; PE_SECTION_NAME_LEN],    /* section name */
+    virtual_size: u32,                  /* size in memory */
+    virtual_address: u32,               /* RVA of section */
+    size_of_raw_data: u32,              /* size on disk */
+    pointer_to_raw_data: u32,           /* file offset of section data */
+    pointer_to_relocations: u32,        /* file offset of relocations */
+    pointer_to_linenumbers: u32,        /* file offset of line numbers */
+    number_of_relocations: u16,         /* number of relocations */
+    number_of_linenumbers: u16,         /* number of line numbers */
+    characteristics: u32                /* flags */
+}
+
+impl SectionHeader {
+    pub fn get_name(&self) -> [u8; PE_SECTION_NAME_LEN] {
+        self.name
+    }
+
+    pub fn get_name_as_string(&self) -> String {
+        let len: usize = self.name.iter()
+            .position(|b| *b == 0)
+            .unwrap_or(PE_SECTION_NAME_LEN);
+
+        String::from_utf8_lossy(&self.name[..len]).into_owned()
+    }
+
+    pub fn set_name(&mut self, name: [u8; PE_SECTION_NAME_LEN]) {
+        self.name = name;
+    }
+
+    pub fn get_virtual_size(&self) -> u32 {
+        self.virtual_size
+    }
+
+    pub fn set_virtual_size(&mut self, virtual_size: u32) {
+        self.virtual_size = virtual_size;
+    }
+
+    pub fn get_virtual_address(&self) -> u32 {
+        self.virtual_address
+    }
+
+    pub fn set_virtual_address(&mut self, virtual_address: u32) {
+        self.virtual_address = virtual_address;
+    }
+
+    pub fn get_size_of_raw_data(&self) -> u32 {
+        self.size_of_raw_data
+    }
+
+    pub fn set_size_of_raw_data(&mut self, size_of_raw_data: u32) {
+        self.size_of_raw_data = size_of_raw_data;
+    }
+
+    pub fn get_pointer_to_raw_data(&self) -> u32 {
+        self.pointer_to_raw_data
+    }
+
+    pub fn set_pointer_to_raw_data(&mut self, pointer_to_raw_data: u32) {
+        self.pointer_to_raw_data = pointer_to_raw_data;
+    }
+
+    pub fn get_pointer_to_relocations(&self) -> u32 {
+        self.pointer_to_relocations
+    }
+
+    pub fn set_pointer_to_relocations(&mut self, pointer_to_relocations: u32) {
+        self.pointer_to_relocations = pointer_to_relocations;
+    }
+
+    pub fn get_pointer_to_linenumbers(&self) -> u32 {
+        self.pointer_to_linenumbers
+    }
+
+    pub fn set_pointer_to_linenumbers(&mut self, pointer_to_linenumbers: u32) {
+        self.pointer_to_linenumbers = pointer_to_linenumbers;
+    }
+
+    pub fn get_number_of_relocations(&self) -> u16 {
+        self.number_of_relocations
+    }
+
+    pub fn set_number_of_relocations(&mut self, number_of_relocations: u16) {
+        self.number_of_relocations = number_of_relocations;
+    }
+
+    pub fn get_number_of_linenumbers(&self) -> u16 {
+        self.number_of_linenumbers
+    }
+
+    pub fn set_number_of_linenumbers(&mut self, number_of_linenumbers: u16) {
+        self.number_of_linenumbers = number_of_linenumbers;
+    }
+
+    pub fn get_characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    pub fn set_characteristics(&mut self, characteristics: u32) {
+        self.characteristics = characteristics;
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<SectionHeader, ButylError> {
+        let mut section_header: SectionHeader = SectionHeader::default();
+
+        section_header.name.copy_from_slice(
+            read_bytes(bytes, 0, PE_SECTION_NAME_LEN)?);
+        section_header.virtual_size = read_le_u32(bytes, 8)?;
+        section_header.virtual_address = read_le_u32(bytes, 12)?;
+        section_header.size_of_raw_data = read_le_u32(bytes, 16)?;
+        section_header.pointer_to_raw_data = read_le_u32(bytes, 20)?;
+        section_header.pointer_to_relocations = read_le_u32(bytes, 24)?;
+        section_header.pointer_to_linenumbers = read_le_u32(bytes, 28)?;
+        section_header.number_of_relocations = read_le_u16(bytes, 32)?;
+        section_header.number_of_linenumbers = read_le_u16(bytes, 34)?;
+        section_header.characteristics = read_le_u32(bytes, 36)?;
+
+        Ok(section_header)
+    }
+}
+
+#[derive(Debug)]
+pub struct PeFile<'a> {
+    dos_header: DosHeader,
+    coff_header: CoffHeader,
+    optional_header: PeOptionalHeader,
+    sections: Vec<SectionHeader>,
+    address_space: AddressSpace,
+    data: &'a[u8]
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_dos_header(&self) -> &DosHeader {
+        &self.dos_header
+    }
+
+    pub fn get_coff_header(&self) -> &CoffHeader {
+        &self.coff_header
+    }
+
+    pub fn get_optional_header(&self) -> &PeOptionalHeader {
+        &self.optional_header
+    }
+
+    pub fn get_sections(&self) -> &[SectionHeader] {
+        &self.sections
+    }
+
+    pub fn get_address_space(&self) -> &AddressSpace {
+        &self.address_space
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn get_coff_header_offset(&self) -> usize {
+        self.dos_header.get_lfanew() as usize + PE_SIGNATURE_LEN
+    }
+
+    pub fn get_optional_header_offset(&self) -> usize {
+        self.get_coff_header_offset() + PE_COFF_HEADER_LEN
+    }
+
+    pub fn get_section_table_offset(&self) -> usize {
+        self.get_optional_header_offset() +
+            self.coff_header.get_opthdr() as usize
+    }
+
+    pub fn is_64_bit(&self) -> bool {
+        self.optional_header.is_pe32_plus()
+    }
+
+    pub fn get_data_directory(&self, directory: DataDirectoryType) ->
+        Option<DataDirectory> {
+        self.optional_header.get_data_directory(directory)
+            .filter(|t| t.is_present())
+    }
+
+    pub fn rva_to_offset(&self, rva: u32) -> Result<usize, ButylError> {
+        Ok(self.address_space.rva_to_offset(rva)?)
+    }
+
+    pub fn offset_to_rva(&self, offset: usize) -> Result<u32, ButylError> {
+        Ok(self.address_space.offset_to_rva(offset)?)
+    }
+
+    pub fn va_to_rva(&self, va: u64) -> Result<u32, ButylError> {
+        Ok(self.address_space.va_to_rva(va)?)
+    }
+
+    pub fn read_rva(&self, rva: u32, len: usize) ->
+        Result<&'a[u8], ButylError> {
+        read_bytes(self.data, self.rva_to_offset(rva)?, len)
+    }
+
+    pub fn read_cstring_at_rva(&self, rva: u32) -> Result<String, ButylError> {
+        read_cstring(self.data, self.rva_to_offset(rva)?)
+    }
+
+    pub fn get_check_sum_offset(&self) -> usize {
+        self.get_optional_header_offset() + PE_OPTIONAL_HEADER_CHECK_SUM_OFFSET
+    }
+
+    pub fn compute_check_sum(&self) -> u32 {
+        compute_check_sum(self.data, self.get_check_sum_offset())
+    }
+
+    pub fn from_le_bytes(data: &[u8]) -> Result<PeFile<'_>, ButylError> {
+        let dos_header: DosHeader = DosHeader::from_le_bytes(data)?;
+        let pe_offset: usize = dos_header.get_lfanew() as usize;
+
+        if read_le_u32(data, pe_offset)? != PE_SIGNATURE {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let coff_offset: usize = pe_offset + PE_SIGNATURE_LEN;
+        let coff_header: CoffHeader = CoffHeader::from_le_bytes(
+            read_bytes(data, coff_offset, PE_COFF_HEADER_LEN)?)?;
+
+        let optional_offset: usize = coff_offset + PE_COFF_HEADER_LEN;
+        let optional_header: PeOptionalHeader = PeOptionalHeader::from_le_bytes(
+            read_bytes(data, optional_offset,
+                coff_header.get_opthdr() as usize)?)?;
+
+        let section_table_offset: usize =
+            optional_offset + coff_header.get_opthdr() as usize;
+        let mut sections: Vec<SectionHeader> = Vec::new();
+
+        for i in 0..coff_header.get_nscns() as usize {
+            let offset: usize = section_table_offset +
+                i * PE_SECTION_HEADER_LEN;
+
+            sections.push(SectionHeader::from_le_bytes(
+                read_bytes(data, offset, PE_SECTION_HEADER_LEN)?)?);
+        }
+
+        let address_space: AddressSpace = AddressSpace::new(&optional_header,
+            &sections, data.len());
+
+        Ok(PeFile {
+            dos_header,
+            coff_header,
+            optional_header,
+            sections,
+            address_space,
+            data
+        })
+    }
+}
+
+/* the image checksum as computed by CheckSumMappedFile: a 16-bit one's
+ * complement style sum over the file, skipping the CheckSum field itself,
+ * plus the file length */
+pub fn compute_check_sum(data: &[u8], check_sum_offset: usize) -> u32 {
+    let mut sum: u64 = 0;
+
+    for (i, chunk) in data.chunks(2).enumerate() {
+        if i * 2 >= check_sum_offset && i * 2 < check_sum_offset + 4 {
+            continue;
+        }
+
+        let word: u64 = match chunk {
+            [lo, hi] => ((*hi as u64) << 8) | *lo as u64,
+            [lo] => *lo as u64,
+            _ => 0
+        };
+
+        sum += word;
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    sum = (sum & 0xffff) + (sum >> 16);
+
+    (sum as u32).wrapping_add(data.len() as u32)
+}
+
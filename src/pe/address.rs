@@ -0,0 +1,244 @@
+use std::convert::TryFrom;
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeOptionalHeader, SectionHeader};
+
+/* the loader ignores the low bits of PointerToRawData regardless of the
+ * declared file alignment */
+pub const RAW_DATA_POINTER_ALIGNMENT: u32 = 0x200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressKind {
+    Rva,
+    Va,
+    Offset
+}
+
+/* a section as the loader maps it, with sizes already aligned and clamped */
+#[derive(Debug, Default, Clone)]
+pub struct SectionMapping {
+    name: String,               /* section name */
+    virtual_address: u32,       /* RVA of the section start */
+    virtual_size: u32,          /* mapped size, aligned to SectionAlignment */
+    pointer_to_raw_data: u32,   /* effective file offset of the data */
+    raw_size: u32               /* file-backed bytes; the rest is zeroed */
+}
+
+impl SectionMapping {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_virtual_address(&self) -> u32 {
+        self.virtual_address
+    }
+
+    pub fn get_virtual_size(&self) -> u32 {
+        self.virtual_size
+    }
+
+    pub fn get_pointer_to_raw_data(&self) -> u32 {
+        self.pointer_to_raw_data
+    }
+
+    pub fn get_raw_size(&self) -> u32 {
+        self.raw_size
+    }
+
+    pub fn contains_rva(&self, rva: u32) -> bool {
+        rva >= self.virtual_address && rva - self.virtual_address <
+            self.virtual_size
+    }
+
+    pub fn contains_offset(&self, offset: usize) -> bool {
+        offset >= self.pointer_to_raw_data as usize &&
+            offset - (self.pointer_to_raw_data as usize) <
+            self.raw_size as usize
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AddressSpace {
+    image_base: u64,            /* preferred load address */
+    size_of_image: u32,         /* mapped size of the whole image */
+    size_of_headers: u32,       /* file-backed header bytes */
+    section_alignment: u32,     /* in-memory section alignment */
+    file_alignment: u32,        /* on-disk section alignment */
+    mappings: Vec<SectionMapping>
+}
+
+impl AddressSpace {
+    pub fn get_image_base(&self) -> u64 {
+        self.image_base
+    }
+
+    pub fn get_size_of_image(&self) -> u32 {
+        self.size_of_image
+    }
+
+    pub fn get_size_of_headers(&self) -> u32 {
+        self.size_of_headers
+    }
+
+    pub fn get_section_alignment(&self) -> u32 {
+        self.section_alignment
+    }
+
+    pub fn get_file_alignment(&self) -> u32 {
+        self.file_alignment
+    }
+
+    pub fn get_mappings(&self) -> &[SectionMapping] {
+        &self.mappings
+    }
+
+    pub fn get_mapping(&self, rva: u32) -> Option<&SectionMapping> {
+        self.mappings.iter().find(|t| t.contains_rva(rva))
+    }
+
+    pub fn rva_to_offset(&self, rva: u32) -> Result<usize, AddressError> {
+        if rva < self.size_of_headers {
+            return Ok(rva as usize);
+        }
+
+        if rva >= self.size_of_image {
+            return Err(AddressError::OutsideImage);
+        }
+
+        let mapping: &SectionMapping = match self.get_mapping(rva) {
+            Some(t) => t,
+            None if rva < self.get_headers_end() =>
+                return Err(AddressError::NotInFile),
+            None => return Err(AddressError::NotInSection)
+        };
+
+        let delta: u32 = rva - mapping.get_virtual_address();
+
+        if delta >= mapping.get_raw_size() {
+            return Err(AddressError::NotInFile);
+        }
+
+        Ok(mapping.get_pointer_to_raw_data() as usize + delta as usize)
+    }
+
+    pub fn offset_to_rva(&self, offset: usize) -> Result<u32, AddressError> {
+        if offset < self.size_of_headers as usize {
+            return Ok(offset as u32);
+        }
+
+        match self.mappings.iter().find(|t| t.contains_offset(offset)) {
+            Some(t) => t.get_virtual_address()
+                .checked_add((offset - t.get_pointer_to_raw_data() as usize)
+                    as u32)
+                .ok_or(AddressError::OutsideImage),
+            None => Err(AddressError::Overlay)
+        }
+    }
+
+    pub fn va_to_rva(&self, va: u64) -> Result<u32, AddressError> {
+        match va.checked_sub(self.image_base) {
+            Some(t) if t < self.size_of_image as u64 => Ok(t as u32),
+            Some(_) => Err(AddressError::OutsideImage),
+            None => Err(AddressError::BelowImageBase)
+        }
+    }
+
+    pub fn rva_to_va(&self, rva: u32) -> Result<u64, AddressError> {
+        if rva >= self.size_of_image {
+            return Err(AddressError::OutsideImage);
+        }
+
+        self.image_base.checked_add(rva as u64)
+            .ok_or(AddressError::OutsideImage)
+    }
+
+    pub fn translate(&self, address: u64, from: AddressKind, to: AddressKind)
+        -> Result<u64, AddressError> {
+        let rva: u32 = match from {
+            AddressKind::Rva => u32::try_from(address)
+                .map_err(|_e| AddressError::OutsideImage)?,
+            AddressKind::Va => self.va_to_rva(address)?,
+            AddressKind::Offset => self.offset_to_rva(address as usize)?
+        };
+
+        match to {
+            AddressKind::Rva => Ok(rva as u64),
+            AddressKind::Va => self.rva_to_va(rva),
+            AddressKind::Offset => Ok(self.rva_to_offset(rva)? as u64)
+        }
+    }
+
+    /* how far the headers and sections actually reach once mapped, which a
+     * consistent SizeOfImage matches */
+    pub fn get_mapped_extent(&self) -> u64 {
+        self.mappings.iter()
+            .map(|t| t.get_virtual_address() as u64 +
+                t.get_virtual_size() as u64)
+            .fold(self.get_headers_end() as u64, u64::max)
+            .next_multiple_of(self.section_alignment as u64)
+    }
+
+    /* the headers occupy their own aligned region ahead of the sections */
+    fn get_headers_end(&self) -> u32 {
+        align_up(self.size_of_headers, self.section_alignment)
+    }
+
+    pub fn new(optional_header: &PeOptionalHeader, sections: &[SectionHeader],
+        file_len: usize) -> AddressSpace {
+        let section_alignment: u32 = optional_header.get_section_alignment()
+            .max(1);
+        let file_alignment: u32 = optional_header.get_file_alignment().max(1);
+        let mut mappings: Vec<SectionMapping> = Vec::new();
+
+        for section in sections.iter() {
+            let pointer_to_raw_data: u32 =
+                if file_alignment >= RAW_DATA_POINTER_ALIGNMENT {
+                    section.get_pointer_to_raw_data() &
+                        !(RAW_DATA_POINTER_ALIGNMENT - 1)
+                } else {
+                    section.get_pointer_to_raw_data()
+                };
+
+            /* a zero VirtualSize means the raw size is used instead */
+            let virtual_size: u32 = align_up(match section.get_virtual_size() {
+                0 => section.get_size_of_raw_data(),
+                t => t
+            }, section_alignment);
+
+            /* raw data can't extend past the mapped region or the file */
+            let available: u32 = (file_len as u64)
+                .saturating_sub(pointer_to_raw_data as u64)
+                .min(u32::MAX as u64) as u32;
+            let raw_size: u32 = align_up(section.get_size_of_raw_data(),
+                file_alignment)
+                .min(virtual_size)
+                .min(available);
+
+            mappings.push(SectionMapping {
+                name: section.get_name_as_string(),
+                virtual_address: section.get_virtual_address(),
+                virtual_size,
+                pointer_to_raw_data: if raw_size == 0 { 0 } else {
+                    pointer_to_raw_data
+                },
+                raw_size
+            });
+        }
+
+        AddressSpace {
+            image_base: optional_header.get_image_base(),
+            size_of_image: optional_header.get_size_of_image(),
+            size_of_headers: optional_header.get_size_of_headers()
+                .min(file_len.min(u32::MAX as usize) as u32),
+            section_alignment,
+            file_alignment,
+            mappings
+        }
+    }
+}
+
+fn align_up(value: u32, alignment: u32) -> u32 {
+    value.checked_next_multiple_of(alignment).unwrap_or(u32::MAX)
+}
+
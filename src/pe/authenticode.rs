@@ -0,0 +1,705 @@
+use std::convert::TryFrom;
+use std::result::Result;
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::errors::*;
+use crate::der::*;
+use crate::pe::{self, PeFile, DataDirectory, DataDirectoryType, SectionHeader,
+    PE_OPTIONAL_HEADER_CHECK_SUM_OFFSET, PE_DATA_DIRECTORY_LEN};
+use crate::utils::*;
+
+pub const WIN_CERTIFICATE_HEADER_LEN: usize = 8;
+pub const WIN_CERTIFICATE_ALIGNMENT: usize = 8;
+pub const WIN_CERT_REVISION_1_0: u16 = 0x0100;
+pub const WIN_CERT_REVISION_2_0: u16 = 0x0200;
+
+pub const OID_SIGNED_DATA: &str = "1.2.840.113549.1.7.2";
+pub const OID_SPC_INDIRECT_DATA: &str = "1.3.6.1.4.1.311.2.1.4";
+pub const OID_MESSAGE_DIGEST: &str = "1.2.840.113549.1.9.4";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WinCertificateType {
+    X509,
+    PkcsSignedData,
+    Reserved,
+    TsStackSigned,
+    Unknown(u16)
+}
+
+impl WinCertificateType {
+    pub fn to_value(certificate_type: WinCertificateType) -> u16 {
+        match certificate_type {
+            WinCertificateType::X509 => 0x0001,
+            WinCertificateType::PkcsSignedData => 0x0002,
+            WinCertificateType::Reserved => 0x0003,
+            WinCertificateType::TsStackSigned => 0x0004,
+            WinCertificateType::Unknown(t) => t
+        }
+    }
+
+    pub fn from_value(value: u16) -> WinCertificateType {
+        match value {
+            0x0001 => WinCertificateType::X509,
+            0x0002 => WinCertificateType::PkcsSignedData,
+            0x0003 => WinCertificateType::Reserved,
+            0x0004 => WinCertificateType::TsStackSigned,
+            t => WinCertificateType::Unknown(t)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    Unknown(String)
+}
+
+impl DigestAlgorithm {
+    pub fn from_oid(oid: &str) -> DigestAlgorithm {
+        match oid {
+            "1.2.840.113549.2.5" => DigestAlgorithm::Md5,
+            "1.3.14.3.2.26" => DigestAlgorithm::Sha1,
+            "2.16.840.1.101.3.4.2.1" => DigestAlgorithm::Sha256,
+            "2.16.840.1.101.3.4.2.2" => DigestAlgorithm::Sha384,
+            "2.16.840.1.101.3.4.2.3" => DigestAlgorithm::Sha512,
+            t => DigestAlgorithm::Unknown(t.to_string())
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            DigestAlgorithm::Md5 => String::from("MD5"),
+            DigestAlgorithm::Sha1 => String::from("SHA-1"),
+            DigestAlgorithm::Sha256 => String::from("SHA-256"),
+            DigestAlgorithm::Sha384 => String::from("SHA-384"),
+            DigestAlgorithm::Sha512 => String::from("SHA-512"),
+            DigestAlgorithm::Unknown(t) => t.clone()
+        }
+    }
+
+    fn from_algorithm_identifier(identifier: &DerObject) ->
+        Result<DigestAlgorithm, ButylError> {
+        Ok(DigestAlgorithm::from_oid(&identifier.get_child(0)?.as_oid()?))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WinCertificate<'a> {
+    length: u32,            /* length including this header */
+    revision: u16,          /* WIN_CERT_REVISION_* */
+    certificate_type: u16,  /* WIN_CERT_TYPE_* */
+    certificate: &'a[u8]    /* the certificate blob itself */
+}
+
+impl<'a> WinCertificate<'a> {
+    pub fn get_length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn get_revision(&self) -> u16 {
+        self.revision
+    }
+
+    pub fn get_certificate_type(&self) -> u16 {
+        self.certificate_type
+    }
+
+    pub fn get_certificate_type_as_enum(&self) -> WinCertificateType {
+        WinCertificateType::from_value(self.certificate_type)
+    }
+
+    pub fn get_certificate(&self) -> &'a[u8] {
+        self.certificate
+    }
+
+    pub fn get_signed_data(&self) -> Result<SignedData, ButylError> {
+        if self.get_certificate_type_as_enum() !=
+            WinCertificateType::PkcsSignedData {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        SignedData::from_der(self.certificate)
+    }
+
+    pub fn from_le_bytes(bytes: &'a[u8]) ->
+        Result<WinCertificate<'a>, ButylError> {
+        let length: u32 = read_le_u32(bytes, 0)?;
+
+        if (length as usize) < WIN_CERTIFICATE_HEADER_LEN {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        Ok(WinCertificate {
+            length,
+            revision: read_le_u16(bytes, 4)?,
+            certificate_type: read_le_u16(bytes, 6)?,
+            certificate: read_bytes(bytes, WIN_CERTIFICATE_HEADER_LEN,
+                length as usize - WIN_CERTIFICATE_HEADER_LEN)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Certificate {
+    serial_number: Vec<u8>,
+    issuer: String,
+    subject: String,
+    not_before: String,
+    not_after: String
+}
+
+impl Certificate {
+    pub fn get_serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+
+    pub fn get_serial_number_as_string(&self) -> String {
+        format_hex(&self.serial_number)
+    }
+
+    pub fn get_issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn get_not_before(&self) -> &str {
+        &self.not_before
+    }
+
+    pub fn get_not_after(&self) -> &str {
+        &self.not_after
+    }
+
+    pub fn from_der_object(certificate: &DerObject) ->
+        Result<Certificate, ButylError> {
+        let tbs: Vec<DerObject> = certificate.get_child(0)?
+            .expect(DER_TAG_SEQUENCE)?
+            .get_children()?;
+
+        /* the version is optional and explicitly tagged */
+        let base: usize = match tbs.first() {
+            Some(t) if t.is_context(0) => 1,
+            _ => 0
+        };
+
+        let field = |index: usize| -> Result<DerObject, ButylError> {
+            tbs.get(base + index).copied().ok_or(ButylError::MalformedDataError)
+        };
+
+        let validity: DerObject = field(3)?.expect(DER_TAG_SEQUENCE)?;
+
+        Ok(Certificate {
+            serial_number: field(0)?.expect(DER_TAG_INTEGER)?
+                .get_contents().to_vec(),
+            issuer: format_name(&field(2)?)?,
+            subject: format_name(&field(4)?)?,
+            not_before: validity.get_child(0)?.as_string()?,
+            not_after: validity.get_child(1)?.as_string()?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignerInfo {
+    issuer: String,
+    serial_number: Vec<u8>,
+    digest_algorithm: DigestAlgorithm,
+    message_digest: Option<Vec<u8>>    /* digest of the signed content */
+}
+
+impl SignerInfo {
+    pub fn get_issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn get_serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+
+    pub fn get_serial_number_as_string(&self) -> String {
+        format_hex(&self.serial_number)
+    }
+
+    pub fn get_digest_algorithm(&self) -> &DigestAlgorithm {
+        &self.digest_algorithm
+    }
+
+    pub fn get_message_digest(&self) -> Option<&[u8]> {
+        self.message_digest.as_deref()
+    }
+
+    pub fn from_der_object(signer_info: &DerObject) ->
+        Result<SignerInfo, ButylError> {
+        let fields: Vec<DerObject> = signer_info.get_children()?;
+        let field = |index: usize| -> Result<DerObject, ButylError> {
+            fields.get(index).copied().ok_or(ButylError::MalformedDataError)
+        };
+
+        /* version 3 signers may use a subject key identifier instead */
+        let (issuer, serial_number): (String, Vec<u8>) =
+            match field(1)?.get_tag() {
+                DER_TAG_SEQUENCE => {
+                    let id: DerObject = field(1)?;
+                    (format_name(&id.get_child(0)?)?,
+                        id.get_child(1)?.get_contents().to_vec())
+                },
+                _ => (String::new(), field(1)?.get_contents().to_vec())
+            };
+
+        let mut message_digest: Option<Vec<u8>> = None;
+
+        if let Ok(attributes) = field(3) {
+            if attributes.is_context(0) {
+                for attribute in attributes.get_children()? {
+                    if attribute.get_child(0)?.as_oid()? == OID_MESSAGE_DIGEST {
+                        message_digest = Some(attribute.get_child(1)?
+                            .get_child(0)?.get_contents().to_vec());
+                    }
+                }
+            }
+        }
+
+        Ok(SignerInfo {
+            issuer,
+            serial_number,
+            digest_algorithm: DigestAlgorithm::from_algorithm_identifier(
+                &field(2)?)?,
+            message_digest
+        })
+    }
+}
+
+/* the parts of a PKCS#7 SignedData that carry an Authenticode signature */
+#[derive(Debug, Clone)]
+pub struct SignedData {
+    version: u32,
+    content_type: String,
+    digest_algorithm: DigestAlgorithm,  /* algorithm of the image digest */
+    digest: Vec<u8>,                    /* signed Authenticode image digest */
+    certificates: Vec<Certificate>,
+    signers: Vec<SignerInfo>
+}
+
+impl SignedData {
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn get_content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn get_digest_algorithm(&self) -> &DigestAlgorithm {
+        &self.digest_algorithm
+    }
+
+    pub fn get_digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    pub fn get_certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    pub fn get_signers(&self) -> &[SignerInfo] {
+        &self.signers
+    }
+
+    pub fn from_der(bytes: &[u8]) -> Result<SignedData, ButylError> {
+        let (content_info, _) = DerObject::parse(bytes)?;
+        let content_info: DerObject = content_info.expect(DER_TAG_SEQUENCE)?;
+
+        if content_info.get_child(0)?.as_oid()? != OID_SIGNED_DATA {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let signed_data: DerObject = content_info.get_child(1)?
+            .get_child(0)?
+            .expect(DER_TAG_SEQUENCE)?;
+        let fields: Vec<DerObject> = signed_data.get_children()?;
+
+        if fields.len() < 4 {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let version: u32 = fields[0].expect(DER_TAG_INTEGER)?.get_contents()
+            .iter()
+            .fold(0, |acc, b| (acc << 8) | *b as u32);
+
+        /* contentInfo: SpcIndirectDataContent, either directly inside the
+         * explicit tag (PKCS#7) or wrapped in an OCTET STRING (CMS) */
+        let encapsulated: DerObject = fields[2].expect(DER_TAG_SEQUENCE)?;
+        let content_type: String = encapsulated.get_child(0)?.as_oid()?;
+        let mut content: DerObject = encapsulated.get_child(1)?.get_child(0)?;
+
+        if content.get_tag() == DER_TAG_OCTET_STRING {
+            content = DerObject::parse(content.get_contents())?.0;
+        }
+
+        let digest_info: DerObject = content.get_child(1)?;
+        let digest_algorithm: DigestAlgorithm =
+            DigestAlgorithm::from_algorithm_identifier(
+                &digest_info.get_child(0)?)?;
+        let digest: Vec<u8> = digest_info.get_child(1)?
+            .expect(DER_TAG_OCTET_STRING)?
+            .get_contents()
+            .to_vec();
+
+        let mut certificates: Vec<Certificate> = Vec::new();
+        let mut signers: Vec<SignerInfo> = Vec::new();
+
+        for field in fields[3..].iter() {
+            if field.is_context(0) {
+                for certificate in field.get_children()? {
+                    /* skip attribute certificates and other choices */
+                    if certificate.get_tag() == DER_TAG_SEQUENCE {
+                        certificates.push(
+                            Certificate::from_der_object(&certificate)?);
+                    }
+                }
+            } else if field.get_tag() == DER_TAG_SET {
+                for signer in field.get_children()? {
+                    signers.push(SignerInfo::from_der_object(&signer)?);
+                }
+            }
+        }
+
+        Ok(SignedData {
+            version,
+            content_type,
+            digest_algorithm,
+            digest,
+            certificates,
+            signers
+        })
+    }
+}
+
+enum Hasher {
+    Sha1(Sha1),
+    Sha256(Sha256)
+}
+
+impl Hasher {
+    fn new(algorithm: &DigestAlgorithm) -> Result<Hasher, ButylError> {
+        match algorithm {
+            DigestAlgorithm::Sha1 => Ok(Hasher::Sha1(Sha1::new())),
+            DigestAlgorithm::Sha256 => Ok(Hasher::Sha256(Sha256::new())),
+            _ => Err(ButylError::UnsupportedFeatureError)
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Sha1(t) => t.update(bytes),
+            Hasher::Sha256(t) => t.update(bytes)
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha1(t) => t.finalize().to_vec(),
+            Hasher::Sha256(t) => t.finalize().to_vec()
+        }
+    }
+}
+
+/* outcome of comparing a signature's stored image digest against the
+ * digest of the file as it is now */
+#[derive(Debug, Clone)]
+pub struct DigestCheck {
+    algorithm: DigestAlgorithm,
+    expected: Vec<u8>,
+    actual: Vec<u8>
+}
+
+impl DigestCheck {
+    pub fn get_algorithm(&self) -> &DigestAlgorithm {
+        &self.algorithm
+    }
+
+    pub fn get_expected(&self) -> &[u8] {
+        &self.expected
+    }
+
+    pub fn get_actual(&self) -> &[u8] {
+        &self.actual
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|t| format!("{:02x}", t)).collect()
+}
+
+impl<'a> PeFile<'a> {
+    /* the security directory holds a file offset rather than an RVA, and
+     * is not mapped into memory */
+    pub fn get_certificates(&self) ->
+        Result<Vec<WinCertificate<'a>>, ButylError> {
+        let mut certificates: Vec<WinCertificate<'a>> = Vec::new();
+
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Security) {
+                Some(t) => t,
+                None => return Ok(certificates)
+            };
+
+        let table: &'a[u8] = read_bytes(self.get_data(),
+            directory.get_virtual_address() as usize,
+            directory.get_size() as usize)?;
+        let mut offset: usize = 0;
+
+        while offset + WIN_CERTIFICATE_HEADER_LEN <= table.len() {
+            let certificate: WinCertificate<'a> =
+                WinCertificate::from_le_bytes(&table[offset..])?;
+
+            offset += (certificate.get_length() as usize)
+                .next_multiple_of(WIN_CERTIFICATE_ALIGNMENT);
+            certificates.push(certificate);
+        }
+
+        Ok(certificates)
+    }
+
+    pub fn get_signatures(&self) -> Result<Vec<SignedData>, ButylError> {
+        self.get_certificates()?.iter()
+            .filter(|t| t.get_certificate_type_as_enum() ==
+                WinCertificateType::PkcsSignedData)
+            .map(|t| t.get_signed_data())
+            .collect()
+    }
+
+    /* computes the Authenticode image hash: everything except the checksum,
+     * the security directory entry and the certificate table itself */
+    pub fn compute_authenticode_digest(&self, algorithm: &DigestAlgorithm) ->
+        Result<Vec<u8>, ButylError> {
+        let data: &[u8] = self.get_data();
+        let mut hasher: Hasher = Hasher::new(algorithm)?;
+
+        let optional_offset: usize = self.get_optional_header_offset();
+        let check_sum_offset: usize =
+            optional_offset + PE_OPTIONAL_HEADER_CHECK_SUM_OFFSET;
+        let size_of_headers: usize =
+            self.get_optional_header().get_size_of_headers() as usize;
+
+        if size_of_headers > data.len() || size_of_headers < check_sum_offset {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        hasher.update(&data[..check_sum_offset]);
+
+        let mut offset: usize = check_sum_offset + 4;
+
+        if self.get_optional_header().get_data_directories().len() >
+            DataDirectoryType::to_value(DataDirectoryType::Security) {
+            let security_offset: usize = optional_offset +
+                self.get_optional_header().get_data_directory_offset(
+                    DataDirectoryType::Security);
+
+            hasher.update(read_bytes(data, offset, security_offset - offset)?);
+            offset = security_offset + PE_DATA_DIRECTORY_LEN;
+        }
+
+        /* the headers may end before the security entry does */
+        let remaining: usize = size_of_headers.checked_sub(offset)
+            .ok_or(ButylError::MalformedDataError)?;
+
+        hasher.update(read_bytes(data, offset, remaining)?);
+
+        let mut sections: Vec<&SectionHeader> = self.get_sections().iter()
+            .filter(|t| t.get_size_of_raw_data() != 0)
+            .collect();
+        sections.sort_by_key(|t| t.get_pointer_to_raw_data());
+
+        let mut sum_of_bytes_hashed: usize = size_of_headers;
+
+        for section in sections {
+            hasher.update(read_bytes(data,
+                section.get_pointer_to_raw_data() as usize,
+                section.get_size_of_raw_data() as usize)?);
+            sum_of_bytes_hashed += section.get_size_of_raw_data() as usize;
+        }
+
+        /* anything past the sections other than the certificate table */
+        let certificate_size: usize =
+            match self.get_data_directory(DataDirectoryType::Security) {
+                Some(t) => t.get_size() as usize,
+                None => 0
+            };
+
+        if data.len() > sum_of_bytes_hashed + certificate_size {
+            hasher.update(&data[sum_of_bytes_hashed..
+                data.len() - certificate_size]);
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    pub fn check_signature_digests(&self) ->
+        Result<Vec<DigestCheck>, ButylError> {
+        let mut checks: Vec<DigestCheck> = Vec::new();
+
+        for signature in self.get_signatures()? {
+            let algorithm: DigestAlgorithm =
+                signature.get_digest_algorithm().clone();
+
+            checks.push(DigestCheck {
+                actual: self.compute_authenticode_digest(&algorithm)?,
+                expected: signature.get_digest().to_vec(),
+                algorithm
+            });
+        }
+
+        Ok(checks)
+    }
+
+    /* returns a copy of the file with the certificate table removed, the
+     * security directory cleared and the checksum recomputed */
+    pub fn strip_signatures(&self) -> Result<Vec<u8>, ButylError> {
+        let mut data: Vec<u8> = self.get_data().to_vec();
+
+        if let Some(directory) =
+            self.get_data_directory(DataDirectoryType::Security) {
+            let start: usize = directory.get_virtual_address() as usize;
+            let end: usize = start + directory.get_size() as usize;
+
+            /* the table is never mapped, so only a trailing one can be
+             * dropped without disturbing the rest of the file */
+            if end != data.len() {
+                return Err(ButylError::UnsupportedFeatureError);
+            }
+
+            data.truncate(start);
+        }
+
+        self.write_security_directory(&mut data, DataDirectory::default())?;
+        Ok(data)
+    }
+
+    /* returns a copy of the file with `pkcs7` appended to the certificate
+     * table as a new WIN_CERT_TYPE_PKCS_SIGNED_DATA entry; the file is
+     * first padded to an 8-byte boundary, as Authenticode requires */
+    pub fn append_signature(&self, pkcs7: &[u8]) ->
+        Result<Vec<u8>, ButylError> {
+        let mut data: Vec<u8> = self.get_data().to_vec();
+
+        let mut directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Security) {
+                Some(t) => {
+                    if t.get_virtual_address() as usize +
+                        t.get_size() as usize != data.len() {
+                        return Err(ButylError::UnsupportedFeatureError);
+                    }
+
+                    t
+                },
+                None => {
+                    data.resize(data.len()
+                        .next_multiple_of(WIN_CERTIFICATE_ALIGNMENT), 0);
+
+                    let mut t: DataDirectory = DataDirectory::default();
+                    t.set_virtual_address(data.len() as u32);
+                    t
+                }
+            };
+
+        let length: usize = WIN_CERTIFICATE_HEADER_LEN + pkcs7.len();
+        let padded_length: usize =
+            length.next_multiple_of(WIN_CERTIFICATE_ALIGNMENT);
+
+        let size: u32 = u32::try_from(padded_length).ok()
+            .and_then(|t| directory.get_size().checked_add(t))
+            .ok_or(ButylError::ExcessiveDataError)?;
+
+        data.extend_from_slice(&(length as u32).to_le_bytes());
+        data.extend_from_slice(&WIN_CERT_REVISION_2_0.to_le_bytes());
+        data.extend_from_slice(&WinCertificateType::to_value(
+            WinCertificateType::PkcsSignedData).to_le_bytes());
+        data.extend_from_slice(pkcs7);
+        data.resize(data.len() + padded_length - length, 0);
+
+        directory.set_size(size);
+        self.write_security_directory(&mut data, directory)?;
+
+        Ok(data)
+    }
+
+    pub fn replace_signature(&self, pkcs7: &[u8]) ->
+        Result<Vec<u8>, ButylError> {
+        let stripped: Vec<u8> = self.strip_signatures()?;
+        PeFile::from_le_bytes(&stripped)?.append_signature(pkcs7)
+    }
+
+    fn write_security_directory(&self, data: &mut [u8],
+        directory: DataDirectory) -> Result<(), ButylError> {
+        if self.get_optional_header().get_data_directories().len() <=
+            DataDirectoryType::to_value(DataDirectoryType::Security) {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        let offset: usize = self.get_optional_header_offset() +
+            self.get_optional_header().get_data_directory_offset(
+                DataDirectoryType::Security);
+
+        write_le_u32(data, offset, directory.get_virtual_address())?;
+        write_le_u32(data, offset + 4, directory.get_size())?;
+
+        let check_sum_offset: usize = self.get_check_sum_offset();
+        let check_sum: u32 = pe::compute_check_sum(data, check_sum_offset);
+
+        write_le_u32(data, check_sum_offset, check_sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNED: &[u8] =
+        include_bytes!("../../tests/fixtures/pe/conda-cli-32.exe");
+
+    #[test]
+    fn append_then_strip_round_trips() {
+        let signed: PeFile = PeFile::from_le_bytes(SIGNED).unwrap();
+        let original: Vec<u8> = signed.strip_signatures().unwrap();
+        let unsigned: PeFile = PeFile::from_le_bytes(&original).unwrap();
+
+        assert!(unsigned.get_data_directory(DataDirectoryType::Security)
+            .is_none());
+        assert_eq!(unsigned.get_optional_header().get_check_sum(),
+            unsigned.compute_check_sum());
+
+        let certificates: Vec<WinCertificate> =
+            signed.get_certificates().unwrap();
+        let pkcs7: &[u8] = certificates[0].get_certificate();
+        let appended: Vec<u8> = unsigned.append_signature(pkcs7).unwrap();
+        let resigned: PeFile = PeFile::from_le_bytes(&appended).unwrap();
+
+        assert!(appended == SIGNED);
+        assert!(resigned.strip_signatures().unwrap() == original);
+
+        /* the signature covers none of what either step rewrites */
+        let signature: SignedData = resigned.get_signatures().unwrap()
+            .remove(0);
+        let algorithm: &DigestAlgorithm = signature.get_digest_algorithm();
+        let digest: Vec<u8> =
+            unsigned.compute_authenticode_digest(algorithm).unwrap();
+
+        assert_eq!(digest, signature.get_digest());
+        assert_eq!(resigned.compute_authenticode_digest(algorithm).unwrap(),
+            digest);
+    }
+}
@@ -0,0 +1,1084 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const COR20_HEADER_LEN: usize = 72;
+pub const METADATA_SIGNATURE: u32 = 0x424a_5342;    /* "BSJB" */
+pub const METADATA_TABLE_COUNT: usize = 64;
+pub const HEAP_SIZE_STRINGS: u8 = 0x01;
+pub const HEAP_SIZE_GUID: u8 = 0x02;
+pub const HEAP_SIZE_BLOB: u8 = 0x04;
+pub const HEAP_EXTRA_DATA: u8 = 0x40;
+pub const COMIMAGE_FLAGS_ILONLY: u32 = 0x0000_0001;
+pub const COMIMAGE_FLAGS_32BITREQUIRED: u32 = 0x0000_0002;
+pub const COMIMAGE_FLAGS_STRONGNAMESIGNED: u32 = 0x0000_0008;
+pub const COMIMAGE_FLAGS_NATIVE_ENTRYPOINT: u32 = 0x0000_0010;
+pub const COMIMAGE_FLAGS_32BITPREFERRED: u32 = 0x0002_0000;
+
+#[derive(Debug, Default, Clone)]
+pub struct CorHeader {
+    cb: u32,                                /* size of the header */
+    major_runtime_version: u16,             /* minimum runtime major version */
+    minor_runtime_version: u16,             /* minimum runtime minor version */
+    metadata: DataDirectory,                /* metadata root */
+    flags: u32,                             /* COMIMAGE_FLAGS_* */
+    entry_point: u32,                       /* entry point token or RVA */
+    resources: DataDirectory,               /* managed resources */
+    strong_name_signature: DataDirectory,   /* strong name hash */
+    code_manager_table: DataDirectory,      /* (reserved) */
+    vtable_fixups: DataDirectory,           /* unmanaged vtable fixups */
+    export_address_table_jumps: DataDirectory,  /* (reserved) */
+    managed_native_header: DataDirectory    /* ReadyToRun header */
+}
+
+impl CorHeader {
+    pub fn get_cb(&self) -> u32 {
+        self.cb
+    }
+
+    pub fn get_major_runtime_version(&self) -> u16 {
+        self.major_runtime_version
+    }
+
+    pub fn get_minor_runtime_version(&self) -> u16 {
+        self.minor_runtime_version
+    }
+
+    pub fn get_metadata(&self) -> DataDirectory {
+        self.metadata
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    /* a token unless COMIMAGE_FLAGS_NATIVE_ENTRYPOINT is set */
+    pub fn get_entry_point(&self) -> u32 {
+        self.entry_point
+    }
+
+    pub fn get_resources(&self) -> DataDirectory {
+        self.resources
+    }
+
+    pub fn get_strong_name_signature(&self) -> DataDirectory {
+        self.strong_name_signature
+    }
+
+    pub fn get_code_manager_table(&self) -> DataDirectory {
+        self.code_manager_table
+    }
+
+    pub fn get_vtable_fixups(&self) -> DataDirectory {
+        self.vtable_fixups
+    }
+
+    pub fn get_export_address_table_jumps(&self) -> DataDirectory {
+        self.export_address_table_jumps
+    }
+
+    pub fn get_managed_native_header(&self) -> DataDirectory {
+        self.managed_native_header
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<CorHeader, ButylError> {
+        let directory = |offset: usize| -> Result<DataDirectory, ButylError> {
+            DataDirectory::from_le_bytes(read_bytes(bytes, offset, 8)?)
+        };
+
+        Ok(CorHeader {
+            cb: read_le_u32(bytes, 0)?,
+            major_runtime_version: read_le_u16(bytes, 4)?,
+            minor_runtime_version: read_le_u16(bytes, 6)?,
+            metadata: directory(8)?,
+            flags: read_le_u32(bytes, 16)?,
+            entry_point: read_le_u32(bytes, 20)?,
+            resources: directory(24)?,
+            strong_name_signature: directory(32)?,
+            code_manager_table: directory(40)?,
+            vtable_fixups: directory(48)?,
+            export_address_table_jumps: directory(56)?,
+            managed_native_header: directory(64)?
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetadataTable {
+    Module,
+    TypeRef,
+    TypeDef,
+    FieldPtr,
+    Field,
+    MethodPtr,
+    MethodDef,
+    ParamPtr,
+    Param,
+    InterfaceImpl,
+    MemberRef,
+    Constant,
+    CustomAttribute,
+    FieldMarshal,
+    DeclSecurity,
+    ClassLayout,
+    FieldLayout,
+    StandAloneSig,
+    EventMap,
+    EventPtr,
+    Event,
+    PropertyMap,
+    PropertyPtr,
+    Property,
+    MethodSemantics,
+    MethodImpl,
+    ModuleRef,
+    TypeSpec,
+    ImplMap,
+    FieldRva,
+    EncLog,
+    EncMap,
+    Assembly,
+    AssemblyProcessor,
+    AssemblyOs,
+    AssemblyRef,
+    AssemblyRefProcessor,
+    AssemblyRefOs,
+    File,
+    ExportedType,
+    ManifestResource,
+    NestedClass,
+    GenericParam,
+    MethodSpec,
+    GenericParamConstraint
+}
+
+/* in table number order */
+const METADATA_TABLES: [MetadataTable; 45] = [
+    MetadataTable::Module,
+    MetadataTable::TypeRef,
+    MetadataTable::TypeDef,
+    MetadataTable::FieldPtr,
+    MetadataTable::Field,
+    MetadataTable::MethodPtr,
+    MetadataTable::MethodDef,
+    MetadataTable::ParamPtr,
+    MetadataTable::Param,
+    MetadataTable::InterfaceImpl,
+    MetadataTable::MemberRef,
+    MetadataTable::Constant,
+    MetadataTable::CustomAttribute,
+    MetadataTable::FieldMarshal,
+    MetadataTable::DeclSecurity,
+    MetadataTable::ClassLayout,
+    MetadataTable::FieldLayout,
+    MetadataTable::StandAloneSig,
+    MetadataTable::EventMap,
+    MetadataTable::EventPtr,
+    MetadataTable::Event,
+    MetadataTable::PropertyMap,
+    MetadataTable::PropertyPtr,
+    MetadataTable::Property,
+    MetadataTable::MethodSemantics,
+    MetadataTable::MethodImpl,
+    MetadataTable::ModuleRef,
+    MetadataTable::TypeSpec,
+    MetadataTable::ImplMap,
+    MetadataTable::FieldRva,
+    MetadataTable::EncLog,
+    MetadataTable::EncMap,
+    MetadataTable::Assembly,
+    MetadataTable::AssemblyProcessor,
+    MetadataTable::AssemblyOs,
+    MetadataTable::AssemblyRef,
+    MetadataTable::AssemblyRefProcessor,
+    MetadataTable::AssemblyRefOs,
+    MetadataTable::File,
+    MetadataTable::ExportedType,
+    MetadataTable::ManifestResource,
+    MetadataTable::NestedClass,
+    MetadataTable::GenericParam,
+    MetadataTable::MethodSpec,
+    MetadataTable::GenericParamConstraint
+];
+
+impl MetadataTable {
+    pub fn to_value(table: MetadataTable) -> usize {
+        METADATA_TABLES.iter().position(|t| *t == table).unwrap_or(0)
+    }
+
+    pub fn from_value(value: usize) -> Option<MetadataTable> {
+        METADATA_TABLES.get(value).copied()
+    }
+
+    pub fn get_name(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn get_columns(&self) -> &'static [Column] {
+        use self::CodedIndex as C;
+        use self::Column::*;
+        use self::MetadataTable as T;
+
+        match self {
+            T::Module => &[U16, Str, Guid, Guid, Guid],
+            T::TypeRef => &[Coded(C::ResolutionScope), Str, Str],
+            T::TypeDef => &[U32, Str, Str, Coded(C::TypeDefOrRef),
+                Table(T::Field), Table(T::MethodDef)],
+            T::FieldPtr => &[Table(T::Field)],
+            T::Field => &[U16, Str, Blob],
+            T::MethodPtr => &[Table(T::MethodDef)],
+            T::MethodDef => &[U32, U16, U16, Str, Blob, Table(T::Param)],
+            T::ParamPtr => &[Table(T::Param)],
+            T::Param => &[U16, U16, Str],
+            T::InterfaceImpl => &[Table(T::TypeDef), Coded(C::TypeDefOrRef)],
+            T::MemberRef => &[Coded(C::MemberRefParent), Str, Blob],
+            T::Constant => &[U16, Coded(C::HasConstant), Blob],
+            T::CustomAttribute => &[Coded(C::HasCustomAttribute),
+                Coded(C::CustomAttributeType), Blob],
+            T::FieldMarshal => &[Coded(C::HasFieldMarshal), Blob],
+            T::DeclSecurity => &[U16, Coded(C::HasDeclSecurity), Blob],
+            T::ClassLayout => &[U16, U32, Table(T::TypeDef)],
+            T::FieldLayout => &[U32, Table(T::Field)],
+            T::StandAloneSig => &[Blob],
+            T::EventMap => &[Table(T::TypeDef), Table(T::Event)],
+            T::EventPtr => &[Table(T::Event)],
+            T::Event => &[U16, Str, Coded(C::TypeDefOrRef)],
+            T::PropertyMap => &[Table(T::TypeDef), Table(T::Property)],
+            T::PropertyPtr => &[Table(T::Property)],
+            T::Property => &[U16, Str, Blob],
+            T::MethodSemantics => &[U16, Table(T::MethodDef),
+                Coded(C::HasSemantics)],
+            T::MethodImpl => &[Table(T::TypeDef), Coded(C::MethodDefOrRef),
+                Coded(C::MethodDefOrRef)],
+            T::ModuleRef => &[Str],
+            T::TypeSpec => &[Blob],
+            T::ImplMap => &[U16, Coded(C::MemberForwarded), Str,
+                Table(T::ModuleRef)],
+            T::FieldRva => &[U32, Table(T::Field)],
+            T::EncLog => &[U32, U32],
+            T::EncMap => &[U32],
+            T::Assembly => &[U32, U16, U16, U16, U16, U32, Blob, Str, Str],
+            T::AssemblyProcessor => &[U32],
+            T::AssemblyOs => &[U32, U32, U32],
+            T::AssemblyRef => &[U16, U16, U16, U16, U32, Blob, Str, Str, Blob],
+            T::AssemblyRefProcessor => &[U32, Table(T::AssemblyRef)],
+            T::AssemblyRefOs => &[U32, U32, U32, Table(T::AssemblyRef)],
+            T::File => &[U32, Str, Blob],
+            T::ExportedType => &[U32, U32, Str, Str, Coded(C::Implementation)],
+            T::ManifestResource => &[U32, U32, Str, Coded(C::Implementation)],
+            T::NestedClass => &[Table(T::TypeDef), Table(T::TypeDef)],
+            T::GenericParam => &[U16, U16, Coded(C::TypeOrMethodDef), Str],
+            T::MethodSpec => &[Coded(C::MethodDefOrRef), Blob],
+            T::GenericParamConstraint => &[Table(T::GenericParam),
+                Coded(C::TypeDefOrRef)]
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CodedIndex {
+    TypeDefOrRef,
+    HasConstant,
+    HasCustomAttribute,
+    HasFieldMarshal,
+    HasDeclSecurity,
+    MemberRefParent,
+    HasSemantics,
+    MethodDefOrRef,
+    MemberForwarded,
+    Implementation,
+    CustomAttributeType,
+    ResolutionScope,
+    TypeOrMethodDef
+}
+
+impl CodedIndex {
+    /* tables indexed by each tag value; gaps are unused tags */
+    pub fn get_tables(&self) -> &'static [Option<MetadataTable>] {
+        use self::MetadataTable as T;
+
+        match self {
+            CodedIndex::TypeDefOrRef =>
+                &[Some(T::TypeDef), Some(T::TypeRef), Some(T::TypeSpec)],
+            CodedIndex::HasConstant =>
+                &[Some(T::Field), Some(T::Param), Some(T::Property)],
+            CodedIndex::HasCustomAttribute => &[Some(T::MethodDef),
+                Some(T::Field), Some(T::TypeRef), Some(T::TypeDef),
+                Some(T::Param), Some(T::InterfaceImpl), Some(T::MemberRef),
+                Some(T::Module), Some(T::DeclSecurity), Some(T::Property),
+                Some(T::Event), Some(T::StandAloneSig), Some(T::ModuleRef),
+                Some(T::TypeSpec), Some(T::Assembly), Some(T::AssemblyRef),
+                Some(T::File), Some(T::ExportedType),
+                Some(T::ManifestResource), Some(T::GenericParam),
+                Some(T::GenericParamConstraint), Some(T::MethodSpec)],
+            CodedIndex::HasFieldMarshal => &[Some(T::Field), Some(T::Param)],
+            CodedIndex::HasDeclSecurity =>
+                &[Some(T::TypeDef), Some(T::MethodDef), Some(T::Assembly)],
+            CodedIndex::MemberRefParent => &[Some(T::TypeDef),
+                Some(T::TypeRef), Some(T::ModuleRef), Some(T::MethodDef),
+                Some(T::TypeSpec)],
+            CodedIndex::HasSemantics => &[Some(T::Event), Some(T::Property)],
+            CodedIndex::MethodDefOrRef =>
+                &[Some(T::MethodDef), Some(T::MemberRef)],
+            CodedIndex::MemberForwarded =>
+                &[Some(T::Field), Some(T::MethodDef)],
+            CodedIndex::Implementation =>
+                &[Some(T::File), Some(T::AssemblyRef), Some(T::ExportedType)],
+            CodedIndex::CustomAttributeType => &[None, None,
+                Some(T::MethodDef), Some(T::MemberRef), None],
+            CodedIndex::ResolutionScope => &[Some(T::Module), Some(T::TypeRef),
+                Some(T::ModuleRef), Some(T::AssemblyRef)],
+            CodedIndex::TypeOrMethodDef =>
+                &[Some(T::TypeDef), Some(T::MethodDef)]
+        }
+    }
+
+    pub fn get_tag_bits(&self) -> u32 {
+        usize::BITS - (self.get_tables().len() - 1).leading_zeros()
+    }
+
+    /* splits a coded index into the table it refers to and a 1-based row,
+     * where row 0 is a null reference */
+    pub fn decode(&self, value: u32) -> Option<(MetadataTable, u32)> {
+        let tag: usize = (value & ((1 << self.get_tag_bits()) - 1)) as usize;
+
+        self.get_tables().get(tag).copied().flatten()
+            .map(|t| (t, value >> self.get_tag_bits()))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    U16,
+    U32,
+    Str,
+    Guid,
+    Blob,
+    Table(MetadataTable),
+    Coded(CodedIndex)
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamHeader {
+    offset: u32,    /* offset from the metadata root */
+    size: u32,      /* size in bytes */
+    name: String    /* e.g. #Strings */
+}
+
+impl StreamHeader {
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/* a reference into another table, rendered as a metadata token */
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataToken {
+    table: MetadataTable,
+    row: u32
+}
+
+impl MetadataToken {
+    pub fn get_table(&self) -> MetadataTable {
+        self.table
+    }
+
+    pub fn get_row(&self) -> u32 {
+        self.row
+    }
+
+    pub fn get_value(&self) -> u32 {
+        ((MetadataTable::to_value(self.table) as u32) << 24) | self.row
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDef {
+    flags: u32,                     /* TypeAttributes */
+    name: String,
+    namespace: String,
+    extends: Option<MetadataToken>, /* base type, if any */
+    field_list: u32,                /* first row in the Field table */
+    method_list: u32                /* first row in the MethodDef table */
+}
+
+impl TypeDef {
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn get_full_name(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.namespace, self.name)
+        }
+    }
+
+    pub fn get_extends(&self) -> Option<MetadataToken> {
+        self.extends
+    }
+
+    pub fn get_field_list(&self) -> u32 {
+        self.field_list
+    }
+
+    pub fn get_method_list(&self) -> u32 {
+        self.method_list
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MethodDef {
+    rva: u32,           /* RVA of the method body, or zero */
+    impl_flags: u16,    /* MethodImplAttributes */
+    flags: u16,         /* MethodAttributes */
+    name: String,
+    signature: Vec<u8>, /* raw signature blob */
+    param_list: u32     /* first row in the Param table */
+}
+
+impl MethodDef {
+    pub fn get_rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn get_impl_flags(&self) -> u16 {
+        self.impl_flags
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn get_param_list(&self) -> u32 {
+        self.param_list
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberRef {
+    class: Option<MetadataToken>,   /* declaring type or module */
+    name: String,
+    signature: Vec<u8>              /* raw signature blob */
+}
+
+impl MemberRef {
+    pub fn get_class(&self) -> Option<MetadataToken> {
+        self.class
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /* field signatures start with 0x06; everything else is a method */
+    pub fn is_field(&self) -> bool {
+        self.signature.first() == Some(&0x06)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Assembly {
+    hash_alg_id: u32,       /* AssemblyHashAlgorithm */
+    version: [u16; 4],      /* major, minor, build, revision */
+    flags: u32,             /* AssemblyFlags */
+    public_key: Vec<u8>,
+    name: String,
+    culture: String
+}
+
+impl Assembly {
+    pub fn get_hash_alg_id(&self) -> u32 {
+        self.hash_alg_id
+    }
+
+    pub fn get_version(&self) -> [u16; 4] {
+        self.version
+    }
+
+    pub fn get_version_as_string(&self) -> String {
+        format!("{}.{}.{}.{}", self.version[0], self.version[1],
+            self.version[2], self.version[3])
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_culture(&self) -> &str {
+        &self.culture
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssemblyRef {
+    version: [u16; 4],          /* major, minor, build, revision */
+    flags: u32,                 /* AssemblyFlags */
+    public_key_or_token: Vec<u8>,
+    name: String,
+    culture: String,
+    hash_value: Vec<u8>
+}
+
+impl AssemblyRef {
+    pub fn get_version(&self) -> [u16; 4] {
+        self.version
+    }
+
+    pub fn get_version_as_string(&self) -> String {
+        format!("{}.{}.{}.{}", self.version[0], self.version[1],
+            self.version[2], self.version[3])
+    }
+
+    pub fn get_flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn get_public_key_or_token(&self) -> &[u8] {
+        &self.public_key_or_token
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_culture(&self) -> &str {
+        &self.culture
+    }
+
+    pub fn get_hash_value(&self) -> &[u8] {
+        &self.hash_value
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Metadata<'a> {
+    major_version: u16,             /* metadata root major version */
+    minor_version: u16,             /* metadata root minor version */
+    version: String,                /* runtime version, e.g. v4.0.30319 */
+    flags: u16,                     /* (reserved) */
+    streams: Vec<StreamHeader>,
+    strings: &'a[u8],               /* #Strings heap */
+    user_strings: &'a[u8],          /* #US heap */
+    guids: &'a[u8],                 /* #GUID heap */
+    blobs: &'a[u8],                 /* #Blob heap */
+    heap_sizes: u8,                 /* wide heap index flags */
+    row_counts: [u32; METADATA_TABLE_COUNT],
+    table_offsets: [usize; METADATA_TABLE_COUNT],
+    tables: &'a[u8]                 /* #~ stream */
+}
+
+impl<'a> Metadata<'a> {
+    pub fn get_major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn get_minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn get_streams(&self) -> &[StreamHeader] {
+        &self.streams
+    }
+
+    pub fn get_row_count(&self, table: MetadataTable) -> u32 {
+        self.row_counts[MetadataTable::to_value(table)]
+    }
+
+    pub fn get_string(&self, index: u32) -> Result<String, ButylError> {
+        read_cstring(self.strings, index as usize)
+    }
+
+    pub fn get_guid(&self, index: u32) -> Result<Option<[u8; 16]>, ButylError> {
+        if index == 0 {
+            return Ok(None);
+        }
+
+        let mut guid: [u8; 16] = [0; 16];
+        guid.copy_from_slice(read_bytes(self.guids,
+            (index as usize - 1) * 16, 16)?);
+
+        Ok(Some(guid))
+    }
+
+    pub fn get_blob(&self, index: u32) -> Result<&'a[u8], ButylError> {
+        let (length, header): (usize, usize) =
+            read_compressed_u32(self.blobs, index as usize)?;
+
+        read_bytes(self.blobs, index as usize + header, length)
+    }
+
+    /* every string literal in the #US heap, in heap order */
+    pub fn get_user_strings(&self) -> Result<Vec<String>, ButylError> {
+        let mut strings: Vec<String> = Vec::new();
+        let mut offset: usize = 1;
+
+        while offset < self.user_strings.len() {
+            let (length, header): (usize, usize) =
+                match read_compressed_u32(self.user_strings, offset) {
+                    Ok(t) => t,
+                    Err(_e) => break
+                };
+
+            if length == 0 {
+                offset += header;
+                continue;
+            }
+
+            /* the trailing byte flags strings needing special handling */
+            let units: Vec<u16> = read_bytes(self.user_strings,
+                offset + header, length - 1)?
+                .chunks_exact(2)
+                .map(|t| ((t[1] as u16) << 8) | t[0] as u16)
+                .collect();
+
+            strings.push(String::from_utf16_lossy(&units));
+            offset += header + length;
+        }
+
+        Ok(strings)
+    }
+
+    pub fn get_type_defs(&self) -> Result<Vec<TypeDef>, ButylError> {
+        self.read_rows(MetadataTable::TypeDef)?
+            .iter()
+            .map(|t| Ok(TypeDef {
+                flags: t[0],
+                name: self.get_string(t[1])?,
+                namespace: self.get_string(t[2])?,
+                extends: self.decode_coded(CodedIndex::TypeDefOrRef, t[3]),
+                field_list: t[4],
+                method_list: t[5]
+            }))
+            .collect()
+    }
+
+    pub fn get_method_defs(&self) -> Result<Vec<MethodDef>, ButylError> {
+        self.read_rows(MetadataTable::MethodDef)?
+            .iter()
+            .map(|t| Ok(MethodDef {
+                rva: t[0],
+                impl_flags: t[1] as u16,
+                flags: t[2] as u16,
+                name: self.get_string(t[3])?,
+                signature: self.get_blob(t[4])?.to_vec(),
+                param_list: t[5]
+            }))
+            .collect()
+    }
+
+    pub fn get_member_refs(&self) -> Result<Vec<MemberRef>, ButylError> {
+        self.read_rows(MetadataTable::MemberRef)?
+            .iter()
+            .map(|t| Ok(MemberRef {
+                class: self.decode_coded(CodedIndex::MemberRefParent, t[0]),
+                name: self.get_string(t[1])?,
+                signature: self.get_blob(t[2])?.to_vec()
+            }))
+            .collect()
+    }
+
+    pub fn get_assembly(&self) -> Result<Option<Assembly>, ButylError> {
+        Ok(match self.read_rows(MetadataTable::Assembly)?.first() {
+            Some(t) => Some(Assembly {
+                hash_alg_id: t[0],
+                version: [t[1] as u16, t[2] as u16, t[3] as u16, t[4] as u16],
+                flags: t[5],
+                public_key: self.get_blob(t[6])?.to_vec(),
+                name: self.get_string(t[7])?,
+                culture: self.get_string(t[8])?
+            }),
+            None => None
+        })
+    }
+
+    pub fn get_assembly_refs(&self) -> Result<Vec<AssemblyRef>, ButylError> {
+        self.read_rows(MetadataTable::AssemblyRef)?
+            .iter()
+            .map(|t| Ok(AssemblyRef {
+                version: [t[0] as u16, t[1] as u16, t[2] as u16, t[3] as u16],
+                flags: t[4],
+                public_key_or_token: self.get_blob(t[5])?.to_vec(),
+                name: self.get_string(t[6])?,
+                culture: self.get_string(t[7])?,
+                hash_value: self.get_blob(t[8])?.to_vec()
+            }))
+            .collect()
+    }
+
+    /* resolves a TypeDef or TypeRef token to a dotted type name */
+    pub fn get_type_name(&self, token: &MetadataToken) ->
+        Result<String, ButylError> {
+        let columns: Vec<u32> = match token.get_table() {
+            MetadataTable::TypeDef | MetadataTable::TypeRef if
+                token.get_row() > 0 =>
+                self.read_row(token.get_table(), token.get_row() - 1)?,
+            _ => return Ok(format!("{:#010x}", token.get_value()))
+        };
+
+        /* both tables keep the name and namespace in columns 1 and 2 */
+        let name: String = self.get_string(columns[1])?;
+        let namespace: String = self.get_string(columns[2])?;
+
+        Ok(if namespace.is_empty() {
+            name
+        } else {
+            format!("{}.{}", namespace, name)
+        })
+    }
+
+    fn decode_coded(&self, coded: CodedIndex, value: u32) ->
+        Option<MetadataToken> {
+        coded.decode(value)
+            .filter(|t| t.1 != 0)
+            .map(|t| MetadataToken {
+                table: t.0,
+                row: t.1
+            })
+    }
+
+    fn get_column_size(&self, column: Column) -> usize {
+        let wide = |flag: u8| if self.heap_sizes & flag != 0 { 4 } else { 2 };
+
+        match column {
+            Column::U16 => 2,
+            Column::U32 => 4,
+            Column::Str => wide(HEAP_SIZE_STRINGS),
+            Column::Guid => wide(HEAP_SIZE_GUID),
+            Column::Blob => wide(HEAP_SIZE_BLOB),
+            Column::Table(t) =>
+                if self.get_row_count(t) > 0xffff { 4 } else { 2 },
+            Column::Coded(t) => {
+                let limit: u32 = 1 << (16 - t.get_tag_bits());
+                let largest: u32 = t.get_tables().iter()
+                    .flatten()
+                    .map(|t| self.get_row_count(*t))
+                    .max()
+                    .unwrap_or(0);
+
+                if largest < limit { 2 } else { 4 }
+            }
+        }
+    }
+
+    fn get_row_size(&self, table: MetadataTable) -> usize {
+        table.get_columns().iter()
+            .map(|t| self.get_column_size(*t))
+            .sum()
+    }
+
+    fn read_row(&self, table: MetadataTable, row: u32) ->
+        Result<Vec<u32>, ButylError> {
+        if row >= self.get_row_count(table) {
+            return Err(ButylError::NotFoundError);
+        }
+
+        let mut offset: usize =
+            self.table_offsets[MetadataTable::to_value(table)] +
+            row as usize * self.get_row_size(table);
+        let mut values: Vec<u32> = Vec::new();
+
+        for column in table.get_columns() {
+            let size: usize = self.get_column_size(*column);
+
+            values.push(if size == 2 {
+                read_le_u16(self.tables, offset)? as u32
+            } else {
+                read_le_u32(self.tables, offset)?
+            });
+            offset += size;
+        }
+
+        Ok(values)
+    }
+
+    fn read_rows(&self, table: MetadataTable) ->
+        Result<Vec<Vec<u32>>, ButylError> {
+        (0..self.get_row_count(table))
+            .map(|t| self.read_row(table, t))
+            .collect()
+    }
+
+    pub fn from_le_bytes(bytes: &'a[u8]) -> Result<Metadata<'a>, ButylError> {
+        if read_le_u32(bytes, 0)? != METADATA_SIGNATURE {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let version_len: usize = read_le_u32(bytes, 12)? as usize;
+        let version: String = String::from_utf8_lossy(
+            read_bytes(bytes, 16, version_len)?)
+            .trim_end_matches('\0')
+            .to_string();
+        let mut offset: usize = 16 + version_len;
+
+        let flags: u16 = read_le_u16(bytes, offset)?;
+        let stream_count: u16 = read_le_u16(bytes, offset + 2)?;
+        let mut streams: Vec<StreamHeader> = Vec::new();
+        offset += 4;
+
+        for _ in 0..stream_count {
+            let name: String = read_cstring(bytes, offset + 8)?;
+
+            streams.push(StreamHeader {
+                offset: read_le_u32(bytes, offset)?,
+                size: read_le_u32(bytes, offset + 4)?,
+                name: name.clone()
+            });
+
+            /* names are NUL-terminated and padded to a 4-byte boundary */
+            offset += 8 + (name.len() + 1).next_multiple_of(4);
+        }
+
+        let stream = |name: &str| -> Result<&'a[u8], ButylError> {
+            match streams.iter().find(|t| t.get_name() == name) {
+                Some(t) => read_bytes(bytes, t.get_offset() as usize,
+                    t.get_size() as usize),
+                None => Ok(&[])
+            }
+        };
+
+        /* "#-" is the uncompressed form used by edit-and-continue */
+        let tables: &[u8] = match stream("#~")? {
+            [] => stream("#-")?,
+            t => t
+        };
+
+        let mut metadata: Metadata = Metadata {
+            major_version: read_le_u16(bytes, 4)?,
+            minor_version: read_le_u16(bytes, 6)?,
+            version,
+            flags,
+            strings: stream("#Strings")?,
+            user_strings: stream("#US")?,
+            guids: stream("#GUID")?,
+            blobs: stream("#Blob")?,
+            heap_sizes: read_u8(tables, 6)?,
+            row_counts: [0; METADATA_TABLE_COUNT],
+            table_offsets: [0; METADATA_TABLE_COUNT],
+            tables,
+            streams: streams.clone()
+        };
+
+        let valid: u64 = read_le_u64(tables, 8)?;
+        let mut cursor: usize = 24;
+
+        for table in 0..METADATA_TABLE_COUNT {
+            if valid & (1 << table) != 0 {
+                metadata.row_counts[table] = read_le_u32(tables, cursor)?;
+                cursor += 4;
+            }
+        }
+
+        if metadata.heap_sizes & HEAP_EXTRA_DATA != 0 {
+            cursor += 4;
+        }
+
+        for (index, table) in METADATA_TABLES.iter().enumerate() {
+            metadata.table_offsets[index] = cursor;
+            cursor += metadata.get_row_size(*table) *
+                metadata.row_counts[index] as usize;
+        }
+
+        /* tables past the ones we know have no defined layout */
+        if valid >> METADATA_TABLES.len() != 0 {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        Ok(metadata)
+    }
+}
+
+/* ECMA-335 II.23.2 compressed unsigned integer; returns the value and the
+ * number of bytes it occupied */
+fn read_compressed_u32(bytes: &[u8], offset: usize) ->
+    Result<(usize, usize), ButylError> {
+    let first: u8 = read_u8(bytes, offset)?;
+
+    if first & 0x80 == 0 {
+        Ok((first as usize, 1))
+    } else if first & 0xc0 == 0x80 {
+        Ok(((((first & 0x3f) as usize) << 8) |
+            read_u8(bytes, offset + 1)? as usize, 2))
+    } else if first & 0xe0 == 0xc0 {
+        Ok(((((first & 0x1f) as usize) << 24) |
+            (read_u8(bytes, offset + 1)? as usize) << 16 |
+            (read_u8(bytes, offset + 2)? as usize) << 8 |
+            read_u8(bytes, offset + 3)? as usize, 4))
+    } else {
+        Err(ButylError::MalformedDataError)
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_cor_header(&self) -> Result<Option<CorHeader>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::ComDescriptor) {
+                Some(t) => t,
+                None => return Ok(None)
+            };
+
+        Ok(Some(CorHeader::from_le_bytes(self.read_rva(
+            directory.get_virtual_address(), COR20_HEADER_LEN)?)?))
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.get_data_directory(DataDirectoryType::ComDescriptor).is_some()
+    }
+
+    pub fn get_metadata(&self) -> Result<Option<Metadata<'a>>, ButylError> {
+        let cor_header: CorHeader = match self.get_cor_header()? {
+            Some(t) => t,
+            None => return Ok(None)
+        };
+
+        let directory: DataDirectory = cor_header.get_metadata();
+
+        Ok(Some(Metadata::from_le_bytes(self.read_rva(
+            directory.get_virtual_address(), directory.get_size() as usize)?)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* a #~ stream holding the given row counts, followed by the table data */
+    fn build_tables(heap_sizes: u8, rows: &[(MetadataTable, u32)],
+        body: &[u8]) -> Vec<u8> {
+        let mut rows: Vec<(MetadataTable, u32)> = rows.to_vec();
+        rows.sort_by_key(|t| MetadataTable::to_value(t.0));
+
+        let valid: u64 = rows.iter()
+            .fold(0, |a, t| a | 1 << MetadataTable::to_value(t.0));
+        let mut data: Vec<u8> = vec![0, 0, 0, 0, 2, 0, heap_sizes, 1];
+
+        data.extend_from_slice(&valid.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+
+        for (_, count) in &rows {
+            data.extend_from_slice(&count.to_le_bytes());
+        }
+
+        if heap_sizes & HEAP_EXTRA_DATA != 0 {
+            data.extend_from_slice(&[0xff; 4]);
+        }
+
+        data.extend_from_slice(body);
+        data
+    }
+
+    /* a metadata root with a #~ and a #Strings stream */
+    fn build_root(tables: &[u8], strings: &[u8]) -> Vec<u8> {
+        let version: &[u8] = b"v4.0.30319\0\0";
+        let tables_offset: usize = 16 + version.len() + 4 + 12 + 20;
+        let strings_offset: usize = tables_offset + tables.len();
+        let mut data: Vec<u8> = Vec::new();
+
+        data.extend_from_slice(&METADATA_SIGNATURE.to_le_bytes());
+        data.extend_from_slice(&[1, 0, 1, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&(version.len() as u32).to_le_bytes());
+        data.extend_from_slice(version);
+        data.extend_from_slice(&[0, 0, 2, 0]);
+
+        data.extend_from_slice(&(tables_offset as u32).to_le_bytes());
+        data.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+        data.extend_from_slice(b"#~\0\0");
+        data.extend_from_slice(&(strings_offset as u32).to_le_bytes());
+        data.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+        data.extend_from_slice(b"#Strings\0\0\0\0");
+
+        data.extend_from_slice(tables);
+        data.extend_from_slice(strings);
+        data
+    }
+
+    #[test]
+    fn tag_bits_cover_every_table() {
+        assert_eq!(CodedIndex::HasFieldMarshal.get_tag_bits(), 1);
+        assert_eq!(CodedIndex::TypeDefOrRef.get_tag_bits(), 2);
+        assert_eq!(CodedIndex::ResolutionScope.get_tag_bits(), 2);
+        assert_eq!(CodedIndex::MemberRefParent.get_tag_bits(), 3);
+        assert_eq!(CodedIndex::CustomAttributeType.get_tag_bits(), 3);
+        assert_eq!(CodedIndex::HasCustomAttribute.get_tag_bits(), 5);
+    }
+
+    #[test]
+    fn coded_index_widens_at_the_tag_limit() {
+        let column: Column = Column::Coded(CodedIndex::TypeDefOrRef);
+
+        for (count, size) in [(0x3fff, 2), (0x4000, 4)] {
+            let tables: Vec<u8> =
+                build_tables(0, &[(MetadataTable::TypeRef, count)], &[]);
+            let root: Vec<u8> = build_root(&tables, &[0]);
+            let metadata: Metadata = Metadata::from_le_bytes(&root).unwrap();
+
+            assert_eq!(metadata.get_column_size(column), size);
+        }
+    }
+
+    #[test]
+    fn extra_data_is_skipped_before_the_tables() {
+        /* Generation, Name, Mvid, EncId, EncBaseId */
+        let module: &[u8] = &[0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let strings: &[u8] = b"\0Test\0";
+
+        for heap_sizes in [0, HEAP_EXTRA_DATA] {
+            let tables: Vec<u8> = build_tables(heap_sizes,
+                &[(MetadataTable::Module, 1)], module);
+            let root: Vec<u8> = build_root(&tables, strings);
+            let metadata: Metadata = Metadata::from_le_bytes(&root).unwrap();
+            let row: Vec<u32> =
+                metadata.read_row(MetadataTable::Module, 0).unwrap();
+
+            assert_eq!(row, [0, 1, 0, 0, 0]);
+            assert_eq!(metadata.get_string(row[1]).unwrap(), "Test");
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const DEBUG_DIRECTORY_LEN: usize = 28;
+pub const CODEVIEW_SIGNATURE_RSDS: u32 = 0x5344_5352;     /* "RSDS" */
+pub const CODEVIEW_SIGNATURE_NB10: u32 = 0x3031_424e;     /* "NB10" */
+pub const VC_FEATURE_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugType {
+    Unknown,
+    Coff,
+    CodeView,
+    Fpo,
+    Misc,
+    Exception,
+    Fixup,
+    OmapToSrc,
+    OmapFromSrc,
+    Borland,
+    Reserved10,
+    Clsid,
+    VcFeature,
+    Pogo,
+    Iltcg,
+    Mpx,
+    Repro,
+    EmbeddedPortablePdb,
+    PdbChecksum,
+    ExDllCharacteristics,
+    Other(u32)
+}
+
+impl DebugType {
+    pub fn to_value(debug_type: DebugType) -> u32 {
+        match debug_type {
+            DebugType::Unknown => 0,
+            DebugType::Coff => 1,
+            DebugType::CodeView => 2,
+            DebugType::Fpo => 3,
+            DebugType::Misc => 4,
+            DebugType::Exception => 5,
+            DebugType::Fixup => 6,
+            DebugType::OmapToSrc => 7,
+            DebugType::OmapFromSrc => 8,
+            DebugType::Borland => 9,
+            DebugType::Reserved10 => 10,
+            DebugType::Clsid => 11,
+            DebugType::VcFeature => 12,
+            DebugType::Pogo => 13,
+            DebugType::Iltcg => 14,
+            DebugType::Mpx => 15,
+            DebugType::Repro => 16,
+            DebugType::EmbeddedPortablePdb => 17,
+            DebugType::PdbChecksum => 19,
+            DebugType::ExDllCharacteristics => 20,
+            DebugType::Other(t) => t
+        }
+    }
+
+    pub fn from_value(value: u32) -> DebugType {
+        match value {
+            0 => DebugType::Unknown,
+            1 => DebugType::Coff,
+            2 => DebugType::CodeView,
+            3 => DebugType::Fpo,
+            4 => DebugType::Misc,
+            5 => DebugType::Exception,
+            6 => DebugType::Fixup,
+            7 => DebugType::OmapToSrc,
+            8 => DebugType::OmapFromSrc,
+            9 => DebugType::Borland,
+            10 => DebugType::Reserved10,
+            11 => DebugType::Clsid,
+            12 => DebugType::VcFeature,
+            13 => DebugType::Pogo,
+            14 => DebugType::Iltcg,
+            15 => DebugType::Mpx,
+            16 => DebugType::Repro,
+            17 => DebugType::EmbeddedPortablePdb,
+            19 => DebugType::PdbChecksum,
+            20 => DebugType::ExDllCharacteristics,
+            t => DebugType::Other(t)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DebugDirectory {
+    characteristics: u32,       /* (reserved) */
+    time_date_stamp: u32,       /* creation time of the debug data */
+    major_version: u16,         /* debug data format major version */
+    minor_version: u16,         /* debug data format minor version */
+    debug_type: u32,            /* format of the debug data */
+    size_of_data: u32,          /* size of the debug data */
+    address_of_raw_data: u32,   /* RVA of the debug data, if mapped */
+    pointer_to_raw_data: u32    /* file offset of the debug data */
+}
+
+impl DebugDirectory {
+    pub fn get_characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn get_minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn get_debug_type(&self) -> u32 {
+        self.debug_type
+    }
+
+    pub fn get_debug_type_as_enum(&self) -> DebugType {
+        DebugType::from_value(self.debug_type)
+    }
+
+    pub fn get_size_of_data(&self) -> u32 {
+        self.size_of_data
+    }
+
+    pub fn get_address_of_raw_data(&self) -> u32 {
+        self.address_of_raw_data
+    }
+
+    pub fn get_pointer_to_raw_data(&self) -> u32 {
+        self.pointer_to_raw_data
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<DebugDirectory, ButylError> {
+        Ok(DebugDirectory {
+            characteristics: read_le_u32(bytes, 0)?,
+            time_date_stamp: read_le_u32(bytes, 4)?,
+            major_version: read_le_u16(bytes, 8)?,
+            minor_version: read_le_u16(bytes, 10)?,
+            debug_type: read_le_u32(bytes, 12)?,
+            size_of_data: read_le_u32(bytes, 16)?,
+            address_of_raw_data: read_le_u32(bytes, 20)?,
+            pointer_to_raw_data: read_le_u32(bytes, 24)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeViewInfo {
+    signature: u32,     /* RSDS or NB10 */
+    guid: [u8; 16],     /* PDB GUID (RSDS only) */
+    time_date_stamp: u32,   /* PDB signature (NB10 only) */
+    age: u32,           /* PDB age */
+    path: String        /* PDB path as recorded by the linker */
+}
+
+impl CodeViewInfo {
+    pub fn get_signature(&self) -> u32 {
+        self.signature
+    }
+
+    pub fn get_guid(&self) -> [u8; 16] {
+        self.guid
+    }
+
+    /* the GUID in its registry form, e.g. {0A1B2C3D-...} */
+    pub fn get_guid_as_string(&self) -> String {
+        let g: &[u8; 16] = &self.guid;
+
+        format!("{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{}}}",
+            u32::from_le_bytes([g[0], g[1], g[2], g[3]]),
+            u16::from_le_bytes([g[4], g[5]]),
+            u16::from_le_bytes([g[6], g[7]]),
+            g[8], g[9],
+            g[10..].iter().map(|t| format!("{:02X}", t)).collect::<String>())
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_age(&self) -> u32 {
+        self.age
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /* the `<pdb>/<id>/<pdb>` path used by symbol servers */
+    pub fn get_symbol_server_path(&self) -> String {
+        let name: &str = self.path.rsplit(['\\', '/']).next()
+            .unwrap_or(&self.path);
+
+        let id: String = if self.signature == CODEVIEW_SIGNATURE_RSDS {
+            format!("{}{:X}", self.get_guid_as_string()
+                .replace(['{', '}', '-'], ""), self.age)
+        } else {
+            format!("{:08X}{:X}", self.time_date_stamp, self.age)
+        };
+
+        format!("{}/{}/{}", name, id, name)
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<CodeViewInfo, ButylError> {
+        let signature: u32 = read_le_u32(bytes, 0)?;
+
+        match signature {
+            CODEVIEW_SIGNATURE_RSDS => {
+                let mut guid: [u8; 16] = [0; 16];
+                guid.copy_from_slice(read_bytes(bytes, 4, 16)?);
+
+                Ok(CodeViewInfo {
+                    signature,
+                    guid,
+                    time_date_stamp: 0,
+                    age: read_le_u32(bytes, 20)?,
+                    path: read_cstring(bytes, 24)?
+                })
+            },
+            CODEVIEW_SIGNATURE_NB10 => Ok(CodeViewInfo {
+                signature,
+                guid: [0; 16],
+                time_date_stamp: read_le_u32(bytes, 8)?,
+                age: read_le_u32(bytes, 12)?,
+                path: read_cstring(bytes, 16)?
+            }),
+            _ => Err(ButylError::InvalidMagicError)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PogoEntry {
+    rva: u32,       /* start of the contribution */
+    size: u32,      /* size of the contribution */
+    name: String    /* section name, e.g. .text$mn */
+}
+
+impl PogoEntry {
+    pub fn get_rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PogoInfo {
+    signature: u32,     /* "PGU\0", "PGI\0", "LTCG" etc. */
+    entries: Vec<PogoEntry>
+}
+
+impl PogoInfo {
+    pub fn get_signature(&self) -> u32 {
+        self.signature
+    }
+
+    pub fn get_entries(&self) -> &[PogoEntry] {
+        &self.entries
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<PogoInfo, ButylError> {
+        let signature: u32 = read_le_u32(bytes, 0)?;
+        let mut entries: Vec<PogoEntry> = Vec::new();
+        let mut offset: usize = 4;
+
+        while offset + 8 < bytes.len() {
+            let name: String = read_cstring(bytes, offset + 8)?;
+
+            /* names are NUL-terminated and padded to a 4-byte boundary */
+            let next: usize = (offset + 8 + name.len() + 1).next_multiple_of(4);
+
+            entries.push(PogoEntry {
+                rva: read_le_u32(bytes, offset)?,
+                size: read_le_u32(bytes, offset + 4)?,
+                name
+            });
+            offset = next;
+        }
+
+        Ok(PogoInfo {
+            signature,
+            entries
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VcFeatureInfo {
+    pre_vc11: u32,      /* objects built before VC++ 11 */
+    c_cpp: u32,         /* C/C++ objects */
+    gs: u32,            /* objects built with /GS */
+    sdl: u32,           /* objects built with /sdl */
+    guard_n: u32        /* objects built with /guardN */
+}
+
+impl VcFeatureInfo {
+    pub fn get_pre_vc11(&self) -> u32 {
+        self.pre_vc11
+    }
+
+    pub fn get_c_cpp(&self) -> u32 {
+        self.c_cpp
+    }
+
+    pub fn get_gs(&self) -> u32 {
+        self.gs
+    }
+
+    pub fn get_sdl(&self) -> u32 {
+        self.sdl
+    }
+
+    pub fn get_guard_n(&self) -> u32 {
+        self.guard_n
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<VcFeatureInfo, ButylError> {
+        Ok(VcFeatureInfo {
+            pre_vc11: read_le_u32(bytes, 0)?,
+            c_cpp: read_le_u32(bytes, 4)?,
+            gs: read_le_u32(bytes, 8)?,
+            sdl: read_le_u32(bytes, 12)?,
+            guard_n: read_le_u32(bytes, 16)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DebugInfo {
+    CodeView(CodeViewInfo),
+    Pogo(PogoInfo),
+    Repro(Vec<u8>),         /* build hash; empty for a plain /Brepro */
+    VcFeature(VcFeatureInfo)
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_debug_directories(&self) ->
+        Result<Vec<DebugDirectory>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Debug) {
+                Some(t) => t,
+                None => return Ok(Vec::new())
+            };
+
+        self.read_rva(directory.get_virtual_address(),
+            directory.get_size() as usize)?
+            .chunks_exact(DEBUG_DIRECTORY_LEN)
+            .map(DebugDirectory::from_le_bytes)
+            .collect()
+    }
+
+    pub fn get_debug_data(&self, entry: &DebugDirectory) ->
+        Result<&'a[u8], ButylError> {
+        read_bytes(self.get_data(), entry.get_pointer_to_raw_data() as usize,
+            entry.get_size_of_data() as usize)
+    }
+
+    /* decodes the payload of the entry types we understand */
+    pub fn get_debug_info(&self, entry: &DebugDirectory) ->
+        Result<Option<DebugInfo>, ButylError> {
+        let data: &[u8] = self.get_debug_data(entry)?;
+
+        Ok(match entry.get_debug_type_as_enum() {
+            DebugType::CodeView =>
+                Some(DebugInfo::CodeView(CodeViewInfo::from_le_bytes(data)?)),
+            DebugType::Pogo =>
+                Some(DebugInfo::Pogo(PogoInfo::from_le_bytes(data)?)),
+            DebugType::Repro => {
+                let hash: Vec<u8> = if data.len() >= 4 {
+                    let len: usize = read_le_u32(data, 0)? as usize;
+                    read_bytes(data, 4, len)?.to_vec()
+                } else {
+                    Vec::new()
+                };
+
+                Some(DebugInfo::Repro(hash))
+            },
+            DebugType::VcFeature =>
+                Some(DebugInfo::VcFeature(VcFeatureInfo::from_le_bytes(data)?)),
+            _ => None
+        })
+    }
+
+    pub fn get_codeview_info(&self) ->
+        Result<Option<CodeViewInfo>, ButylError> {
+        for entry in self.get_debug_directories()? {
+            if let Some(DebugInfo::CodeView(t)) = self.get_debug_info(&entry)? {
+                return Ok(Some(t));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
@@ -0,0 +1,708 @@
+use std::result::Result;
+
+use crate::coff::CoffHeaderMachineType;
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const AMD64_RUNTIME_FUNCTION_LEN: usize = 12;
+pub const ARM64_RUNTIME_FUNCTION_LEN: usize = 8;
+pub const UNW_FLAG_EHANDLER: u8 = 0x01;
+pub const UNW_FLAG_UHANDLER: u8 = 0x02;
+pub const UNW_FLAG_CHAININFO: u8 = 0x04;
+
+pub const AMD64_REGISTERS: [&str; 16] = ["rax", "rcx", "rdx", "rbx", "rsp",
+    "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15"];
+
+/* one .pdata entry; ARM64 entries carry no end address, so it is recovered
+ * from the function length in the unwind data */
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RuntimeFunction {
+    begin_address: u32,     /* RVA of the function start */
+    end_address: u32,       /* RVA just past the function end */
+    unwind_data: u32        /* RVA of the unwind data, or packed unwind data */
+}
+
+impl RuntimeFunction {
+    pub fn get_begin_address(&self) -> u32 {
+        self.begin_address
+    }
+
+    pub fn get_end_address(&self) -> u32 {
+        self.end_address
+    }
+
+    pub fn get_unwind_data(&self) -> u32 {
+        self.unwind_data
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<RuntimeFunction, ButylError> {
+        Ok(RuntimeFunction {
+            begin_address: read_le_u32(bytes, 0)?,
+            end_address: read_le_u32(bytes, 4)?,
+            unwind_data: read_le_u32(bytes, 8)?
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnwindOperation {
+    PushNonVol,
+    AllocLarge,
+    AllocSmall,
+    SetFpReg,
+    SaveNonVol,
+    SaveNonVolFar,
+    Epilog,
+    SpareCode,
+    SaveXmm128,
+    SaveXmm128Far,
+    PushMachFrame,
+    Unknown(u8)
+}
+
+impl UnwindOperation {
+    pub fn to_value(operation: UnwindOperation) -> u8 {
+        match operation {
+            UnwindOperation::PushNonVol => 0,
+            UnwindOperation::AllocLarge => 1,
+            UnwindOperation::AllocSmall => 2,
+            UnwindOperation::SetFpReg => 3,
+            UnwindOperation::SaveNonVol => 4,
+            UnwindOperation::SaveNonVolFar => 5,
+            UnwindOperation::Epilog => 6,
+            UnwindOperation::SpareCode => 7,
+            UnwindOperation::SaveXmm128 => 8,
+            UnwindOperation::SaveXmm128Far => 9,
+            UnwindOperation::PushMachFrame => 10,
+            UnwindOperation::Unknown(t) => t
+        }
+    }
+
+    pub fn from_value(value: u8) -> UnwindOperation {
+        match value {
+            0 => UnwindOperation::PushNonVol,
+            1 => UnwindOperation::AllocLarge,
+            2 => UnwindOperation::AllocSmall,
+            3 => UnwindOperation::SetFpReg,
+            4 => UnwindOperation::SaveNonVol,
+            5 => UnwindOperation::SaveNonVolFar,
+            6 => UnwindOperation::Epilog,
+            7 => UnwindOperation::SpareCode,
+            8 => UnwindOperation::SaveXmm128,
+            9 => UnwindOperation::SaveXmm128Far,
+            10 => UnwindOperation::PushMachFrame,
+            t => UnwindOperation::Unknown(t)
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            UnwindOperation::PushNonVol => "UWOP_PUSH_NONVOL",
+            UnwindOperation::AllocLarge => "UWOP_ALLOC_LARGE",
+            UnwindOperation::AllocSmall => "UWOP_ALLOC_SMALL",
+            UnwindOperation::SetFpReg => "UWOP_SET_FPREG",
+            UnwindOperation::SaveNonVol => "UWOP_SAVE_NONVOL",
+            UnwindOperation::SaveNonVolFar => "UWOP_SAVE_NONVOL_FAR",
+            UnwindOperation::Epilog => "UWOP_EPILOG",
+            UnwindOperation::SpareCode => "UWOP_SPARE_CODE",
+            UnwindOperation::SaveXmm128 => "UWOP_SAVE_XMM128",
+            UnwindOperation::SaveXmm128Far => "UWOP_SAVE_XMM128_FAR",
+            UnwindOperation::PushMachFrame => "UWOP_PUSH_MACHFRAME",
+            UnwindOperation::Unknown(_) => "UWOP_UNKNOWN"
+        }
+    }
+
+    /* number of 16-bit slots the code occupies, including its own */
+    pub fn get_slot_count(&self, op_info: u8) -> usize {
+        match self {
+            UnwindOperation::AllocLarge if op_info == 0 => 2,
+            UnwindOperation::AllocLarge => 3,
+            UnwindOperation::SaveNonVol | UnwindOperation::SaveXmm128 |
+            UnwindOperation::Epilog => 2,
+            UnwindOperation::SaveNonVolFar | UnwindOperation::SaveXmm128Far |
+            UnwindOperation::SpareCode => 3,
+            _ => 1
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnwindCode {
+    code_offset: u8,            /* prolog offset just past the instruction */
+    operation: UnwindOperation, /* what the instruction did */
+    op_info: u8,                /* register number or size selector */
+    operand: u32                /* decoded size or stack offset, if any */
+}
+
+impl UnwindCode {
+    pub fn get_code_offset(&self) -> u8 {
+        self.code_offset
+    }
+
+    pub fn get_operation(&self) -> UnwindOperation {
+        self.operation
+    }
+
+    pub fn get_op_info(&self) -> u8 {
+        self.op_info
+    }
+
+    pub fn get_operand(&self) -> u32 {
+        self.operand
+    }
+
+    /* renders the prolog instruction the code describes */
+    pub fn get_description(&self) -> String {
+        let register: &str = AMD64_REGISTERS[(self.op_info & 0xf) as usize];
+
+        match self.operation {
+            UnwindOperation::PushNonVol => format!("push {}", register),
+            UnwindOperation::AllocLarge | UnwindOperation::AllocSmall =>
+                format!("sub rsp, {:#x}", self.operand),
+            UnwindOperation::SetFpReg =>
+                format!("lea {}, [rsp + {:#x}]", register, self.operand),
+            UnwindOperation::SaveNonVol | UnwindOperation::SaveNonVolFar =>
+                format!("mov [rsp + {:#x}], {}", self.operand, register),
+            UnwindOperation::SaveXmm128 | UnwindOperation::SaveXmm128Far =>
+                format!("movaps [rsp + {:#x}], xmm{}", self.operand,
+                    self.op_info),
+            UnwindOperation::PushMachFrame => format!("push machine frame{}",
+                if self.op_info == 1 { " (with error code)" } else { "" }),
+            UnwindOperation::Epilog => format!("epilog ({:#x} bytes)",
+                self.code_offset),
+            t => t.get_name().to_string()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct UnwindInfo {
+    version: u8,                    /* 1, or 2 with epilog codes */
+    flags: u8,                      /* UNW_FLAG_* */
+    size_of_prolog: u8,             /* prolog length in bytes */
+    frame_register: u8,             /* frame pointer register, if nonzero */
+    frame_offset: u8,               /* scaled frame pointer offset */
+    codes: Vec<UnwindCode>,         /* in reverse prolog order */
+    handler: Option<u32>,           /* RVA of the language handler */
+    chained: Option<RuntimeFunction>    /* parent function for chained info */
+}
+
+impl UnwindInfo {
+    pub fn get_version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn get_size_of_prolog(&self) -> u8 {
+        self.size_of_prolog
+    }
+
+    pub fn get_frame_register(&self) -> u8 {
+        self.frame_register
+    }
+
+    pub fn get_frame_offset(&self) -> u8 {
+        self.frame_offset
+    }
+
+    pub fn get_codes(&self) -> &[UnwindCode] {
+        &self.codes
+    }
+
+    pub fn get_handler(&self) -> Option<u32> {
+        self.handler
+    }
+
+    pub fn get_chained(&self) -> Option<RuntimeFunction> {
+        self.chained
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<UnwindInfo, ButylError> {
+        let mut unwind_info: UnwindInfo = UnwindInfo::default();
+
+        unwind_info.version = read_u8(bytes, 0)? & 0x7;
+        unwind_info.flags = read_u8(bytes, 0)? >> 3;
+        unwind_info.size_of_prolog = read_u8(bytes, 1)?;
+        unwind_info.frame_register = read_u8(bytes, 3)? & 0xf;
+        unwind_info.frame_offset = read_u8(bytes, 3)? >> 4;
+
+        let count: usize = read_u8(bytes, 2)? as usize;
+        let mut slot: usize = 0;
+
+        while slot < count {
+            let offset: usize = 4 + slot * 2;
+            let operation: UnwindOperation =
+                UnwindOperation::from_value(read_u8(bytes, offset + 1)? & 0xf);
+            let op_info: u8 = read_u8(bytes, offset + 1)? >> 4;
+
+            let mut code: UnwindCode = UnwindCode {
+                code_offset: read_u8(bytes, offset)?,
+                operation,
+                op_info,
+                operand: 0
+            };
+
+            code.operand = match operation {
+                UnwindOperation::AllocLarge if op_info == 0 =>
+                    read_le_u16(bytes, offset + 2)? as u32 * 8,
+                UnwindOperation::AllocLarge | UnwindOperation::SaveNonVolFar |
+                UnwindOperation::SaveXmm128Far =>
+                    read_le_u32(bytes, offset + 2)?,
+                UnwindOperation::AllocSmall => op_info as u32 * 8 + 8,
+                UnwindOperation::SaveNonVol =>
+                    read_le_u16(bytes, offset + 2)? as u32 * 8,
+                UnwindOperation::SaveXmm128 =>
+                    read_le_u16(bytes, offset + 2)? as u32 * 16,
+                UnwindOperation::SetFpReg => {
+                    code.op_info = unwind_info.frame_register;
+                    unwind_info.frame_offset as u32 * 16
+                },
+                _ => 0
+            };
+
+            unwind_info.codes.push(code);
+            slot += operation.get_slot_count(op_info);
+        }
+
+        /* the code array is padded to an even number of slots */
+        let trailer: usize = 4 + count.next_multiple_of(2) * 2;
+
+        if unwind_info.flags & UNW_FLAG_CHAININFO != 0 {
+            unwind_info.chained = Some(RuntimeFunction::from_le_bytes(
+                read_bytes(bytes, trailer, AMD64_RUNTIME_FUNCTION_LEN)?)?);
+        } else if unwind_info.flags & (UNW_FLAG_EHANDLER | UNW_FLAG_UHANDLER)
+            != 0 {
+            unwind_info.handler = Some(read_le_u32(bytes, trailer)?);
+        }
+
+        Ok(unwind_info)
+    }
+}
+
+/* the compact form of ARM64 unwind data that fits in the .pdata entry
+ * itself, describing a canonical prolog */
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Arm64PackedUnwind {
+    flag: u8,               /* 1 = packed, 2 = packed fragment */
+    function_length: u32,   /* function length in bytes */
+    reg_f: u8,              /* number of saved d8-d15 registers */
+    reg_i: u8,              /* number of saved x19-x28 registers */
+    h: bool,                /* x0-x7 homed */
+    cr: u8,                 /* 0 = no lr, 1 = lr, 2 = pac lr, 3 = fp/lr chain */
+    frame_size: u32         /* stack frame size in bytes */
+}
+
+impl Arm64PackedUnwind {
+    pub fn get_flag(&self) -> u8 {
+        self.flag
+    }
+
+    pub fn get_function_length(&self) -> u32 {
+        self.function_length
+    }
+
+    pub fn get_reg_f(&self) -> u8 {
+        self.reg_f
+    }
+
+    pub fn get_reg_i(&self) -> u8 {
+        self.reg_i
+    }
+
+    pub fn get_h(&self) -> bool {
+        self.h
+    }
+
+    pub fn get_cr(&self) -> u8 {
+        self.cr
+    }
+
+    pub fn get_frame_size(&self) -> u32 {
+        self.frame_size
+    }
+
+    pub fn from_value(value: u32) -> Arm64PackedUnwind {
+        Arm64PackedUnwind {
+            flag: (value & 0x3) as u8,
+            function_length: ((value >> 2) & 0x7ff) * 4,
+            reg_f: ((value >> 13) & 0x7) as u8,
+            reg_i: ((value >> 16) & 0xf) as u8,
+            h: (value >> 20) & 0x1 != 0,
+            cr: ((value >> 21) & 0x3) as u8,
+            frame_size: ((value >> 23) & 0x1ff) * 16
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Arm64UnwindCode {
+    index: usize,       /* byte index within the code array */
+    bytes: Vec<u8>,     /* raw encoding */
+    name: &'static str, /* e.g. save_fplr_x */
+    text: String        /* equivalent prolog instruction */
+}
+
+impl Arm64UnwindCode {
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn from_bytes(bytes: &[u8], index: usize) ->
+        Result<Arm64UnwindCode, ButylError> {
+        let b0: u32 = read_u8(bytes, index)? as u32;
+        let b1 = || -> Result<u32, ButylError> {
+            Ok(read_u8(bytes, index + 1)? as u32)
+        };
+
+        let (len, name, text): (usize, &'static str, String) = match b0 {
+            0x00..=0x1f => (1, "alloc_s", format!("sub sp, sp, #{}", b0 * 16)),
+            0x20..=0x3f => (1, "save_r19r20_x",
+                format!("stp x19, x20, [sp, #-{}]!", (b0 & 0x1f) * 8)),
+            0x40..=0x7f => (1, "save_fplr",
+                format!("stp x29, x30, [sp, #{}]", (b0 & 0x3f) * 8)),
+            0x80..=0xbf => (1, "save_fplr_x",
+                format!("stp x29, x30, [sp, #-{}]!", ((b0 & 0x3f) + 1) * 8)),
+            0xc0..=0xc7 => (2, "alloc_m",
+                format!("sub sp, sp, #{}", (((b0 & 0x7) << 8) | b1()?) * 16)),
+            0xc8..=0xcb => {
+                let x: u32 = ((b0 & 0x3) << 2) | (b1()? >> 6);
+                (2, "save_regp", format!("stp x{}, x{}, [sp, #{}]", 19 + x,
+                    20 + x, (b1()? & 0x3f) * 8))
+            },
+            0xcc..=0xcf => {
+                let x: u32 = ((b0 & 0x3) << 2) | (b1()? >> 6);
+                (2, "save_regp_x", format!("stp x{}, x{}, [sp, #-{}]!", 19 + x,
+                    20 + x, ((b1()? & 0x3f) + 1) * 8))
+            },
+            0xd0..=0xd3 => {
+                let x: u32 = ((b0 & 0x3) << 2) | (b1()? >> 6);
+                (2, "save_reg", format!("str x{}, [sp, #{}]", 19 + x,
+                    (b1()? & 0x3f) * 8))
+            },
+            0xd4 | 0xd5 => {
+                let x: u32 = ((b0 & 0x1) << 3) | (b1()? >> 5);
+                (2, "save_reg_x", format!("str x{}, [sp, #-{}]!", 19 + x,
+                    ((b1()? & 0x1f) + 1) * 8))
+            },
+            0xd6 | 0xd7 => {
+                let x: u32 = ((b0 & 0x1) << 2) | (b1()? >> 6);
+                (2, "save_lrpair", format!("stp x{}, x30, [sp, #{}]",
+                    19 + 2 * x, (b1()? & 0x3f) * 8))
+            },
+            0xd8 | 0xd9 => {
+                let x: u32 = ((b0 & 0x1) << 2) | (b1()? >> 6);
+                (2, "save_fregp", format!("stp d{}, d{}, [sp, #{}]", 8 + x,
+                    9 + x, (b1()? & 0x3f) * 8))
+            },
+            0xda | 0xdb => {
+                let x: u32 = ((b0 & 0x1) << 2) | (b1()? >> 6);
+                (2, "save_fregp_x", format!("stp d{}, d{}, [sp, #-{}]!", 8 + x,
+                    9 + x, ((b1()? & 0x3f) + 1) * 8))
+            },
+            0xdc | 0xdd => {
+                let x: u32 = ((b0 & 0x1) << 2) | (b1()? >> 6);
+                (2, "save_freg", format!("str d{}, [sp, #{}]", 8 + x,
+                    (b1()? & 0x3f) * 8))
+            },
+            0xde => (2, "save_freg_x", format!("str d{}, [sp, #-{}]!",
+                8 + (b1()? >> 5), ((b1()? & 0x1f) + 1) * 8)),
+            0xdf => (2, "alloc_z", format!("addvl sp, sp, #-{}", b1()?)),
+            0xe0 => {
+                let x: u32 = (b1()? << 16) |
+                    ((read_u8(bytes, index + 2)? as u32) << 8) |
+                    read_u8(bytes, index + 3)? as u32;
+                (4, "alloc_l", format!("sub sp, sp, #{}", x * 16))
+            },
+            0xe1 => (1, "set_fp", String::from("mov x29, sp")),
+            0xe2 => (2, "add_fp", format!("add x29, sp, #{}", b1()? * 8)),
+            0xe3 => (1, "nop", String::from("nop")),
+            0xe4 => (1, "end", String::from("end")),
+            0xe5 => (1, "end_c", String::from("end_c")),
+            0xe6 => (1, "save_next", String::from("save_next")),
+            0xe7 => (3, "save_any_reg", String::from("save_any_reg")),
+            0xe8 => (1, "trap_frame", String::from("trap frame")),
+            0xe9 => (1, "machine_frame", String::from("machine frame")),
+            0xea => (1, "context", String::from("context")),
+            0xeb => (1, "ec_context", String::from("ec context")),
+            0xec => (1, "clear_unwound_to_call",
+                String::from("clear unwound to call")),
+            0xfc => (1, "pac_sign_lr", String::from("pacibsp")),
+            _ => (1, "reserved", String::from("reserved"))
+        };
+
+        Ok(Arm64UnwindCode {
+            index,
+            bytes: read_bytes(bytes, index, len)?.to_vec(),
+            name,
+            text
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EpilogScope {
+    start_offset: u32,  /* offset of the epilog from the function start */
+    start_index: u16    /* index of its first unwind code */
+}
+
+impl EpilogScope {
+    pub fn get_start_offset(&self) -> u32 {
+        self.start_offset
+    }
+
+    pub fn get_start_index(&self) -> u16 {
+        self.start_index
+    }
+}
+
+/* an .xdata record, for functions the packed form can't describe */
+#[derive(Debug, Default, Clone)]
+pub struct Arm64UnwindInfo {
+    function_length: u32,       /* function length in bytes */
+    version: u8,                /* always 0 */
+    x: bool,                    /* exception data present */
+    e: bool,                    /* single epilog packed into the header */
+    epilog_count: u16,          /* epilog scopes, or first code of the epilog */
+    code_words: u8,             /* length of the code array in words */
+    epilog_scopes: Vec<EpilogScope>,
+    codes: Vec<Arm64UnwindCode>,
+    handler: Option<u32>        /* RVA of the exception handler */
+}
+
+impl Arm64UnwindInfo {
+    pub fn get_function_length(&self) -> u32 {
+        self.function_length
+    }
+
+    pub fn get_version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn get_x(&self) -> bool {
+        self.x
+    }
+
+    pub fn get_e(&self) -> bool {
+        self.e
+    }
+
+    pub fn get_epilog_count(&self) -> u16 {
+        self.epilog_count
+    }
+
+    pub fn get_code_words(&self) -> u8 {
+        self.code_words
+    }
+
+    pub fn get_epilog_scopes(&self) -> &[EpilogScope] {
+        &self.epilog_scopes
+    }
+
+    pub fn get_codes(&self) -> &[Arm64UnwindCode] {
+        &self.codes
+    }
+
+    pub fn get_handler(&self) -> Option<u32> {
+        self.handler
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Arm64UnwindInfo, ButylError> {
+        let mut unwind_info: Arm64UnwindInfo = Arm64UnwindInfo::default();
+        let header: u32 = read_le_u32(bytes, 0)?;
+        let mut offset: usize = 4;
+
+        unwind_info.function_length = (header & 0x3ffff) * 4;
+        unwind_info.version = ((header >> 18) & 0x3) as u8;
+        unwind_info.x = (header >> 20) & 0x1 != 0;
+        unwind_info.e = (header >> 21) & 0x1 != 0;
+        unwind_info.epilog_count = ((header >> 22) & 0x1f) as u16;
+        unwind_info.code_words = (header >> 27) as u8;
+
+        /* both fields zero means they spill into an extension word */
+        let mut code_words: usize = unwind_info.code_words as usize;
+
+        if unwind_info.epilog_count == 0 && code_words == 0 {
+            let extension: u32 = read_le_u32(bytes, offset)?;
+            offset += 4;
+
+            unwind_info.epilog_count = extension as u16;
+            code_words = ((extension >> 16) & 0xff) as usize;
+        }
+
+        if !unwind_info.e {
+            for _ in 0..unwind_info.epilog_count {
+                let scope: u32 = read_le_u32(bytes, offset)?;
+                offset += 4;
+
+                unwind_info.epilog_scopes.push(EpilogScope {
+                    start_offset: (scope & 0x3ffff) * 4,
+                    start_index: (scope >> 22) as u16
+                });
+            }
+        }
+
+        let code_bytes: &[u8] = read_bytes(bytes, offset, code_words * 4)?;
+
+        /* the prolog's codes start the array and each epilog's start at its
+         * own index, each running to an end or end_c; an epilog may also
+         * share the tail of the prolog's, and anything after the last end
+         * is padding */
+        let mut starts: Vec<usize> = match unwind_info.e {
+            true => vec![0, unwind_info.epilog_count as usize],
+            false => std::iter::once(0)
+                .chain(unwind_info.epilog_scopes.iter()
+                    .map(|t| t.start_index as usize))
+                .collect()
+        };
+
+        starts.sort_unstable();
+        starts.dedup();
+
+        for start in starts {
+            let mut index: usize = start;
+
+            while index < code_bytes.len() &&
+                !unwind_info.codes.iter().any(|t| t.index == index) {
+                let code: Arm64UnwindCode =
+                    Arm64UnwindCode::from_bytes(code_bytes, index)?;
+                let name: &'static str = code.get_name();
+
+                index += code.get_bytes().len();
+                unwind_info.codes.push(code);
+
+                if name == "end" || name == "end_c" {
+                    break;
+                }
+            }
+        }
+
+        offset += code_words * 4;
+
+        if unwind_info.x {
+            unwind_info.handler = Some(read_le_u32(bytes, offset)?);
+        }
+
+        Ok(unwind_info)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Unwind {
+    Amd64(UnwindInfo),
+    Arm64Packed(Arm64PackedUnwind),
+    Arm64(Arm64UnwindInfo)
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_runtime_functions(&self) ->
+        Result<Vec<RuntimeFunction>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Exception) {
+                Some(t) => t,
+                None => return Ok(Vec::new())
+            };
+
+        let table: &[u8] = self.read_rva(directory.get_virtual_address(),
+            directory.get_size() as usize)?;
+
+        match self.get_coff_header().get_machine_as_enum() {
+            CoffHeaderMachineType::AMD64 => table
+                .chunks_exact(AMD64_RUNTIME_FUNCTION_LEN)
+                .map(RuntimeFunction::from_le_bytes)
+                .collect(),
+            CoffHeaderMachineType::ARM64 => {
+                let mut functions: Vec<RuntimeFunction> = Vec::new();
+
+                for entry in table.chunks_exact(ARM64_RUNTIME_FUNCTION_LEN) {
+                    let begin_address: u32 = read_le_u32(entry, 0)?;
+                    let unwind_data: u32 = read_le_u32(entry, 4)?;
+
+                    let length: u32 = if unwind_data & 0x3 != 0 {
+                        Arm64PackedUnwind::from_value(unwind_data)
+                            .get_function_length()
+                    } else {
+                        (read_le_u32(self.read_rva(unwind_data, 4)?, 0)? &
+                            0x3ffff) * 4
+                    };
+
+                    functions.push(RuntimeFunction {
+                        begin_address,
+                        end_address: begin_address.saturating_add(length),
+                        unwind_data
+                    });
+                }
+
+                Ok(functions)
+            },
+            _ => Err(ButylError::UnsupportedFeatureError)
+        }
+    }
+
+    pub fn get_unwind(&self, function: &RuntimeFunction) ->
+        Result<Unwind, ButylError> {
+        let unwind_data: u32 = function.get_unwind_data();
+
+        match self.get_coff_header().get_machine_as_enum() {
+            CoffHeaderMachineType::AMD64 => {
+                /* the low bit marks an indirection through another
+                 * RUNTIME_FUNCTION rather than real unwind data */
+                let rva: u32 = if unwind_data & 0x1 != 0 {
+                    read_le_u32(self.read_rva(unwind_data & !0x1,
+                        AMD64_RUNTIME_FUNCTION_LEN)?, 8)?
+                } else {
+                    unwind_data
+                };
+
+                let offset: usize = self.rva_to_offset(rva)?;
+                let bytes: &[u8] = read_bytes(self.get_data(), offset,
+                    self.get_data().len() - offset)?;
+
+                Ok(Unwind::Amd64(UnwindInfo::from_le_bytes(bytes)?))
+            },
+            CoffHeaderMachineType::ARM64 if unwind_data & 0x3 != 0 =>
+                Ok(Unwind::Arm64Packed(
+                    Arm64PackedUnwind::from_value(unwind_data))),
+            CoffHeaderMachineType::ARM64 => {
+                let offset: usize = self.rva_to_offset(unwind_data)?;
+                let bytes: &[u8] = read_bytes(self.get_data(), offset,
+                    self.get_data().len() - offset)?;
+
+                Ok(Unwind::Arm64(Arm64UnwindInfo::from_le_bytes(bytes)?))
+            },
+            _ => Err(ButylError::UnsupportedFeatureError)
+        }
+    }
+
+    /* sorted `[begin, end)` code ranges, suitable for splitting executable
+     * sections into functions */
+    pub fn get_function_boundaries(&self) ->
+        Result<Vec<(u32, u32)>, ButylError> {
+        let mut boundaries: Vec<(u32, u32)> = self.get_runtime_functions()?
+            .iter()
+            .map(|t| (t.get_begin_address(), t.get_end_address()))
+            .collect();
+
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        Ok(boundaries)
+    }
+}
+
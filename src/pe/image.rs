@@ -0,0 +1,141 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{self, PeFile, DataDirectoryType, PE_SECTION_HEADER_LEN};
+use crate::utils::*;
+
+pub const PE_OPTIONAL_HEADER_IMAGE_BASE_OFFSET_32: usize = 28;
+pub const PE_OPTIONAL_HEADER_IMAGE_BASE_OFFSET_64: usize = 24;
+pub const SECTION_HEADER_SIZE_OF_RAW_DATA_OFFSET: usize = 16;
+pub const SECTION_HEADER_POINTER_TO_RAW_DATA_OFFSET: usize = 20;
+
+impl<'a> PeFile<'a> {
+    pub fn get_image_base_offset(&self) -> usize {
+        self.get_optional_header_offset() + if self.is_64_bit() {
+            PE_OPTIONAL_HEADER_IMAGE_BASE_OFFSET_64
+        } else {
+            PE_OPTIONAL_HEADER_IMAGE_BASE_OFFSET_32
+        }
+    }
+
+    /* lays the file out as the loader would, with each section at its RVA
+     * and everything not backed by raw data (BSS, alignment padding) left
+     * zeroed */
+    pub fn map_image(&self, new_base: Option<u64>) ->
+        Result<Vec<u8>, ButylError> {
+        let address_space = self.get_address_space();
+
+        /* SizeOfImage comes straight from the file, so don't allocate more
+         * than the sections need */
+        if address_space.get_size_of_image() as u64 >
+            address_space.get_mapped_extent() {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        /* a PE32 ImageBase is only 32 bits wide */
+        if let Some(base) = new_base {
+            if !self.is_64_bit() && base > u32::MAX as u64 {
+                return Err(ButylError::ExcessiveDataError);
+            }
+        }
+
+        let mut image: Vec<u8> =
+            vec![0; address_space.get_size_of_image() as usize];
+
+        let headers_len: usize = (address_space.get_size_of_headers() as usize)
+            .min(image.len());
+        image[..headers_len].copy_from_slice(&self.data[..headers_len]);
+
+        for mapping in address_space.get_mappings() {
+            let start: usize = mapping.get_virtual_address() as usize;
+
+            if start >= image.len() {
+                continue;
+            }
+
+            let len: usize = (mapping.get_raw_size() as usize)
+                .min(image.len() - start);
+            let raw: &[u8] = read_bytes(self.data,
+                mapping.get_pointer_to_raw_data() as usize, len)?;
+            image[start..start + len].copy_from_slice(raw);
+        }
+
+        if let Some(base) = new_base {
+            self.rebase_image(&mut image, base)?;
+
+            let offset: usize = self.get_image_base_offset();
+
+            if self.is_64_bit() {
+                write_le_u64(&mut image, offset, base)?;
+            } else {
+                write_le_u32(&mut image, offset, base as u32)?;
+            }
+        }
+
+        Ok(image)
+    }
+}
+
+/* reverses `map_image` for a dumped image: sections are packed back to back
+ * at the file alignment, each taking its whole virtual extent from the dump
+ * so that anything written at runtime is kept */
+pub fn rebuild_from_memory(memory: &[u8]) -> Result<Vec<u8>, ButylError> {
+    let pe: PeFile = PeFile::from_le_bytes(memory)?;
+    let address_space = pe.get_address_space();
+    let file_alignment: u32 = address_space.get_file_alignment();
+    let headers_len: usize = address_space.get_size_of_headers() as usize;
+
+    let mut file: Vec<u8> = read_bytes(memory, 0, headers_len)?.to_vec();
+    file.resize(align_up(headers_len, file_alignment as usize), 0);
+
+    for (i, section) in pe.get_sections().iter().enumerate() {
+        let start: usize = section.get_virtual_address() as usize;
+        let virtual_size: usize = match section.get_virtual_size() {
+            0 => section.get_size_of_raw_data(),
+            t => t
+        } as usize;
+        let len: usize = virtual_size.min(memory.len().saturating_sub(start));
+
+        let (pointer_to_raw_data, size_of_raw_data): (usize, usize) =
+            if len == 0 {
+                (0, 0)
+            } else {
+                (file.len(), align_up(len, file_alignment as usize))
+            };
+
+        if len != 0 {
+            file.extend_from_slice(read_bytes(memory, start, len)?);
+            file.resize(pointer_to_raw_data + size_of_raw_data, 0);
+        }
+
+        let header: usize = pe.get_section_table_offset() +
+            i * PE_SECTION_HEADER_LEN;
+        write_le_u32(&mut file, header +
+            SECTION_HEADER_SIZE_OF_RAW_DATA_OFFSET, size_of_raw_data as u32)?;
+        write_le_u32(&mut file, header +
+            SECTION_HEADER_POINTER_TO_RAW_DATA_OFFSET,
+            pointer_to_raw_data as u32)?;
+    }
+
+    /* the certificate table is addressed by file offset and never mapped, so
+     * whatever it pointed at is gone */
+    if pe.get_data_directory(DataDirectoryType::Security).is_some() {
+        let security: usize = pe.get_optional_header_offset() +
+            pe.get_optional_header()
+                .get_data_directory_offset(DataDirectoryType::Security);
+        write_bytes(&mut file, security, &[0; pe::PE_DATA_DIRECTORY_LEN])?;
+    }
+
+    let check_sum_offset: usize = pe.get_check_sum_offset();
+    let check_sum: u32 = pe::compute_check_sum(&file, check_sum_offset);
+    write_le_u32(&mut file, check_sum_offset, check_sum)?;
+
+    Ok(file)
+}
+
+fn align_up(value: usize, alignment: usize) -> usize {
+    match alignment {
+        0 => value,
+        t => value.div_ceil(t) * t
+    }
+}
@@ -0,0 +1,593 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const IMPORT_DESCRIPTOR_LEN: usize = 20;
+pub const DELAY_IMPORT_DESCRIPTOR_LEN: usize = 32;
+pub const DELAY_IMPORT_ATTRIBUTE_RVA: u32 = 0x0000_0001;
+pub const IMPORT_ORDINAL_FLAG_32: u64 = 0x8000_0000;
+pub const IMPORT_ORDINAL_FLAG_64: u64 = 0x8000_0000_0000_0000;
+pub const BOUND_IMPORT_DESCRIPTOR_LEN: usize = 8;
+pub const BOUND_FORWARDER_REF_LEN: usize = 8;
+pub const IMPORT_NEW_STYLE_BINDING: u32 = 0xffff_ffff;
+
+#[derive(Debug, Default, Clone)]
+pub struct ImportDescriptor {
+    original_first_thunk: u32,  /* RVA of import lookup table */
+    time_date_stamp: u32,       /* zero unless bound */
+    forwarder_chain: u32,       /* index of first forwarder */
+    name: u32,                  /* RVA of module name */
+    first_thunk: u32            /* RVA of import address table */
+}
+
+impl ImportDescriptor {
+    pub fn get_original_first_thunk(&self) -> u32 {
+        self.original_first_thunk
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_forwarder_chain(&self) -> u32 {
+        self.forwarder_chain
+    }
+
+    pub fn get_name(&self) -> u32 {
+        self.name
+    }
+
+    pub fn get_first_thunk(&self) -> u32 {
+        self.first_thunk
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.original_first_thunk == 0 && self.time_date_stamp == 0 &&
+            self.forwarder_chain == 0 && self.name == 0 &&
+            self.first_thunk == 0
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) ->
+        Result<ImportDescriptor, ButylError> {
+        Ok(ImportDescriptor {
+            original_first_thunk: read_le_u32(bytes, 0)?,
+            time_date_stamp: read_le_u32(bytes, 4)?,
+            forwarder_chain: read_le_u32(bytes, 8)?,
+            name: read_le_u32(bytes, 12)?,
+            first_thunk: read_le_u32(bytes, 16)?
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DelayImportDescriptor {
+    attributes: u32,                        /* addressing mode flags */
+    dll_name: u32,                          /* module name */
+    module_handle: u32,                     /* HMODULE storage */
+    import_address_table: u32,              /* delay-load IAT */
+    import_name_table: u32,                 /* delay-load INT */
+    bound_import_address_table: u32,        /* optional bound IAT */
+    unload_information_table: u32,          /* optional copy of IAT */
+    time_date_stamp: u32                    /* zero unless bound */
+}
+
+impl DelayImportDescriptor {
+    pub fn get_attributes(&self) -> u32 {
+        self.attributes
+    }
+
+    /* older linkers emitted virtual addresses rather than RVAs */
+    pub fn is_rva_based(&self) -> bool {
+        self.attributes & DELAY_IMPORT_ATTRIBUTE_RVA != 0
+    }
+
+    pub fn get_dll_name(&self) -> u32 {
+        self.dll_name
+    }
+
+    pub fn get_module_handle(&self) -> u32 {
+        self.module_handle
+    }
+
+    pub fn get_import_address_table(&self) -> u32 {
+        self.import_address_table
+    }
+
+    pub fn get_import_name_table(&self) -> u32 {
+        self.import_name_table
+    }
+
+    pub fn get_bound_import_address_table(&self) -> u32 {
+        self.bound_import_address_table
+    }
+
+    pub fn get_unload_information_table(&self) -> u32 {
+        self.unload_information_table
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.dll_name == 0 && self.import_address_table == 0 &&
+            self.import_name_table == 0
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) ->
+        Result<DelayImportDescriptor, ButylError> {
+        Ok(DelayImportDescriptor {
+            attributes: read_le_u32(bytes, 0)?,
+            dll_name: read_le_u32(bytes, 4)?,
+            module_handle: read_le_u32(bytes, 8)?,
+            import_address_table: read_le_u32(bytes, 12)?,
+            import_name_table: read_le_u32(bytes, 16)?,
+            bound_import_address_table: read_le_u32(bytes, 20)?,
+            unload_information_table: read_le_u32(bytes, 24)?,
+            time_date_stamp: read_le_u32(bytes, 28)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Import {
+    module: String,         /* name of the imported module */
+    name: Option<String>,   /* symbol name, if imported by name */
+    hint: u16,              /* export name table hint */
+    ordinal: Option<u16>,   /* ordinal, if imported by ordinal */
+    thunk: u32,             /* RVA of the IAT slot */
+    delayed: bool           /* from the delay-load directory */
+}
+
+impl Import {
+    pub fn get_module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn get_hint(&self) -> u16 {
+        self.hint
+    }
+
+    pub fn get_ordinal(&self) -> Option<u16> {
+        self.ordinal
+    }
+
+    pub fn get_thunk(&self) -> u32 {
+        self.thunk
+    }
+
+    pub fn is_delayed(&self) -> bool {
+        self.delayed
+    }
+}
+
+/* IMAGE_BOUND_FORWARDER_REF shares the descriptor's layout, with the
+ * forwarder count replaced by a reserved field */
+#[derive(Debug, Default, Clone)]
+pub struct BoundForwarderRef {
+    time_date_stamp: u32,       /* timestamp of the bound module */
+    offset_module_name: u16,    /* name offset in the directory */
+    reserved: u16,              /* (reserved) */
+    module: String              /* resolved module name */
+}
+
+impl BoundForwarderRef {
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_offset_module_name(&self) -> u16 {
+        self.offset_module_name
+    }
+
+    pub fn get_reserved(&self) -> u16 {
+        self.reserved
+    }
+
+    pub fn get_module(&self) -> &str {
+        &self.module
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BoundImportDescriptor {
+    time_date_stamp: u32,               /* timestamp of the bound module */
+    offset_module_name: u16,            /* name offset in the directory */
+    number_of_module_forwarder_refs: u16,   /* forwarder refs that follow */
+    module: String,                     /* resolved module name */
+    forwarder_refs: Vec<BoundForwarderRef>
+}
+
+impl BoundImportDescriptor {
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_offset_module_name(&self) -> u16 {
+        self.offset_module_name
+    }
+
+    pub fn get_number_of_module_forwarder_refs(&self) -> u16 {
+        self.number_of_module_forwarder_refs
+    }
+
+    pub fn get_module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn get_forwarder_refs(&self) -> &[BoundForwarderRef] {
+        &self.forwarder_refs
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.time_date_stamp == 0 && self.offset_module_name == 0 &&
+            self.number_of_module_forwarder_refs == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingStatus {
+    Unbound,            /* neither side claims a binding */
+    Bound,              /* descriptor and bound import entry agree */
+    OldStyle,           /* timestamp held directly in the descriptor */
+    MissingBoundEntry,  /* descriptor claims new-style binding, no entry */
+    NotMarkedBound,     /* bound entry exists, descriptor claims unbound */
+    UnboundIat,         /* claims binding but the IAT matches the INT */
+    UnusedBoundEntry    /* bound entry for a module that isn't imported */
+}
+
+impl BindingStatus {
+    pub fn is_stale(&self) -> bool {
+        !matches!(self, BindingStatus::Unbound | BindingStatus::Bound |
+            BindingStatus::OldStyle)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportBinding {
+    module: String,                     /* imported module name */
+    descriptor_time_date_stamp: Option<u32>,    /* None if not imported */
+    bound_time_date_stamp: Option<u32>, /* None if no bound entry */
+    status: BindingStatus
+}
+
+impl ImportBinding {
+    pub fn get_module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn get_descriptor_time_date_stamp(&self) -> Option<u32> {
+        self.descriptor_time_date_stamp
+    }
+
+    pub fn get_bound_time_date_stamp(&self) -> Option<u32> {
+        self.bound_time_date_stamp
+    }
+
+    pub fn get_status(&self) -> BindingStatus {
+        self.status
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IatEntry {
+    rva: u32,       /* RVA of the slot */
+    value: u64      /* thunk or bound address stored in the file */
+}
+
+impl IatEntry {
+    pub fn get_rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_import_descriptors(&self) ->
+        Result<Vec<ImportDescriptor>, ButylError> {
+        let mut descriptors: Vec<ImportDescriptor> = Vec::new();
+
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Import) {
+                Some(t) => t,
+                None => return Ok(descriptors)
+            };
+
+        let mut rva: u32 = directory.get_virtual_address();
+
+        loop {
+            let descriptor: ImportDescriptor = ImportDescriptor::from_le_bytes(
+                self.read_rva(rva, IMPORT_DESCRIPTOR_LEN)?)?;
+
+            if descriptor.is_null() {
+                break;
+            }
+
+            descriptors.push(descriptor);
+            rva = rva.checked_add(IMPORT_DESCRIPTOR_LEN as u32)
+                .ok_or(ButylError::MalformedDataError)?;
+        }
+
+        Ok(descriptors)
+    }
+
+    pub fn get_delay_import_descriptors(&self) ->
+        Result<Vec<DelayImportDescriptor>, ButylError> {
+        let mut descriptors: Vec<DelayImportDescriptor> = Vec::new();
+
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::DelayImport) {
+                Some(t) => t,
+                None => return Ok(descriptors)
+            };
+
+        let mut rva: u32 = directory.get_virtual_address();
+
+        loop {
+            let descriptor: DelayImportDescriptor =
+                DelayImportDescriptor::from_le_bytes(
+                    self.read_rva(rva, DELAY_IMPORT_DESCRIPTOR_LEN)?)?;
+
+            if descriptor.is_null() {
+                break;
+            }
+
+            descriptors.push(descriptor);
+            rva = rva.checked_add(DELAY_IMPORT_DESCRIPTOR_LEN as u32)
+                .ok_or(ButylError::MalformedDataError)?;
+        }
+
+        Ok(descriptors)
+    }
+
+    /* returns both regular and delay-loaded imports; delay-loaded entries
+     * are flagged via `Import::is_delayed` */
+    pub fn get_imports(&self) -> Result<Vec<Import>, ButylError> {
+        let mut imports: Vec<Import> = Vec::new();
+
+        for descriptor in self.get_import_descriptors()? {
+            let module: String = self.read_cstring_at_rva(
+                descriptor.get_name())?;
+
+            /* some linkers leave the lookup table empty, in which case the
+             * IAT still holds the unbound thunks */
+            let lookup: u32 = match descriptor.get_original_first_thunk() {
+                0 => descriptor.get_first_thunk(),
+                t => t
+            };
+
+            self.read_thunks(&module, lookup, descriptor.get_first_thunk(),
+                false, false, &mut imports)?;
+        }
+
+        for descriptor in self.get_delay_import_descriptors()? {
+            /* the legacy form stores virtual addresses instead of RVAs */
+            let va_based: bool = !descriptor.is_rva_based();
+
+            let module: String = self.read_cstring_at_rva(self.to_rva(
+                descriptor.get_dll_name() as u64, va_based)?)?;
+            let lookup: u32 = self.to_rva(
+                descriptor.get_import_name_table() as u64, va_based)?;
+            let iat: u32 = self.to_rva(
+                descriptor.get_import_address_table() as u64, va_based)?;
+
+            self.read_thunks(&module, lookup, iat, va_based, true,
+                &mut imports)?;
+        }
+
+        Ok(imports)
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn get_bound_import_descriptors(&self) ->
+        Result<Vec<BoundImportDescriptor>, ButylError> {
+        let mut descriptors: Vec<BoundImportDescriptor> = Vec::new();
+
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::BoundImport) {
+                Some(t) => t,
+                None => return Ok(descriptors)
+            };
+
+        /* the directory normally sits in the headers, and module name
+         * offsets are relative to its start */
+        let table: &[u8] = self.read_rva(directory.get_virtual_address(),
+            directory.get_size() as usize)?;
+        let mut offset: usize = 0;
+
+        loop {
+            let mut descriptor: BoundImportDescriptor =
+                BoundImportDescriptor::default();
+
+            descriptor.time_date_stamp = read_le_u32(table, offset)?;
+            descriptor.offset_module_name = read_le_u16(table, offset + 4)?;
+            descriptor.number_of_module_forwarder_refs =
+                read_le_u16(table, offset + 6)?;
+
+            if descriptor.is_null() {
+                break;
+            }
+
+            descriptor.module = read_cstring(table,
+                descriptor.offset_module_name as usize)?;
+            offset += BOUND_IMPORT_DESCRIPTOR_LEN;
+
+            for _ in 0..descriptor.number_of_module_forwarder_refs {
+                let offset_module_name: u16 = read_le_u16(table, offset + 4)?;
+
+                descriptor.forwarder_refs.push(BoundForwarderRef {
+                    time_date_stamp: read_le_u32(table, offset)?,
+                    offset_module_name,
+                    reserved: read_le_u16(table, offset + 6)?,
+                    module: read_cstring(table, offset_module_name as usize)?
+                });
+                offset += BOUND_FORWARDER_REF_LEN;
+            }
+
+            descriptors.push(descriptor);
+        }
+
+        Ok(descriptors)
+    }
+
+    pub fn get_import_address_table(&self) ->
+        Result<Vec<IatEntry>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Iat) {
+                Some(t) => t,
+                None => return Ok(Vec::new())
+            };
+
+        let thunk_len: u32 = if self.is_64_bit() { 8 } else { 4 };
+        let mut entries: Vec<IatEntry> = Vec::new();
+
+        for index in 0..directory.get_size() / thunk_len {
+            let rva: u32 = directory.get_virtual_address()
+                .checked_add(index * thunk_len)
+                .ok_or(ButylError::MalformedDataError)?;
+
+            entries.push(IatEntry {
+                rva,
+                value: self.read_thunk(rva)?
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /* cross-checks the import descriptors against the bound import
+     * directory; the referenced DLLs aren't available, so this can only
+     * catch bindings that are inconsistent within the image itself */
+    pub fn get_import_bindings(&self) ->
+        Result<Vec<ImportBinding>, ButylError> {
+        let bound: Vec<BoundImportDescriptor> =
+            self.get_bound_import_descriptors()?;
+        let mut bindings: Vec<ImportBinding> = Vec::new();
+
+        for descriptor in self.get_import_descriptors()? {
+            let module: String = self.read_cstring_at_rva(
+                descriptor.get_name())?;
+            let time_date_stamp: u32 = descriptor.get_time_date_stamp();
+            let bound_time_date_stamp: Option<u32> = bound.iter()
+                .find(|t| t.get_module().eq_ignore_ascii_case(&module))
+                .map(|t| t.get_time_date_stamp());
+
+            let mut status: BindingStatus =
+                match (time_date_stamp, bound_time_date_stamp) {
+                    (0, None) => BindingStatus::Unbound,
+                    (0, Some(_)) => BindingStatus::NotMarkedBound,
+                    (IMPORT_NEW_STYLE_BINDING, None) =>
+                        BindingStatus::MissingBoundEntry,
+                    (IMPORT_NEW_STYLE_BINDING, Some(_)) => BindingStatus::Bound,
+                    (_, _) => BindingStatus::OldStyle
+                };
+
+            /* a binding that was applied leaves resolved addresses in the
+             * IAT, so an IAT identical to the lookup table was never bound */
+            if (status == BindingStatus::Bound ||
+                status == BindingStatus::OldStyle) &&
+                descriptor.get_original_first_thunk() != 0 &&
+                self.read_thunk(descriptor.get_original_first_thunk())? ==
+                self.read_thunk(descriptor.get_first_thunk())? {
+                status = BindingStatus::UnboundIat;
+            }
+
+            bindings.push(ImportBinding {
+                module,
+                descriptor_time_date_stamp: Some(time_date_stamp),
+                bound_time_date_stamp,
+                status
+            });
+        }
+
+        for descriptor in bound {
+            if !bindings.iter().any(|t|
+                t.get_module().eq_ignore_ascii_case(descriptor.get_module())) {
+                bindings.push(ImportBinding {
+                    module: descriptor.get_module().to_string(),
+                    descriptor_time_date_stamp: None,
+                    bound_time_date_stamp:
+                        Some(descriptor.get_time_date_stamp()),
+                    status: BindingStatus::UnusedBoundEntry
+                });
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    fn read_thunk(&self, rva: u32) -> Result<u64, ButylError> {
+        if self.is_64_bit() {
+            read_le_u64(self.read_rva(rva, 8)?, 0)
+        } else {
+            Ok(read_le_u32(self.read_rva(rva, 4)?, 0)? as u64)
+        }
+    }
+
+    fn to_rva(&self, address: u64, va_based: bool) ->
+        Result<u32, ButylError> {
+        if va_based {
+            self.va_to_rva(address)
+        } else {
+            Ok(address as u32)
+        }
+    }
+
+    fn read_thunks(&self, module: &str, lookup: u32, iat: u32,
+        va_based: bool, delayed: bool,
+        imports: &mut Vec<Import>) -> Result<(), ButylError> {
+        let (thunk_len, ordinal_flag): (u32, u64) = if self.is_64_bit() {
+            (8, IMPORT_ORDINAL_FLAG_64)
+        } else {
+            (4, IMPORT_ORDINAL_FLAG_32)
+        };
+
+        let mut index: u32 = 0;
+
+        loop {
+            let offset: u32 = index.checked_mul(thunk_len)
+                .ok_or(ButylError::MalformedDataError)?;
+            let value: u64 = self.read_thunk(lookup.checked_add(offset)
+                .ok_or(ButylError::MalformedDataError)?)?;
+
+            if value == 0 {
+                break;
+            }
+
+            let mut import: Import = Import {
+                module: module.to_string(),
+                name: None,
+                hint: 0,
+                ordinal: None,
+                thunk: iat.checked_add(offset)
+                    .ok_or(ButylError::MalformedDataError)?,
+                delayed
+            };
+
+            if value & ordinal_flag != 0 {
+                import.ordinal = Some((value & 0xffff) as u16);
+            } else {
+                let rva: u32 = self.to_rva(value & 0x7fff_ffff, va_based)?;
+
+                import.hint = read_le_u16(self.read_rva(rva, 2)?, 0)?;
+                import.name = Some(self.read_cstring_at_rva(rva
+                    .checked_add(2).ok_or(ButylError::MalformedDataError)?)?);
+            }
+
+            imports.push(import);
+            index += 1;
+        }
+
+        Ok(())
+    }
+}
+
@@ -0,0 +1,624 @@
+use std::result::Result;
+
+use crate::coff::CoffHeaderMachineType;
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::pe::debug::DebugType;
+use crate::utils::*;
+
+pub const IMAGE_GUARD_CF_INSTRUMENTED: u32 = 0x0000_0100;
+pub const IMAGE_GUARD_CFW_INSTRUMENTED: u32 = 0x0000_0200;
+pub const IMAGE_GUARD_CF_FUNCTION_TABLE_PRESENT: u32 = 0x0000_0400;
+pub const IMAGE_GUARD_SECURITY_COOKIE_UNUSED: u32 = 0x0000_0800;
+pub const IMAGE_GUARD_PROTECT_DELAYLOAD_IAT: u32 = 0x0000_1000;
+pub const IMAGE_GUARD_DELAYLOAD_IAT_IN_ITS_OWN_SECTION: u32 = 0x0000_2000;
+pub const IMAGE_GUARD_CF_EXPORT_SUPPRESSION_INFO_PRESENT: u32 = 0x0000_4000;
+pub const IMAGE_GUARD_CF_ENABLE_EXPORT_SUPPRESSION: u32 = 0x0000_8000;
+pub const IMAGE_GUARD_CF_LONGJUMP_TABLE_PRESENT: u32 = 0x0001_0000;
+pub const IMAGE_GUARD_RF_INSTRUMENTED: u32 = 0x0002_0000;
+pub const IMAGE_GUARD_RF_ENABLE: u32 = 0x0004_0000;
+pub const IMAGE_GUARD_RF_STRICT: u32 = 0x0008_0000;
+pub const IMAGE_GUARD_RETPOLINE_PRESENT: u32 = 0x0010_0000;
+pub const IMAGE_GUARD_EH_CONTINUATION_TABLE_PRESENT: u32 = 0x0040_0000;
+pub const IMAGE_GUARD_XFG_ENABLED: u32 = 0x0080_0000;
+pub const IMAGE_GUARD_CASTGUARD_PRESENT: u32 = 0x0100_0000;
+pub const IMAGE_GUARD_MEMCPY_PRESENT: u32 = 0x0200_0000;
+pub const IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_MASK: u32 = 0xf000_0000;
+pub const IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_SHIFT: u32 = 28;
+
+pub const IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA: u16 = 0x0020;
+pub const IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE: u16 = 0x0040;
+pub const IMAGE_DLLCHARACTERISTICS_FORCE_INTEGRITY: u16 = 0x0080;
+pub const IMAGE_DLLCHARACTERISTICS_NX_COMPAT: u16 = 0x0100;
+pub const IMAGE_DLLCHARACTERISTICS_NO_SEH: u16 = 0x0400;
+pub const IMAGE_DLLCHARACTERISTICS_GUARD_CF: u16 = 0x4000;
+pub const IMAGE_DLLCHARACTERISTICS_EX_CET_COMPAT: u32 = 0x0001;
+
+/* the structure has grown with almost every Windows release and the linker
+ * records how much of it is present in `size`; anything past that is left
+ * zeroed.  Pointer-sized fields are widened to 64 bits */
+#[derive(Debug, Default, Clone)]
+pub struct LoadConfigDirectory {
+    size: u32,
+    time_date_stamp: u32,
+    major_version: u16,
+    minor_version: u16,
+    global_flags_clear: u32,
+    global_flags_set: u32,
+    critical_section_default_timeout: u32,
+    de_commit_free_block_threshold: u64,
+    de_commit_total_free_threshold: u64,
+    lock_prefix_table: u64,
+    maximum_allocation_size: u64,
+    virtual_memory_threshold: u64,
+    process_heap_flags: u32,
+    process_affinity_mask: u64,
+    csd_version: u16,
+    dependent_load_flags: u16,
+    edit_list: u64,
+    security_cookie: u64,
+    se_handler_table: u64,
+    se_handler_count: u64,
+    guard_cf_check_function_pointer: u64,
+    guard_cf_dispatch_function_pointer: u64,
+    guard_cf_function_table: u64,
+    guard_cf_function_count: u64,
+    guard_flags: u32,
+    code_integrity_flags: u16,
+    code_integrity_catalog: u16,
+    code_integrity_catalog_offset: u32,
+    guard_address_taken_iat_entry_table: u64,
+    guard_address_taken_iat_entry_count: u64,
+    guard_long_jump_target_table: u64,
+    guard_long_jump_target_count: u64,
+    dynamic_value_reloc_table: u64,
+    chpe_metadata_pointer: u64,
+    guard_rf_failure_routine: u64,
+    guard_rf_failure_routine_function_pointer: u64,
+    dynamic_value_reloc_table_offset: u32,
+    dynamic_value_reloc_table_section: u16,
+    guard_rf_verify_stack_pointer_function_pointer: u64,
+    hot_patch_table_offset: u32,
+    enclave_configuration_pointer: u64,
+    volatile_metadata_pointer: u64,
+    guard_eh_continuation_table: u64,
+    guard_eh_continuation_count: u64,
+    guard_xfg_check_function_pointer: u64,
+    guard_xfg_dispatch_function_pointer: u64,
+    guard_xfg_table_dispatch_function_pointer: u64,
+    cast_guard_os_determined_failure_mode: u64,
+    guard_memcpy_function_pointer: u64
+}
+
+impl LoadConfigDirectory {
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn get_minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn get_global_flags_clear(&self) -> u32 {
+        self.global_flags_clear
+    }
+
+    pub fn get_global_flags_set(&self) -> u32 {
+        self.global_flags_set
+    }
+
+    pub fn get_critical_section_default_timeout(&self) -> u32 {
+        self.critical_section_default_timeout
+    }
+
+    pub fn get_de_commit_free_block_threshold(&self) -> u64 {
+        self.de_commit_free_block_threshold
+    }
+
+    pub fn get_de_commit_total_free_threshold(&self) -> u64 {
+        self.de_commit_total_free_threshold
+    }
+
+    pub fn get_lock_prefix_table(&self) -> u64 {
+        self.lock_prefix_table
+    }
+
+    pub fn get_maximum_allocation_size(&self) -> u64 {
+        self.maximum_allocation_size
+    }
+
+    pub fn get_virtual_memory_threshold(&self) -> u64 {
+        self.virtual_memory_threshold
+    }
+
+    pub fn get_process_heap_flags(&self) -> u32 {
+        self.process_heap_flags
+    }
+
+    pub fn get_process_affinity_mask(&self) -> u64 {
+        self.process_affinity_mask
+    }
+
+    pub fn get_csd_version(&self) -> u16 {
+        self.csd_version
+    }
+
+    pub fn get_dependent_load_flags(&self) -> u16 {
+        self.dependent_load_flags
+    }
+
+    pub fn get_edit_list(&self) -> u64 {
+        self.edit_list
+    }
+
+    pub fn get_security_cookie(&self) -> u64 {
+        self.security_cookie
+    }
+
+    pub fn get_se_handler_table(&self) -> u64 {
+        self.se_handler_table
+    }
+
+    pub fn get_se_handler_count(&self) -> u64 {
+        self.se_handler_count
+    }
+
+    pub fn get_guard_cf_check_function_pointer(&self) -> u64 {
+        self.guard_cf_check_function_pointer
+    }
+
+    pub fn get_guard_cf_dispatch_function_pointer(&self) -> u64 {
+        self.guard_cf_dispatch_function_pointer
+    }
+
+    pub fn get_guard_cf_function_table(&self) -> u64 {
+        self.guard_cf_function_table
+    }
+
+    pub fn get_guard_cf_function_count(&self) -> u64 {
+        self.guard_cf_function_count
+    }
+
+    pub fn get_guard_flags(&self) -> u32 {
+        self.guard_flags
+    }
+
+    pub fn get_code_integrity_flags(&self) -> u16 {
+        self.code_integrity_flags
+    }
+
+    pub fn get_code_integrity_catalog(&self) -> u16 {
+        self.code_integrity_catalog
+    }
+
+    pub fn get_code_integrity_catalog_offset(&self) -> u32 {
+        self.code_integrity_catalog_offset
+    }
+
+    pub fn get_guard_address_taken_iat_entry_table(&self) -> u64 {
+        self.guard_address_taken_iat_entry_table
+    }
+
+    pub fn get_guard_address_taken_iat_entry_count(&self) -> u64 {
+        self.guard_address_taken_iat_entry_count
+    }
+
+    pub fn get_guard_long_jump_target_table(&self) -> u64 {
+        self.guard_long_jump_target_table
+    }
+
+    pub fn get_guard_long_jump_target_count(&self) -> u64 {
+        self.guard_long_jump_target_count
+    }
+
+    pub fn get_dynamic_value_reloc_table(&self) -> u64 {
+        self.dynamic_value_reloc_table
+    }
+
+    pub fn get_chpe_metadata_pointer(&self) -> u64 {
+        self.chpe_metadata_pointer
+    }
+
+    pub fn get_guard_rf_failure_routine(&self) -> u64 {
+        self.guard_rf_failure_routine
+    }
+
+    pub fn get_guard_rf_failure_routine_function_pointer(&self) -> u64 {
+        self.guard_rf_failure_routine_function_pointer
+    }
+
+    pub fn get_dynamic_value_reloc_table_offset(&self) -> u32 {
+        self.dynamic_value_reloc_table_offset
+    }
+
+    pub fn get_dynamic_value_reloc_table_section(&self) -> u16 {
+        self.dynamic_value_reloc_table_section
+    }
+
+    pub fn get_guard_rf_verify_stack_pointer_function_pointer(&self) -> u64 {
+        self.guard_rf_verify_stack_pointer_function_pointer
+    }
+
+    pub fn get_hot_patch_table_offset(&self) -> u32 {
+        self.hot_patch_table_offset
+    }
+
+    pub fn get_enclave_configuration_pointer(&self) -> u64 {
+        self.enclave_configuration_pointer
+    }
+
+    pub fn get_volatile_metadata_pointer(&self) -> u64 {
+        self.volatile_metadata_pointer
+    }
+
+    pub fn get_guard_eh_continuation_table(&self) -> u64 {
+        self.guard_eh_continuation_table
+    }
+
+    pub fn get_guard_eh_continuation_count(&self) -> u64 {
+        self.guard_eh_continuation_count
+    }
+
+    pub fn get_guard_xfg_check_function_pointer(&self) -> u64 {
+        self.guard_xfg_check_function_pointer
+    }
+
+    pub fn get_guard_xfg_dispatch_function_pointer(&self) -> u64 {
+        self.guard_xfg_dispatch_function_pointer
+    }
+
+    pub fn get_guard_xfg_table_dispatch_function_pointer(&self) -> u64 {
+        self.guard_xfg_table_dispatch_function_pointer
+    }
+
+    pub fn get_cast_guard_os_determined_failure_mode(&self) -> u64 {
+        self.cast_guard_os_determined_failure_mode
+    }
+
+    pub fn get_guard_memcpy_function_pointer(&self) -> u64 {
+        self.guard_memcpy_function_pointer
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8], is_64_bit: bool) ->
+        Result<LoadConfigDirectory, ButylError> {
+        let mut load_config: LoadConfigDirectory =
+            LoadConfigDirectory::default();
+
+        load_config.size = read_le_u32(bytes, 0)?;
+
+        let fields: LoadConfigReader = LoadConfigReader {
+            bytes,
+            size: (load_config.size as usize).min(bytes.len()),
+            is_64_bit
+        };
+
+        load_config.time_date_stamp = fields.read_u32(4, 4)?;
+        load_config.major_version = fields.read_u16(8, 8)?;
+        load_config.minor_version = fields.read_u16(10, 10)?;
+        load_config.global_flags_clear = fields.read_u32(12, 12)?;
+        load_config.global_flags_set = fields.read_u32(16, 16)?;
+        load_config.critical_section_default_timeout = fields.read_u32(20, 20)?;
+        load_config.de_commit_free_block_threshold =
+            fields.read_pointer(24, 24)?;
+        load_config.de_commit_total_free_threshold =
+            fields.read_pointer(28, 32)?;
+        load_config.lock_prefix_table = fields.read_pointer(32, 40)?;
+        load_config.maximum_allocation_size = fields.read_pointer(36, 48)?;
+        load_config.virtual_memory_threshold = fields.read_pointer(40, 56)?;
+        load_config.process_heap_flags = fields.read_u32(44, 72)?;
+        load_config.process_affinity_mask = fields.read_pointer(48, 64)?;
+        load_config.csd_version = fields.read_u16(52, 76)?;
+        load_config.dependent_load_flags = fields.read_u16(54, 78)?;
+        load_config.edit_list = fields.read_pointer(56, 80)?;
+        load_config.security_cookie = fields.read_pointer(60, 88)?;
+        load_config.se_handler_table = fields.read_pointer(64, 96)?;
+        load_config.se_handler_count = fields.read_pointer(68, 104)?;
+        load_config.guard_cf_check_function_pointer =
+            fields.read_pointer(72, 112)?;
+        load_config.guard_cf_dispatch_function_pointer =
+            fields.read_pointer(76, 120)?;
+        load_config.guard_cf_function_table = fields.read_pointer(80, 128)?;
+        load_config.guard_cf_function_count = fields.read_pointer(84, 136)?;
+        load_config.guard_flags = fields.read_u32(88, 144)?;
+        load_config.code_integrity_flags = fields.read_u16(92, 148)?;
+        load_config.code_integrity_catalog = fields.read_u16(94, 150)?;
+        load_config.code_integrity_catalog_offset = fields.read_u32(96, 152)?;
+        load_config.guard_address_taken_iat_entry_table =
+            fields.read_pointer(104, 160)?;
+        load_config.guard_address_taken_iat_entry_count =
+            fields.read_pointer(108, 168)?;
+        load_config.guard_long_jump_target_table =
+            fields.read_pointer(112, 176)?;
+        load_config.guard_long_jump_target_count =
+            fields.read_pointer(116, 184)?;
+        load_config.dynamic_value_reloc_table = fields.read_pointer(120, 192)?;
+        load_config.chpe_metadata_pointer = fields.read_pointer(124, 200)?;
+        load_config.guard_rf_failure_routine = fields.read_pointer(128, 208)?;
+        load_config.guard_rf_failure_routine_function_pointer =
+            fields.read_pointer(132, 216)?;
+        load_config.dynamic_value_reloc_table_offset =
+            fields.read_u32(136, 224)?;
+        load_config.dynamic_value_reloc_table_section =
+            fields.read_u16(140, 228)?;
+        load_config.guard_rf_verify_stack_pointer_function_pointer =
+            fields.read_pointer(144, 232)?;
+        load_config.hot_patch_table_offset = fields.read_u32(148, 240)?;
+        load_config.enclave_configuration_pointer =
+            fields.read_pointer(156, 248)?;
+        load_config.volatile_metadata_pointer = fields.read_pointer(160, 256)?;
+        load_config.guard_eh_continuation_table =
+            fields.read_pointer(164, 264)?;
+        load_config.guard_eh_continuation_count =
+            fields.read_pointer(168, 272)?;
+        load_config.guard_xfg_check_function_pointer =
+            fields.read_pointer(172, 280)?;
+        load_config.guard_xfg_dispatch_function_pointer =
+            fields.read_pointer(176, 288)?;
+        load_config.guard_xfg_table_dispatch_function_pointer =
+            fields.read_pointer(180, 296)?;
+        load_config.cast_guard_os_determined_failure_mode =
+            fields.read_pointer(184, 304)?;
+        load_config.guard_memcpy_function_pointer =
+            fields.read_pointer(188, 312)?;
+
+        Ok(load_config)
+    }
+}
+/* reads a field at its PE32 or PE32+ offset, yielding zero for fields that
+ * lie beyond the recorded structure size */
+struct LoadConfigReader<'a> {
+    bytes: &'a[u8],
+    size: usize,
+    is_64_bit: bool
+}
+
+impl<'a> LoadConfigReader<'a> {
+    fn get_offset(&self, offset_32: usize, offset_64: usize, width: usize) ->
+        Option<usize> {
+        let offset: usize = if self.is_64_bit { offset_64 } else { offset_32 };
+
+        if offset + width <= self.size {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    fn read_u16(&self, offset_32: usize, offset_64: usize) ->
+        Result<u16, ButylError> {
+        match self.get_offset(offset_32, offset_64, 2) {
+            Some(t) => read_le_u16(self.bytes, t),
+            None => Ok(0)
+        }
+    }
+
+    fn read_u32(&self, offset_32: usize, offset_64: usize) ->
+        Result<u32, ButylError> {
+        match self.get_offset(offset_32, offset_64, 4) {
+            Some(t) => read_le_u32(self.bytes, t),
+            None => Ok(0)
+        }
+    }
+
+    fn read_pointer(&self, offset_32: usize, offset_64: usize) ->
+        Result<u64, ButylError> {
+        if self.is_64_bit {
+            match self.get_offset(offset_32, offset_64, 8) {
+                Some(t) => read_le_u64(self.bytes, t),
+                None => Ok(0)
+            }
+        } else {
+            Ok(self.read_u32(offset_32, offset_64)? as u64)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GuardFunction {
+    rva: u32,       /* start of a valid indirect call target */
+    flags: u8       /* IMAGE_GUARD_FLAG_* metadata, if any */
+}
+
+impl GuardFunction {
+    pub fn get_rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HardeningSummary {
+    dynamic_base: bool,         /* ASLR */
+    high_entropy_va: bool,      /* 64-bit ASLR */
+    nx_compat: bool,            /* DEP */
+    force_integrity: bool,      /* signature required to load */
+    no_seh: bool,               /* no structured exception handlers */
+    safe_seh: Option<bool>,     /* SafeSEH table (x86 only) */
+    security_cookie: bool,      /* /GS */
+    control_flow_guard: bool,   /* CFG */
+    return_flow_guard: bool,    /* RFG */
+    eh_continuation: bool,      /* EH continuation metadata */
+    xfg: bool,                  /* extended flow guard */
+    cet_compat: bool            /* CET shadow stack compatible */
+}
+
+impl HardeningSummary {
+    pub fn is_dynamic_base(&self) -> bool {
+        self.dynamic_base
+    }
+
+    pub fn is_high_entropy_va(&self) -> bool {
+        self.high_entropy_va
+    }
+
+    pub fn is_nx_compat(&self) -> bool {
+        self.nx_compat
+    }
+
+    pub fn is_force_integrity(&self) -> bool {
+        self.force_integrity
+    }
+
+    pub fn is_no_seh(&self) -> bool {
+        self.no_seh
+    }
+
+    pub fn is_safe_seh(&self) -> Option<bool> {
+        self.safe_seh
+    }
+
+    pub fn has_security_cookie(&self) -> bool {
+        self.security_cookie
+    }
+
+    pub fn is_control_flow_guard(&self) -> bool {
+        self.control_flow_guard
+    }
+
+    pub fn is_return_flow_guard(&self) -> bool {
+        self.return_flow_guard
+    }
+
+    pub fn is_eh_continuation(&self) -> bool {
+        self.eh_continuation
+    }
+
+    pub fn is_xfg(&self) -> bool {
+        self.xfg
+    }
+
+    pub fn is_cet_compat(&self) -> bool {
+        self.cet_compat
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_load_config(&self) ->
+        Result<Option<LoadConfigDirectory>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::LoadConfig) {
+                Some(t) => t,
+                None => return Ok(None)
+            };
+
+        /* trust the structure's own size over the data directory's, which
+         * older linkers set to a fixed value */
+        let size: u32 = read_le_u32(self.read_rva(
+            directory.get_virtual_address(), 4)?, 0)?;
+
+        Ok(Some(LoadConfigDirectory::from_le_bytes(
+            self.read_rva(directory.get_virtual_address(), size as usize)?,
+            self.is_64_bit())?))
+    }
+
+    pub fn get_safe_seh_handlers(&self) -> Result<Vec<u32>, ButylError> {
+        let load_config: LoadConfigDirectory = match self.get_load_config()? {
+            Some(t) => t,
+            None => return Ok(Vec::new())
+        };
+
+        if load_config.get_se_handler_table() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let table: u32 = self.va_to_rva(load_config.get_se_handler_table())?;
+        let count: usize = load_config.get_se_handler_count() as usize;
+
+        self.read_rva(table, count.checked_mul(4)
+            .ok_or(ButylError::MalformedDataError)?)?
+            .chunks_exact(4)
+            .map(|t| read_le_u32(t, 0))
+            .collect()
+    }
+
+    pub fn get_guard_cf_functions(&self) ->
+        Result<Vec<GuardFunction>, ButylError> {
+        let load_config: LoadConfigDirectory = match self.get_load_config()? {
+            Some(t) => t,
+            None => return Ok(Vec::new())
+        };
+
+        if load_config.get_guard_cf_function_table() == 0 {
+            return Ok(Vec::new());
+        }
+
+        /* each RVA may be followed by a few bytes of metadata */
+        let stride: usize = 4 + ((load_config.get_guard_flags() &
+            IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_MASK) >>
+            IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_SHIFT) as usize;
+        let table: u32 =
+            self.va_to_rva(load_config.get_guard_cf_function_table())?;
+        let count: usize = load_config.get_guard_cf_function_count() as usize;
+
+        self.read_rva(table, count.checked_mul(stride)
+            .ok_or(ButylError::MalformedDataError)?)?
+            .chunks_exact(stride)
+            .map(|t| Ok(GuardFunction {
+                rva: read_le_u32(t, 0)?,
+                flags: if stride > 4 { t[4] } else { 0 }
+            }))
+            .collect()
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn get_hardening_summary(&self) ->
+        Result<HardeningSummary, ButylError> {
+        let dll_characteristics: u16 =
+            self.get_optional_header().get_dll_characteristics();
+        let load_config: LoadConfigDirectory = self.get_load_config()?
+            .unwrap_or_default();
+        let guard_flags: u32 = load_config.get_guard_flags();
+
+        let mut summary: HardeningSummary = HardeningSummary::default();
+
+        summary.dynamic_base =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE != 0;
+        summary.high_entropy_va =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA != 0;
+        summary.nx_compat =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_NX_COMPAT != 0;
+        summary.force_integrity =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_FORCE_INTEGRITY != 0;
+        summary.no_seh =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_NO_SEH != 0;
+
+        if self.get_coff_header().get_machine_as_enum() ==
+            CoffHeaderMachineType::I386 {
+            summary.safe_seh = Some(load_config.get_se_handler_table() != 0);
+        }
+
+        summary.security_cookie = load_config.get_security_cookie() != 0 &&
+            guard_flags & IMAGE_GUARD_SECURITY_COOKIE_UNUSED == 0;
+        summary.control_flow_guard =
+            dll_characteristics & IMAGE_DLLCHARACTERISTICS_GUARD_CF != 0 &&
+            guard_flags & IMAGE_GUARD_CF_INSTRUMENTED != 0;
+        summary.return_flow_guard = guard_flags & IMAGE_GUARD_RF_ENABLE != 0;
+        summary.eh_continuation =
+            guard_flags & IMAGE_GUARD_EH_CONTINUATION_TABLE_PRESENT != 0;
+        summary.xfg = guard_flags & IMAGE_GUARD_XFG_ENABLED != 0;
+
+        for entry in self.get_debug_directories()? {
+            if entry.get_debug_type_as_enum() ==
+                DebugType::ExDllCharacteristics {
+                let flags: u32 = read_le_u32(self.get_debug_data(&entry)?, 0)?;
+                summary.cet_compat =
+                    flags & IMAGE_DLLCHARACTERISTICS_EX_CET_COMPAT != 0;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::result::Result;
+
+use serde::Serialize;
+
+use crate::errors::*;
+use crate::pe::PeFile;
+use crate::pe::resources::{ResourceDirectory, ResourceType};
+
+pub const SUPPORTED_OS_VISTA: &str = "{e2011457-1546-43c5-a5fe-008deee3d3f0}";
+pub const SUPPORTED_OS_WIN7: &str = "{35138b9a-5d96-4fbd-8e2d-a2440225f93a}";
+pub const SUPPORTED_OS_WIN8: &str = "{4a2f28e3-53b9-4441-ba9c-d69d4a4a6e38}";
+pub const SUPPORTED_OS_WIN81: &str = "{1f676c76-80e1-4239-95bb-83d0f6d0da78}";
+pub const SUPPORTED_OS_WIN10: &str = "{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AssemblyIdentity {
+    name: Option<String>,
+    version: Option<String>,
+    #[serde(rename = "type")]
+    assembly_type: Option<String>,
+    processor_architecture: Option<String>,
+    public_key_token: Option<String>,
+    language: Option<String>
+}
+
+impl AssemblyIdentity {
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn get_version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn get_assembly_type(&self) -> Option<&str> {
+        self.assembly_type.as_deref()
+    }
+
+    pub fn get_processor_architecture(&self) -> Option<&str> {
+        self.processor_architecture.as_deref()
+    }
+
+    pub fn get_public_key_token(&self) -> Option<&str> {
+        self.public_key_token.as_deref()
+    }
+
+    pub fn get_language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    fn from_attributes(attributes: &HashMap<String, String>) ->
+        AssemblyIdentity {
+        AssemblyIdentity {
+            name: attributes.get("name").cloned(),
+            version: attributes.get("version").cloned(),
+            assembly_type: attributes.get("type").cloned(),
+            processor_architecture:
+                attributes.get("processorArchitecture").cloned(),
+            public_key_token: attributes.get("publicKeyToken").cloned(),
+            language: attributes.get("language").cloned()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Manifest {
+    assembly_identity: Option<AssemblyIdentity>,
+    requested_execution_level: Option<String>,
+    ui_access: Option<bool>,
+    dpi_aware: Option<String>,
+    dpi_awareness: Option<String>,
+    supported_os: Vec<String>,
+    dependent_assemblies: Vec<AssemblyIdentity>
+}
+
+impl Manifest {
+    pub fn get_assembly_identity(&self) -> Option<&AssemblyIdentity> {
+        self.assembly_identity.as_ref()
+    }
+
+    pub fn get_requested_execution_level(&self) -> Option<&str> {
+        self.requested_execution_level.as_deref()
+    }
+
+    pub fn get_ui_access(&self) -> Option<bool> {
+        self.ui_access
+    }
+
+    pub fn get_dpi_aware(&self) -> Option<&str> {
+        self.dpi_aware.as_deref()
+    }
+
+    pub fn get_dpi_awareness(&self) -> Option<&str> {
+        self.dpi_awareness.as_deref()
+    }
+
+    pub fn get_supported_os(&self) -> &[String] {
+        &self.supported_os
+    }
+
+    pub fn get_dependent_assemblies(&self) -> &[AssemblyIdentity] {
+        &self.dependent_assemblies
+    }
+
+    pub fn from_string(xml: &str) -> Result<Manifest, ButylError> {
+        let mut manifest: Manifest = Manifest::default();
+        let mut stack: Vec<String> = Vec::new();
+        let mut text: String = String::new();
+
+        for token in tokenize(xml)? {
+            match token {
+                XmlToken::Text(t) => text.push_str(&t),
+                XmlToken::End(name) => {
+                    match name.as_str() {
+                        "dpiAware" => manifest.dpi_aware =
+                            Some(text.trim().to_string()),
+                        "dpiAwareness" => manifest.dpi_awareness =
+                            Some(text.trim().to_string()),
+                        _ => {}
+                    }
+
+                    if let Some(t) = stack.iter().rposition(|t| *t == name) {
+                        stack.truncate(t);
+                    }
+                },
+                XmlToken::Start(name, attributes, empty) => {
+                    text.clear();
+
+                    match name.as_str() {
+                        "requestedExecutionLevel" => {
+                            manifest.requested_execution_level =
+                                attributes.get("level").cloned();
+                            manifest.ui_access = attributes.get("uiAccess")
+                                .map(|t| t.eq_ignore_ascii_case("true"));
+                        },
+                        "supportedOS" => {
+                            if let Some(t) = attributes.get("Id") {
+                                manifest.supported_os.push(t.clone());
+                            }
+                        },
+                        "assemblyIdentity" => {
+                            let identity: AssemblyIdentity =
+                                AssemblyIdentity::from_attributes(&attributes);
+
+                            if stack.iter().any(|t| t == "dependentAssembly") {
+                                manifest.dependent_assemblies.push(identity);
+                            } else if stack.len() == 1 {
+                                manifest.assembly_identity = Some(identity);
+                            }
+                        },
+                        _ => {}
+                    }
+
+                    if !empty {
+                        stack.push(name);
+                    }
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+pub fn get_supported_os_name(id: &str) -> Option<&'static str> {
+    match id.to_ascii_lowercase().as_str() {
+        SUPPORTED_OS_VISTA => Some("Windows Vista"),
+        SUPPORTED_OS_WIN7 => Some("Windows 7"),
+        SUPPORTED_OS_WIN8 => Some("Windows 8"),
+        SUPPORTED_OS_WIN81 => Some("Windows 8.1"),
+        SUPPORTED_OS_WIN10 => Some("Windows 10/11"),
+        _ => None
+    }
+}
+
+enum XmlToken {
+    Start(String, HashMap<String, String>, bool),
+    End(String),
+    Text(String)
+}
+
+/* only as much XML as manifests need: elements, attributes and text, with
+ * namespace prefixes dropped and comments, declarations and CDATA skipped */
+fn tokenize(xml: &str) -> Result<Vec<XmlToken>, ButylError> {
+    let mut tokens: Vec<XmlToken> = Vec::new();
+    let mut rest: &str = xml;
+
+    while !rest.is_empty() {
+        let start: usize = match rest.find('<') {
+            Some(t) => t,
+            None => {
+                tokens.push(XmlToken::Text(rest.to_string()));
+                break;
+            }
+        };
+
+        if start > 0 {
+            tokens.push(XmlToken::Text(rest[..start].to_string()));
+        }
+
+        rest = &rest[start..];
+
+        let terminator: &str = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+
+        let end: usize = match rest.find(terminator) {
+            Some(t) => t + terminator.len(),
+            None => return Err(ButylError::MalformedDataError)
+        };
+        let tag: &str = &rest[1..end - 1];
+
+        rest = &rest[end..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(t) = tag.strip_prefix('/') {
+            tokens.push(XmlToken::End(local_name(t.trim()).to_string()));
+            continue;
+        }
+
+        let empty: bool = tag.ends_with('/');
+        let tag: &str = tag.trim_end_matches('/');
+        let name_end: usize = tag.find(char::is_whitespace)
+            .unwrap_or(tag.len());
+
+        tokens.push(XmlToken::Start(local_name(&tag[..name_end]).to_string(),
+            parse_attributes(&tag[name_end..]), empty));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_attributes(string: &str) -> HashMap<String, String> {
+    let mut attributes: HashMap<String, String> = HashMap::new();
+    let mut rest: &str = string;
+
+    while let Some(equals) = rest.find('=') {
+        let name: &str = rest[..equals].trim();
+        let value: &str = rest[equals + 1..].trim_start();
+
+        let quote: char = match value.chars().next() {
+            Some(t) if t == '"' || t == '\'' => t,
+            _ => break
+        };
+
+        let close: usize = match value[1..].find(quote) {
+            Some(t) => t + 1,
+            None => break
+        };
+
+        attributes.insert(local_name(name).to_string(),
+            value[1..close].to_string());
+        rest = &value[close + 1..];
+    }
+
+    attributes
+}
+
+fn local_name(name: &str) -> &str {
+    match name.rfind(':') {
+        Some(t) => &name[t + 1..],
+        None => name
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_manifest(&self) -> Result<Option<Manifest>, ButylError> {
+        let resources: ResourceDirectory = match self.get_resources()? {
+            Some(t) => t,
+            None => return Ok(None)
+        };
+
+        let data: &[u8] = match resources.find_by_type(ResourceType::Manifest)
+            .first() {
+            Some(t) => t.get_data(),
+            None => return Ok(None)
+        };
+
+        /* manifests are normally UTF-8, occasionally with a byte order mark */
+        let xml: String = String::from_utf8_lossy(data)
+            .trim_start_matches('\u{feff}')
+            .to_string();
+
+        Ok(Some(Manifest::from_string(&xml)?))
+    }
+}
+
@@ -0,0 +1,391 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::coff::CoffHeaderMachineType;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const BASE_RELOCATION_BLOCK_HEADER_LEN: usize = 8;
+pub const BASE_RELOCATION_ENTRY_LEN: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BaseRelocationType {
+    Absolute,
+    High,
+    Low,
+    HighLow,
+    HighAdj,
+    MipsJmpAddr,
+    ArmMov32,
+    RiscvHigh20,
+    ThumbMov32,
+    RiscvLow12I,
+    RiscvLow12S,
+    MipsJmpAddr16,
+    Ia64Imm64,
+    Dir64,
+    Unknown(u8)
+}
+
+impl BaseRelocationType {
+    pub fn from_value(value: u8, machine: CoffHeaderMachineType) ->
+        BaseRelocationType {
+        let mips: bool = matches!(machine,
+            CoffHeaderMachineType::R4000 |
+            CoffHeaderMachineType::MIPS16 |
+            CoffHeaderMachineType::MIPSFPU |
+            CoffHeaderMachineType::MIPSFPU16 |
+            CoffHeaderMachineType::WCEMIPSV2);
+        let arm: bool = matches!(machine,
+            CoffHeaderMachineType::ARM |
+            CoffHeaderMachineType::ARMNT |
+            CoffHeaderMachineType::Thumb);
+        let riscv: bool = matches!(machine,
+            CoffHeaderMachineType::RISCV32 |
+            CoffHeaderMachineType::RISCV64 |
+            CoffHeaderMachineType::RISCV128);
+
+        /* types 5 and 7 through 9 are reused across architectures */
+        match value {
+            0 => BaseRelocationType::Absolute,
+            1 => BaseRelocationType::High,
+            2 => BaseRelocationType::Low,
+            3 => BaseRelocationType::HighLow,
+            4 => BaseRelocationType::HighAdj,
+            5 if mips => BaseRelocationType::MipsJmpAddr,
+            5 if arm => BaseRelocationType::ArmMov32,
+            5 if riscv => BaseRelocationType::RiscvHigh20,
+            7 if arm => BaseRelocationType::ThumbMov32,
+            7 if riscv => BaseRelocationType::RiscvLow12I,
+            8 if riscv => BaseRelocationType::RiscvLow12S,
+            9 if mips => BaseRelocationType::MipsJmpAddr16,
+            9 if machine == CoffHeaderMachineType::IA64 =>
+                BaseRelocationType::Ia64Imm64,
+            10 => BaseRelocationType::Dir64,
+            t => BaseRelocationType::Unknown(t)
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            BaseRelocationType::Absolute => String::from("ABSOLUTE"),
+            BaseRelocationType::High => String::from("HIGH"),
+            BaseRelocationType::Low => String::from("LOW"),
+            BaseRelocationType::HighLow => String::from("HIGHLOW"),
+            BaseRelocationType::HighAdj => String::from("HIGHADJ"),
+            BaseRelocationType::MipsJmpAddr => String::from("MIPS_JMPADDR"),
+            BaseRelocationType::ArmMov32 => String::from("ARM_MOV32"),
+            BaseRelocationType::RiscvHigh20 => String::from("RISCV_HIGH20"),
+            BaseRelocationType::ThumbMov32 => String::from("THUMB_MOV32"),
+            BaseRelocationType::RiscvLow12I => String::from("RISCV_LOW12I"),
+            BaseRelocationType::RiscvLow12S => String::from("RISCV_LOW12S"),
+            BaseRelocationType::MipsJmpAddr16 =>
+                String::from("MIPS_JMPADDR16"),
+            BaseRelocationType::Ia64Imm64 => String::from("IA64_IMM64"),
+            BaseRelocationType::Dir64 => String::from("DIR64"),
+            BaseRelocationType::Unknown(t) => format!("UNKNOWN({})", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BaseRelocation {
+    kind: BaseRelocationType,   /* relocation type */
+    offset: u16,                /* offset from the start of the page */
+    parameter: Option<u16>      /* trailing slot used by HIGHADJ */
+}
+
+impl BaseRelocation {
+    pub fn get_kind(&self) -> BaseRelocationType {
+        self.kind
+    }
+
+    pub fn get_offset(&self) -> u16 {
+        self.offset
+    }
+
+    pub fn get_parameter(&self) -> Option<u16> {
+        self.parameter
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BaseRelocationBlock {
+    page_rva: u32,                  /* RVA of the page */
+    block_size: u32,                /* size of block including header */
+    entries: Vec<BaseRelocation>
+}
+
+impl BaseRelocationBlock {
+    pub fn get_page_rva(&self) -> u32 {
+        self.page_rva
+    }
+
+    pub fn get_block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn get_entries(&self) -> &[BaseRelocation] {
+        &self.entries
+    }
+
+    pub fn from_le_bytes(bytes: &[u8], machine: CoffHeaderMachineType) ->
+        Result<BaseRelocationBlock, ButylError> {
+        let page_rva: u32 = read_le_u32(bytes, 0)?;
+        let block_size: u32 = read_le_u32(bytes, 4)?;
+
+        if (block_size as usize) < BASE_RELOCATION_BLOCK_HEADER_LEN {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let count: usize = (block_size as usize -
+            BASE_RELOCATION_BLOCK_HEADER_LEN) / BASE_RELOCATION_ENTRY_LEN;
+        let mut entries: Vec<BaseRelocation> = Vec::new();
+        let mut i: usize = 0;
+
+        while i < count {
+            let raw: u16 = read_le_u16(bytes,
+                BASE_RELOCATION_BLOCK_HEADER_LEN +
+                i * BASE_RELOCATION_ENTRY_LEN)?;
+            let kind: BaseRelocationType =
+                BaseRelocationType::from_value((raw >> 12) as u8, machine);
+
+            let mut entry: BaseRelocation = BaseRelocation {
+                kind,
+                offset: raw & 0x0fff,
+                parameter: None
+            };
+
+            /* HIGHADJ consumes the following slot as its low half */
+            if kind == BaseRelocationType::HighAdj {
+                i += 1;
+                entry.parameter = Some(read_le_u16(bytes,
+                    BASE_RELOCATION_BLOCK_HEADER_LEN +
+                    i * BASE_RELOCATION_ENTRY_LEN)?);
+            }
+
+            entries.push(entry);
+            i += 1;
+        }
+
+        Ok(BaseRelocationBlock {
+            page_rva,
+            block_size,
+            entries
+        })
+    }
+}
+
+/* applies base relocations to an image laid out at its RVAs, as it would be
+ * in memory, moving it from `old_base` to `new_base` */
+pub fn rebase_image(image: &mut [u8], blocks: &[BaseRelocationBlock],
+    old_base: u64, new_base: u64) -> Result<(), ButylError> {
+    let delta: u64 = new_base.wrapping_sub(old_base);
+
+    if delta == 0 {
+        return Ok(());
+    }
+
+    /* RISC-V splits an address across a LUI and a following LOW12 */
+    let mut riscv_high20: Option<usize> = None;
+
+    for block in blocks {
+        for entry in block.get_entries() {
+            let offset: usize = block.get_page_rva() as usize +
+                entry.get_offset() as usize;
+
+            match entry.get_kind() {
+                BaseRelocationType::Absolute => {},
+                BaseRelocationType::High => {
+                    let value: u16 = read_le_u16(image, offset)?;
+                    write_le_u16(image, offset,
+                        value.wrapping_add((delta >> 16) as u16))?;
+                },
+                BaseRelocationType::Low => {
+                    let value: u16 = read_le_u16(image, offset)?;
+                    write_le_u16(image, offset,
+                        value.wrapping_add(delta as u16))?;
+                },
+                BaseRelocationType::HighLow => {
+                    let value: u32 = read_le_u32(image, offset)?;
+                    write_le_u32(image, offset,
+                        value.wrapping_add(delta as u32))?;
+                },
+                BaseRelocationType::HighAdj => {
+                    let low: i16 = entry.get_parameter().unwrap_or(0) as i16;
+                    let value: u32 = ((read_le_u16(image, offset)? as u32)
+                        << 16).wrapping_add(low as i32 as u32)
+                        .wrapping_add(delta as u32)
+                        .wrapping_add(0x8000);
+                    write_le_u16(image, offset, (value >> 16) as u16)?;
+                },
+                BaseRelocationType::Dir64 => {
+                    let value: u64 = read_le_u64(image, offset)?;
+                    write_le_u64(image, offset, value.wrapping_add(delta))?;
+                },
+                BaseRelocationType::MipsJmpAddr => {
+                    let value: u32 = read_le_u32(image, offset)?;
+                    let target: u32 = ((value & 0x03ff_ffff) << 2)
+                        .wrapping_add(delta as u32);
+                    write_le_u32(image, offset,
+                        (value & 0xfc00_0000) | ((target >> 2) & 0x03ff_ffff))?;
+                },
+                BaseRelocationType::ArmMov32 => {
+                    let movw: u32 = read_le_u32(image, offset)?;
+                    let movt: u32 = read_le_u32(image, offset + 4)?;
+                    let value: u32 = ((decode_arm_imm16(movt) as u32) << 16 |
+                        decode_arm_imm16(movw) as u32)
+                        .wrapping_add(delta as u32);
+                    write_le_u32(image, offset,
+                        encode_arm_imm16(movw, value as u16))?;
+                    write_le_u32(image, offset + 4,
+                        encode_arm_imm16(movt, (value >> 16) as u16))?;
+                },
+                BaseRelocationType::ThumbMov32 => {
+                    let movw: u32 = read_thumb_instruction(image, offset)?;
+                    let movt: u32 = read_thumb_instruction(image, offset + 4)?;
+                    let value: u32 = ((decode_thumb_imm16(movt) as u32) << 16 |
+                        decode_thumb_imm16(movw) as u32)
+                        .wrapping_add(delta as u32);
+                    write_thumb_instruction(image, offset,
+                        encode_thumb_imm16(movw, value as u16))?;
+                    write_thumb_instruction(image, offset + 4,
+                        encode_thumb_imm16(movt, (value >> 16) as u16))?;
+                },
+                BaseRelocationType::RiscvHigh20 => {
+                    riscv_high20 = Some(offset);
+                },
+                BaseRelocationType::RiscvLow12I |
+                BaseRelocationType::RiscvLow12S => {
+                    let high_offset: usize = match riscv_high20.take() {
+                        Some(t) => t,
+                        None => return Err(ButylError::MalformedDataError)
+                    };
+                    let store: bool =
+                        entry.get_kind() == BaseRelocationType::RiscvLow12S;
+
+                    apply_riscv_pair(image, high_offset, offset, store,
+                        delta as u32)?;
+                },
+                BaseRelocationType::MipsJmpAddr16 |
+                BaseRelocationType::Ia64Imm64 |
+                BaseRelocationType::Unknown(_) =>
+                    return Err(ButylError::UnsupportedFeatureError)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn decode_arm_imm16(instruction: u32) -> u16 {
+    (((instruction >> 4) & 0xf000) | (instruction & 0x0fff)) as u16
+}
+
+fn encode_arm_imm16(instruction: u32, imm16: u16) -> u32 {
+    (instruction & 0xfff0_f000) |
+        (((imm16 as u32) & 0xf000) << 4) |
+        ((imm16 as u32) & 0x0fff)
+}
+
+/* Thumb-2 instructions are stored as two little-endian halfwords with the
+ * leading halfword first */
+fn read_thumb_instruction(image: &[u8], offset: usize) ->
+    Result<u32, ButylError> {
+    Ok(((read_le_u16(image, offset)? as u32) << 16) |
+        read_le_u16(image, offset + 2)? as u32)
+}
+
+fn write_thumb_instruction(image: &mut [u8], offset: usize,
+    instruction: u32) -> Result<(), ButylError> {
+    write_le_u16(image, offset, (instruction >> 16) as u16)?;
+    write_le_u16(image, offset + 2, instruction as u16)
+}
+
+fn decode_thumb_imm16(instruction: u32) -> u16 {
+    let imm4: u32 = (instruction >> 16) & 0xf;
+    let i: u32 = (instruction >> 26) & 0x1;
+    let imm3: u32 = (instruction >> 12) & 0x7;
+    let imm8: u32 = instruction & 0xff;
+
+    ((imm4 << 12) | (i << 11) | (imm3 << 8) | imm8) as u16
+}
+
+fn encode_thumb_imm16(instruction: u32, imm16: u16) -> u32 {
+    let imm16: u32 = imm16 as u32;
+
+    (instruction & 0xfbf0_8f00) |
+        (((imm16 >> 12) & 0xf) << 16) |
+        (((imm16 >> 11) & 0x1) << 26) |
+        (((imm16 >> 8) & 0x7) << 12) |
+        (imm16 & 0xff)
+}
+
+fn apply_riscv_pair(image: &mut [u8], high_offset: usize, low_offset: usize,
+    store: bool, delta: u32) -> Result<(), ButylError> {
+    let lui: u32 = read_le_u32(image, high_offset)?;
+    let low: u32 = read_le_u32(image, low_offset)?;
+
+    /* I-type keeps imm[11:0] in bits 31:20; S-type splits it into 31:25 and
+     * 11:7 */
+    let low12: u32 = if store {
+        ((low >> 20) & 0xfe0) | ((low >> 7) & 0x1f)
+    } else {
+        low >> 20
+    };
+    let signed_low12: i32 = ((low12 << 20) as i32) >> 20;
+
+    let value: u32 = (lui & 0xffff_f000)
+        .wrapping_add(signed_low12 as u32)
+        .wrapping_add(delta);
+    let new_high: u32 = value.wrapping_add(0x800) & 0xffff_f000;
+    let new_low12: u32 = value & 0xfff;
+
+    write_le_u32(image, high_offset, (lui & 0x0000_0fff) | new_high)?;
+
+    let new_low: u32 = if store {
+        (low & 0x01ff_f07f) | ((new_low12 & 0xfe0) << 20) |
+            ((new_low12 & 0x1f) << 7)
+    } else {
+        (low & 0x000f_ffff) | (new_low12 << 20)
+    };
+
+    write_le_u32(image, low_offset, new_low)
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_base_relocations(&self) ->
+        Result<Vec<BaseRelocationBlock>, ButylError> {
+        let mut blocks: Vec<BaseRelocationBlock> = Vec::new();
+
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::BaseReloc) {
+                Some(t) => t,
+                None => return Ok(blocks)
+            };
+
+        let table: &[u8] = self.read_rva(directory.get_virtual_address(),
+            directory.get_size() as usize)?;
+        let machine: CoffHeaderMachineType =
+            self.get_coff_header().get_machine_as_enum();
+        let mut offset: usize = 0;
+
+        while offset + BASE_RELOCATION_BLOCK_HEADER_LEN <= table.len() {
+            let block: BaseRelocationBlock =
+                BaseRelocationBlock::from_le_bytes(&table[offset..], machine)?;
+
+            offset += block.get_block_size() as usize;
+            blocks.push(block);
+        }
+
+        Ok(blocks)
+    }
+
+    /* rebases an image previously mapped at this file's preferred base */
+    pub fn rebase_image(&self, image: &mut [u8], new_base: u64) ->
+        Result<(), ButylError> {
+        rebase_image(image, &self.get_base_relocations()?,
+            self.get_optional_header().get_image_base(), new_base)
+    }
+}
+
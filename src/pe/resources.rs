@@ -0,0 +1,475 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const RESOURCE_DIRECTORY_LEN: usize = 16;
+pub const RESOURCE_DIRECTORY_ENTRY_LEN: usize = 8;
+pub const RESOURCE_DATA_ENTRY_LEN: usize = 16;
+pub const RESOURCE_NAME_IS_STRING: u32 = 0x8000_0000;
+pub const RESOURCE_DATA_IS_DIRECTORY: u32 = 0x8000_0000;
+
+/* type, name and language */
+pub const RESOURCE_MAX_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceType {
+    Cursor,
+    Bitmap,
+    Icon,
+    Menu,
+    Dialog,
+    String,
+    FontDir,
+    Font,
+    Accelerator,
+    RcData,
+    MessageTable,
+    GroupCursor,
+    GroupIcon,
+    Version,
+    DlgInclude,
+    PlugPlay,
+    Vxd,
+    AniCursor,
+    AniIcon,
+    Html,
+    Manifest
+}
+
+impl ResourceType {
+    pub fn to_value(resource_type: ResourceType) -> u16 {
+        match resource_type {
+            ResourceType::Cursor => 1,
+            ResourceType::Bitmap => 2,
+            ResourceType::Icon => 3,
+            ResourceType::Menu => 4,
+            ResourceType::Dialog => 5,
+            ResourceType::String => 6,
+            ResourceType::FontDir => 7,
+            ResourceType::Font => 8,
+            ResourceType::Accelerator => 9,
+            ResourceType::RcData => 10,
+            ResourceType::MessageTable => 11,
+            ResourceType::GroupCursor => 12,
+            ResourceType::GroupIcon => 14,
+            ResourceType::Version => 16,
+            ResourceType::DlgInclude => 17,
+            ResourceType::PlugPlay => 19,
+            ResourceType::Vxd => 20,
+            ResourceType::AniCursor => 21,
+            ResourceType::AniIcon => 22,
+            ResourceType::Html => 23,
+            ResourceType::Manifest => 24
+        }
+    }
+
+    pub fn from_value(value: u16) -> Option<ResourceType> {
+        match value {
+            1 => Some(ResourceType::Cursor),
+            2 => Some(ResourceType::Bitmap),
+            3 => Some(ResourceType::Icon),
+            4 => Some(ResourceType::Menu),
+            5 => Some(ResourceType::Dialog),
+            6 => Some(ResourceType::String),
+            7 => Some(ResourceType::FontDir),
+            8 => Some(ResourceType::Font),
+            9 => Some(ResourceType::Accelerator),
+            10 => Some(ResourceType::RcData),
+            11 => Some(ResourceType::MessageTable),
+            12 => Some(ResourceType::GroupCursor),
+            14 => Some(ResourceType::GroupIcon),
+            16 => Some(ResourceType::Version),
+            17 => Some(ResourceType::DlgInclude),
+            19 => Some(ResourceType::PlugPlay),
+            20 => Some(ResourceType::Vxd),
+            21 => Some(ResourceType::AniCursor),
+            22 => Some(ResourceType::AniIcon),
+            23 => Some(ResourceType::Html),
+            24 => Some(ResourceType::Manifest),
+            _ => None
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ResourceType::Cursor => "CURSOR",
+            ResourceType::Bitmap => "BITMAP",
+            ResourceType::Icon => "ICON",
+            ResourceType::Menu => "MENU",
+            ResourceType::Dialog => "DIALOG",
+            ResourceType::String => "STRING",
+            ResourceType::FontDir => "FONTDIR",
+            ResourceType::Font => "FONT",
+            ResourceType::Accelerator => "ACCELERATOR",
+            ResourceType::RcData => "RCDATA",
+            ResourceType::MessageTable => "MESSAGETABLE",
+            ResourceType::GroupCursor => "GROUP_CURSOR",
+            ResourceType::GroupIcon => "GROUP_ICON",
+            ResourceType::Version => "VERSION",
+            ResourceType::DlgInclude => "DLGINCLUDE",
+            ResourceType::PlugPlay => "PLUGPLAY",
+            ResourceType::Vxd => "VXD",
+            ResourceType::AniCursor => "ANICURSOR",
+            ResourceType::AniIcon => "ANIICON",
+            ResourceType::Html => "HTML",
+            ResourceType::Manifest => "MANIFEST"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ResourceType> {
+        let name: &str = name.strip_prefix("RT_").unwrap_or(name);
+
+        (1..=24)
+            .filter_map(ResourceType::from_value)
+            .find(|t| t.get_name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceId {
+    Id(u16),
+    Name(String)
+}
+
+impl ResourceId {
+    /* accepts numeric IDs, well-known type names (e.g. `RT_MANIFEST`) when
+     * `is_type` is set, and otherwise treats the input as a string name */
+    pub fn from_string(string: &str, is_type: bool) -> ResourceId {
+        if let Ok(t) = string.parse::<u16>() {
+            return ResourceId::Id(t);
+        }
+
+        if is_type {
+            if let Some(t) = ResourceType::from_name(string) {
+                return ResourceId::Id(ResourceType::to_value(t));
+            }
+        }
+
+        ResourceId::Name(string.to_string())
+    }
+
+    pub fn get_type(&self) -> Option<ResourceType> {
+        match self {
+            ResourceId::Id(t) => ResourceType::from_value(*t),
+            ResourceId::Name(_) => None
+        }
+    }
+}
+
+impl fmt::Display for ResourceId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResourceId::Id(t) => write!(f, "{}", t),
+            ResourceId::Name(t) => write!(f, "\"{}\"", t)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourceData<'a> {
+    offset_to_data: u32,    /* RVA of the resource data */
+    size: u32,              /* size of the resource data */
+    code_page: u32,         /* code page used to decode the data */
+    reserved: u32,          /* (reserved) */
+    data: &'a[u8]
+}
+
+impl<'a> ResourceData<'a> {
+    pub fn get_offset_to_data(&self) -> u32 {
+        self.offset_to_data
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn get_code_page(&self) -> u32 {
+        self.code_page
+    }
+
+    pub fn get_reserved(&self) -> u32 {
+        self.reserved
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    fn from_le_bytes(pe: &PeFile<'a>, section: &'a[u8], offset: usize) ->
+        Result<ResourceData<'a>, ButylError> {
+        let raw: &[u8] = read_bytes(section, offset, RESOURCE_DATA_ENTRY_LEN)?;
+        let offset_to_data: u32 = read_le_u32(raw, 0)?;
+        let size: u32 = read_le_u32(raw, 4)?;
+
+        Ok(ResourceData {
+            offset_to_data,
+            size,
+            code_page: read_le_u32(raw, 8)?,
+            reserved: read_le_u32(raw, 12)?,
+            data: pe.read_rva(offset_to_data, size as usize)?
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ResourceNode<'a> {
+    Directory(ResourceDirectory<'a>),
+    Data(ResourceData<'a>)
+}
+
+impl<'a> ResourceNode<'a> {
+    pub fn get_data(&self) -> &'a[u8] {
+        match self {
+            ResourceNode::Directory(t) => t.get_data(),
+            ResourceNode::Data(t) => t.get_data()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourceEntry<'a> {
+    id: ResourceId,
+    node: ResourceNode<'a>
+}
+
+impl<'a> ResourceEntry<'a> {
+    pub fn get_id(&self) -> &ResourceId {
+        &self.id
+    }
+
+    pub fn get_node(&self) -> &ResourceNode<'a> {
+        &self.node
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourceDirectory<'a> {
+    characteristics: u32,           /* (reserved) */
+    time_date_stamp: u32,           /* creation time */
+    major_version: u16,             /* major version */
+    minor_version: u16,             /* minor version */
+    number_of_named_entries: u16,   /* entries identified by name */
+    number_of_id_entries: u16,      /* entries identified by ID */
+    entries: Vec<ResourceEntry<'a>>,
+    data: &'a[u8]
+}
+
+impl<'a> ResourceDirectory<'a> {
+    pub fn get_characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    pub fn get_time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn get_major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn get_minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn get_number_of_named_entries(&self) -> u16 {
+        self.number_of_named_entries
+    }
+
+    pub fn get_number_of_id_entries(&self) -> u16 {
+        self.number_of_id_entries
+    }
+
+    pub fn get_entries(&self) -> &[ResourceEntry<'a>] {
+        &self.entries
+    }
+
+    /* raw bytes of this directory table and its entries */
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data
+    }
+
+    pub fn get_entry(&self, id: &ResourceId) -> Option<&ResourceEntry<'a>> {
+        self.entries.iter().find(|t| t.get_id() == id)
+    }
+
+    /* flattens the type/name/language tree into its leaves */
+    pub fn get_resources(&self) -> Vec<Resource<'a>> {
+        let mut resources: Vec<Resource<'a>> = Vec::new();
+
+        for type_entry in self.entries.iter() {
+            let names: &ResourceDirectory = match type_entry.get_node() {
+                ResourceNode::Directory(t) => t,
+                ResourceNode::Data(_) => continue
+            };
+
+            for name_entry in names.get_entries() {
+                let languages: &ResourceDirectory =
+                    match name_entry.get_node() {
+                        ResourceNode::Directory(t) => t,
+                        ResourceNode::Data(_) => continue
+                    };
+
+                for language_entry in languages.get_entries() {
+                    if let ResourceNode::Data(t) = language_entry.get_node() {
+                        resources.push(Resource {
+                            resource_type: type_entry.get_id().clone(),
+                            name: name_entry.get_id().clone(),
+                            language: language_entry.get_id().clone(),
+                            data: t.clone()
+                        });
+                    }
+                }
+            }
+        }
+
+        resources
+    }
+
+    pub fn find(&self, resource_type: &ResourceId, name: &ResourceId,
+        language: Option<&ResourceId>) -> Option<Resource<'a>> {
+        self.get_resources().into_iter().find(|t|
+            t.get_type() == resource_type && t.get_name() == name &&
+                language.is_none_or(|l| t.get_language() == l))
+    }
+
+    pub fn find_by_type(&self, resource_type: ResourceType) ->
+        Vec<Resource<'a>> {
+        let id: ResourceId = ResourceId::Id(ResourceType::to_value(
+            resource_type));
+
+        self.get_resources().into_iter()
+            .filter(|t| *t.get_type() == id)
+            .collect()
+    }
+
+    /* a directory reached twice would make the tree a graph, and one
+     * pointing back at itself would never finish */
+    fn parse(pe: &PeFile<'a>, section: &'a[u8], offset: usize,
+        depth: usize, visited: &mut HashSet<usize>) ->
+        Result<ResourceDirectory<'a>, ButylError> {
+        if depth >= RESOURCE_MAX_DEPTH || !visited.insert(offset) {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let header: &[u8] = read_bytes(section, offset,
+            RESOURCE_DIRECTORY_LEN)?;
+        let number_of_named_entries: u16 = read_le_u16(header, 12)?;
+        let number_of_id_entries: u16 = read_le_u16(header, 14)?;
+        let count: usize = number_of_named_entries as usize +
+            number_of_id_entries as usize;
+        let len: usize = RESOURCE_DIRECTORY_LEN +
+            count * RESOURCE_DIRECTORY_ENTRY_LEN;
+
+        let mut directory: ResourceDirectory = ResourceDirectory {
+            characteristics: read_le_u32(header, 0)?,
+            time_date_stamp: read_le_u32(header, 4)?,
+            major_version: read_le_u16(header, 8)?,
+            minor_version: read_le_u16(header, 10)?,
+            number_of_named_entries,
+            number_of_id_entries,
+            entries: Vec::new(),
+            data: read_bytes(section, offset, len)?
+        };
+
+        for i in 0..count {
+            let entry_offset: usize = offset + RESOURCE_DIRECTORY_LEN +
+                i * RESOURCE_DIRECTORY_ENTRY_LEN;
+            let name: u32 = read_le_u32(section, entry_offset)?;
+            let target: u32 = read_le_u32(section, entry_offset + 4)?;
+
+            let id: ResourceId = if name & RESOURCE_NAME_IS_STRING != 0 {
+                ResourceId::Name(read_resource_string(section,
+                    (name & !RESOURCE_NAME_IS_STRING) as usize)?)
+            } else {
+                ResourceId::Id(name as u16)
+            };
+
+            let target_offset: usize =
+                (target & !RESOURCE_DATA_IS_DIRECTORY) as usize;
+            let is_directory: bool = target & RESOURCE_DATA_IS_DIRECTORY != 0;
+
+            let node: ResourceNode = if is_directory {
+                ResourceNode::Directory(ResourceDirectory::parse(pe, section,
+                    target_offset, depth + 1, visited)?)
+            } else {
+                /* a leaf whose data cannot be read is left out rather than
+                 * losing every other resource along with it */
+                match ResourceData::from_le_bytes(pe, section, target_offset) {
+                    Ok(t) => ResourceNode::Data(t),
+                    Err(_e) => continue
+                }
+            };
+
+            directory.entries.push(ResourceEntry { id, node });
+        }
+
+        Ok(directory)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Resource<'a> {
+    resource_type: ResourceId,
+    name: ResourceId,
+    language: ResourceId,
+    data: ResourceData<'a>
+}
+
+impl<'a> Resource<'a> {
+    pub fn get_type(&self) -> &ResourceId {
+        &self.resource_type
+    }
+
+    pub fn get_name(&self) -> &ResourceId {
+        &self.name
+    }
+
+    pub fn get_language(&self) -> &ResourceId {
+        &self.language
+    }
+
+    pub fn get_data_entry(&self) -> &ResourceData<'a> {
+        &self.data
+    }
+
+    pub fn get_data(&self) -> &'a[u8] {
+        self.data.get_data()
+    }
+}
+
+/* names are stored as a length-prefixed, non-terminated UTF-16 string */
+fn read_resource_string(section: &[u8], offset: usize) ->
+    Result<String, ButylError> {
+    let len: usize = read_le_u16(section, offset)? as usize;
+    let raw: &[u8] = read_bytes(section, offset + 2, len * 2)?;
+    let units: Vec<u16> = raw.chunks_exact(2)
+        .map(|t| ((t[1] as u16) << 8) | t[0] as u16)
+        .collect();
+
+    Ok(String::from_utf16_lossy(&units))
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_resources(&self) ->
+        Result<Option<ResourceDirectory<'a>>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Resource) {
+                Some(t) => t,
+                None => return Ok(None)
+            };
+
+        /* entry offsets are relative to the start of the resource
+         * directory, so hand the parser everything from there onwards */
+        let start: usize = self.rva_to_offset(directory.get_virtual_address())?;
+        let section: &'a[u8] = self.get_data().get(start..)
+            .ok_or(ButylError::InsufficientDataError)?;
+
+        Ok(Some(ResourceDirectory::parse(self, section, 0, 0,
+            &mut HashSet::new())?))
+    }
+}
+
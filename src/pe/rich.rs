@@ -0,0 +1,306 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::DOS_HEADER_LEN;
+use crate::pe::PeFile;
+use crate::utils::*;
+
+pub const RICH_SIGNATURE: u32 = 0x6863_6952;        /* "Rich" */
+pub const DANS_SIGNATURE: u32 = 0x536e_6144;        /* "DanS" */
+pub const RICH_ENTRY_LEN: usize = 8;
+pub const RICH_HEADER_PADDING_LEN: usize = 12;
+
+/* the e_lfanew field is left out of the checksum, as the linker writes it
+ * after the Rich header has been generated */
+const LFANEW_OFFSET: usize = 0x3c;
+
+/* product IDs as assigned by the MSVC toolchain, indexed by value */
+const PRODUCT_NAMES: &[&str] = &[
+    "Unknown", "Import0", "Linker510", "Cvtomf510", "Linker600",
+    "Cvtomf600", "Cvtres500", "Utc11_Basic", "Utc11_C", "Utc12_Basic",
+    "Utc12_C", "Utc12_CPP", "AliasObj60", "VisualBasic60", "Masm613",
+    "Masm710", "Linker511", "Cvtomf511", "Masm614", "Linker512",
+    "Cvtomf512", "Utc12_C_Std", "Utc12_CPP_Std", "Utc12_C_Book",
+    "Utc12_CPP_Book", "Implib700", "Cvtomf700", "Utc13_Basic", "Utc13_C",
+    "Utc13_CPP", "Linker610", "Cvtomf610", "Linker601", "Cvtomf601",
+    "Utc12_1_Basic", "Utc12_1_C", "Utc12_1_CPP", "Linker620", "Cvtomf620",
+    "AliasObj70", "Linker621", "Cvtomf621", "Masm615", "Utc13_LTCG_C",
+    "Utc13_LTCG_CPP", "Masm620", "ILAsm100", "Utc12_2_Basic", "Utc12_2_C",
+    "Utc12_2_CPP", "Utc12_2_C_Std", "Utc12_2_CPP_Std", "Utc12_2_C_Book",
+    "Utc12_2_CPP_Book", "Implib622", "Cvtomf622", "Cvtres501",
+    "Utc13_C_Std", "Utc13_CPP_Std", "Cvtpgd1300", "Linker622", "Linker700",
+    "Export622", "Export700", "Masm700", "Utc13_POGO_I_C",
+    "Utc13_POGO_I_CPP", "Utc13_POGO_O_C", "Utc13_POGO_O_CPP", "Cvtres700",
+    "Cvtres710p", "Linker710p", "Cvtomf710p", "Export710p", "Implib710p",
+    "Masm710p", "Utc1310p_C", "Utc1310p_CPP", "Utc1310p_C_Std",
+    "Utc1310p_CPP_Std", "Utc1310p_LTCG_C", "Utc1310p_LTCG_CPP",
+    "Utc1310p_POGO_I_C", "Utc1310p_POGO_I_CPP", "Utc1310p_POGO_O_C",
+    "Utc1310p_POGO_O_CPP", "Linker624", "Cvtomf624", "Export624",
+    "Implib624", "Linker710", "Cvtomf710", "Export710", "Implib710",
+    "Cvtres710", "Utc1310_C", "Utc1310_CPP", "Utc1310_C_Std",
+    "Utc1310_CPP_Std", "Utc1310_LTCG_C", "Utc1310_LTCG_CPP",
+    "Utc1310_POGO_I_C", "Utc1310_POGO_I_CPP", "Utc1310_POGO_O_C",
+    "Utc1310_POGO_O_CPP", "AliasObj710", "AliasObj710p", "Cvtpgd1310",
+    "Cvtpgd1310p", "Utc1400_C", "Utc1400_CPP", "Utc1400_C_Std",
+    "Utc1400_CPP_Std", "Utc1400_LTCG_C", "Utc1400_LTCG_CPP",
+    "Utc1400_POGO_I_C", "Utc1400_POGO_I_CPP", "Utc1400_POGO_O_C",
+    "Utc1400_POGO_O_CPP", "Cvtpgd1400", "Linker800", "Cvtomf800",
+    "Export800", "Implib800", "Cvtres800", "Masm800", "AliasObj800",
+    "PhoenixPrerelease", "Utc1400_CVTCIL_C", "Utc1400_CVTCIL_CPP",
+    "Utc1400_LTCG_MSIL", "Utc1500_C", "Utc1500_CPP", "Utc1500_C_Std",
+    "Utc1500_CPP_Std", "Utc1500_CVTCIL_C", "Utc1500_CVTCIL_CPP",
+    "Utc1500_LTCG_C", "Utc1500_LTCG_CPP", "Utc1500_LTCG_MSIL",
+    "Utc1500_POGO_I_C", "Utc1500_POGO_I_CPP", "Utc1500_POGO_O_C",
+    "Utc1500_POGO_O_CPP", "Cvtpgd1500", "Linker900", "Export900",
+    "Implib900", "Cvtres900", "Masm900", "AliasObj900", "Resource",
+    "AliasObj1000", "Cvtpgd1600", "Cvtres1000", "Export1000", "Implib1000",
+    "Linker1000", "Masm1000", "Phx1600_C", "Phx1600_CPP",
+    "Phx1600_CVTCIL_C", "Phx1600_CVTCIL_CPP", "Phx1600_LTCG_C",
+    "Phx1600_LTCG_CPP", "Phx1600_LTCG_MSIL", "Phx1600_POGO_I_C",
+    "Phx1600_POGO_I_CPP", "Phx1600_POGO_O_C", "Phx1600_POGO_O_CPP",
+    "Utc1600_C", "Utc1600_CPP", "Utc1600_CVTCIL_C", "Utc1600_CVTCIL_CPP",
+    "Utc1600_LTCG_C", "Utc1600_LTCG_CPP", "Utc1600_LTCG_MSIL",
+    "Utc1600_POGO_I_C", "Utc1600_POGO_I_CPP", "Utc1600_POGO_O_C",
+    "Utc1600_POGO_O_CPP", "AliasObj1010", "Cvtpgd1610", "Cvtres1010",
+    "Export1010", "Implib1010", "Linker1010", "Masm1010", "Utc1610_C",
+    "Utc1610_CPP", "Utc1610_CVTCIL_C", "Utc1610_CVTCIL_CPP",
+    "Utc1610_LTCG_C", "Utc1610_LTCG_CPP", "Utc1610_LTCG_MSIL",
+    "Utc1610_POGO_I_C", "Utc1610_POGO_I_CPP", "Utc1610_POGO_O_C",
+    "Utc1610_POGO_O_CPP", "AliasObj1100", "Cvtpgd1700", "Cvtres1100",
+    "Export1100", "Implib1100", "Linker1100", "Masm1100", "Utc1700_C",
+    "Utc1700_CPP", "Utc1700_CVTCIL_C", "Utc1700_CVTCIL_CPP",
+    "Utc1700_LTCG_C", "Utc1700_LTCG_CPP", "Utc1700_LTCG_MSIL",
+    "Utc1700_POGO_I_C", "Utc1700_POGO_I_CPP", "Utc1700_POGO_O_C",
+    "Utc1700_POGO_O_CPP", "AliasObj1200", "Cvtpgd1800", "Cvtres1200",
+    "Export1200", "Implib1200", "Linker1200", "Masm1200", "Utc1800_C",
+    "Utc1800_CPP", "Utc1800_CVTCIL_C", "Utc1800_CVTCIL_CPP",
+    "Utc1800_LTCG_C", "Utc1800_LTCG_CPP", "Utc1800_LTCG_MSIL",
+    "Utc1800_POGO_I_C", "Utc1800_POGO_I_CPP", "Utc1800_POGO_O_C",
+    "Utc1800_POGO_O_CPP", "AliasObj1210", "Cvtpgd1810", "Cvtres1210",
+    "Export1210", "Implib1210", "Linker1210", "Masm1210", "Utc1810_C",
+    "Utc1810_CPP", "Utc1810_CVTCIL_C", "Utc1810_CVTCIL_CPP",
+    "Utc1810_LTCG_C", "Utc1810_LTCG_CPP", "Utc1810_LTCG_MSIL",
+    "Utc1810_POGO_I_C", "Utc1810_POGO_I_CPP", "Utc1810_POGO_O_C",
+    "Utc1810_POGO_O_CPP", "AliasObj1400", "Cvtpgd1900", "Cvtres1400",
+    "Export1400", "Implib1400", "Linker1400", "Masm1400", "Utc1900_C",
+    "Utc1900_CPP", "Utc1900_CVTCIL_C", "Utc1900_CVTCIL_CPP",
+    "Utc1900_LTCG_C", "Utc1900_LTCG_CPP", "Utc1900_LTCG_MSIL",
+    "Utc1900_POGO_I_C", "Utc1900_POGO_I_CPP", "Utc1900_POGO_O_C",
+    "Utc1900_POGO_O_CPP"
+];
+
+/* (first build, last build, toolchain) for released MSVC toolsets; 50727 is
+ * shared by Visual Studio 2005 and 2012 and is resolved by product ID */
+const MSVC_BUILDS: &[(u16, u16, &str)] = &[
+    (8168, 8168, "Visual Studio 6.0"),
+    (8447, 8447, "Visual Studio 6.0 SP5"),
+    (8799, 8799, "Visual Studio 6.0 SP6"),
+    (9466, 9466, "Visual Studio .NET 2002"),
+    (3077, 3077, "Visual Studio .NET 2003"),
+    (4035, 4035, "Visual Studio .NET 2003 (DDK)"),
+    (6030, 6030, "Visual Studio .NET 2003 SP1"),
+    (21022, 21022, "Visual Studio 2008"),
+    (30729, 30729, "Visual Studio 2008 SP1"),
+    (30319, 30319, "Visual Studio 2010"),
+    (40219, 40219, "Visual Studio 2010 SP1"),
+    (51025, 51106, "Visual Studio 2012 Update 1"),
+    (60315, 60315, "Visual Studio 2012 Update 2"),
+    (60610, 60610, "Visual Studio 2012 Update 3"),
+    (61030, 61030, "Visual Studio 2012 Update 4"),
+    (21005, 21005, "Visual Studio 2013"),
+    (30501, 30501, "Visual Studio 2013 Update 2"),
+    (30723, 30723, "Visual Studio 2013 Update 3"),
+    (31101, 31101, "Visual Studio 2013 Update 4"),
+    (40629, 40629, "Visual Studio 2013 Update 5"),
+    (23026, 23026, "Visual Studio 2015"),
+    (23506, 23506, "Visual Studio 2015 Update 1"),
+    (23918, 23918, "Visual Studio 2015 Update 2"),
+    (24123, 24215, "Visual Studio 2015 Update 3"),
+    (25017, 25019, "Visual Studio 2017 15.0"),
+    (25506, 25508, "Visual Studio 2017 15.3"),
+    (25542, 25547, "Visual Studio 2017 15.4"),
+    (25830, 25835, "Visual Studio 2017 15.5"),
+    (26128, 26131, "Visual Studio 2017 15.6"),
+    (26428, 26433, "Visual Studio 2017 15.7"),
+    (26726, 26732, "Visual Studio 2017 15.8"),
+    (27023, 27051, "Visual Studio 2017 15.9"),
+    (27404, 27508, "Visual Studio 2019 16.0"),
+    (27702, 27702, "Visual Studio 2019 16.1"),
+    (27905, 27905, "Visual Studio 2019 16.2"),
+    (28105, 28106, "Visual Studio 2019 16.3"),
+    (28314, 28316, "Visual Studio 2019 16.4"),
+    (28610, 28614, "Visual Studio 2019 16.5"),
+    (28805, 28806, "Visual Studio 2019 16.6"),
+    (29110, 29112, "Visual Studio 2019 16.7"),
+    (29333, 29337, "Visual Studio 2019 16.8"),
+    (29910, 29915, "Visual Studio 2019 16.9"),
+    (30034, 30040, "Visual Studio 2019 16.10"),
+    (30133, 30159, "Visual Studio 2019 16.11"),
+    (30705, 30709, "Visual Studio 2022 17.0"),
+    (31104, 31107, "Visual Studio 2022 17.1"),
+    (31328, 31332, "Visual Studio 2022 17.2"),
+    (31629, 31630, "Visual Studio 2022 17.3"),
+    (31933, 31938, "Visual Studio 2022 17.4"),
+    (32215, 32217, "Visual Studio 2022 17.5"),
+    (32532, 32537, "Visual Studio 2022 17.6"),
+    (32822, 32825, "Visual Studio 2022 17.7"),
+    (33130, 33135, "Visual Studio 2022 17.8"),
+    (33517, 33523, "Visual Studio 2022 17.9"),
+    (33808, 33813, "Visual Studio 2022 17.10"),
+    (34120, 34123, "Visual Studio 2022 17.11"),
+    (34433, 34438, "Visual Studio 2022 17.12"),
+    (34808, 34810, "Visual Studio 2022 17.13"),
+    (35207, 35215, "Visual Studio 2022 17.14")
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct RichEntry {
+    product_id: u16,        /* tool that produced the objects */
+    build: u16,             /* build number of that tool */
+    count: u32              /* number of objects it contributed */
+}
+
+impl RichEntry {
+    pub fn get_product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    pub fn get_build(&self) -> u16 {
+        self.build
+    }
+
+    pub fn get_count(&self) -> u32 {
+        self.count
+    }
+
+    /* the product and build as packed together in the header */
+    pub fn get_comp_id(&self) -> u32 {
+        (self.product_id as u32) << 16 | self.build as u32
+    }
+
+    pub fn get_product_name(&self) -> Option<&'static str> {
+        PRODUCT_NAMES.get(self.product_id as usize).copied()
+    }
+
+    pub fn get_toolchain(&self) -> Option<&'static str> {
+        if self.build == 50727 {
+            return match self.product_id {
+                0x006d..=0x0082 => Some("Visual Studio 2005"),
+                0x00c7..=0x00d8 => Some("Visual Studio 2012"),
+                _ => None
+            };
+        }
+
+        MSVC_BUILDS.iter()
+            .find(|(first, last, _)| (*first..=*last).contains(&self.build))
+            .map(|(_, _, name)| *name)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RichHeader {
+    offset: usize,          /* file offset of the "DanS" marker */
+    len: usize,             /* bytes up to and including the key */
+    key: u32,               /* XOR key, which doubles as the checksum */
+    check_sum: u32,         /* checksum computed over the DOS header & stub */
+    entries: Vec<RichEntry>
+}
+
+impl RichHeader {
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get_key(&self) -> u32 {
+        self.key
+    }
+
+    pub fn get_check_sum(&self) -> u32 {
+        self.check_sum
+    }
+
+    pub fn get_entries(&self) -> &[RichEntry] {
+        &self.entries
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.key == self.check_sum
+    }
+
+    /* `bytes` is the start of the file up to `e_lfanew`; the header is found
+     * by its trailing "Rich" marker and decoded backwards to "DanS" */
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8]) ->
+        Result<Option<RichHeader>, ButylError> {
+        let rich_offset: usize = match (DOS_HEADER_LEN..bytes.len())
+            .step_by(4)
+            .find(|t| read_le_u32(bytes, *t).ok() == Some(RICH_SIGNATURE)) {
+            Some(t) => t,
+            None => return Ok(None)
+        };
+
+        let key: u32 = read_le_u32(bytes, rich_offset + 4)?;
+        let mut offset: usize = rich_offset;
+
+        loop {
+            if offset < DOS_HEADER_LEN + 4 {
+                return Err(ButylError::MalformedDataError);
+            }
+
+            offset -= 4;
+
+            if read_le_u32(bytes, offset)? ^ key == DANS_SIGNATURE {
+                break;
+            }
+        }
+
+        let mut rich_header: RichHeader = RichHeader::default();
+        rich_header.offset = offset;
+        rich_header.len = rich_offset + 8 - offset;
+        rich_header.key = key;
+
+        let mut check_sum: u32 = offset as u32;
+
+        for (i, byte) in bytes[..offset].iter().enumerate() {
+            if (LFANEW_OFFSET..LFANEW_OFFSET + 4).contains(&i) {
+                continue;
+            }
+
+            check_sum = check_sum
+                .wrapping_add((*byte as u32).rotate_left(i as u32));
+        }
+
+        let mut entry_offset: usize = offset + 4 + RICH_HEADER_PADDING_LEN;
+
+        while entry_offset + RICH_ENTRY_LEN <= rich_offset {
+            let comp_id: u32 = read_le_u32(bytes, entry_offset)? ^ key;
+            let count: u32 = read_le_u32(bytes, entry_offset + 4)? ^ key;
+
+            check_sum = check_sum.wrapping_add(comp_id.rotate_left(count));
+
+            let mut entry: RichEntry = RichEntry::default();
+            entry.product_id = (comp_id >> 16) as u16;
+            entry.build = comp_id as u16;
+            entry.count = count;
+            rich_header.entries.push(entry);
+
+            entry_offset += RICH_ENTRY_LEN;
+        }
+
+        rich_header.check_sum = check_sum;
+
+        Ok(Some(rich_header))
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_rich_header(&self) -> Result<Option<RichHeader>, ButylError> {
+        let lfanew: usize = (self.get_dos_header().get_lfanew() as usize)
+            .min(self.data.len());
+        RichHeader::from_le_bytes(&self.data[..lfanew])
+    }
+}
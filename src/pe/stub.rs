@@ -0,0 +1,346 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::dos::DOS_HEADER_LEN;
+use crate::pe::{self, PeFile, DataDirectoryType, PE_SECTION_HEADER_LEN};
+use crate::pe::debug::DEBUG_DIRECTORY_LEN;
+use crate::pe::image::SECTION_HEADER_POINTER_TO_RAW_DATA_OFFSET;
+use crate::utils::*;
+
+pub const PE_HEADER_ALIGNMENT: usize = 8;
+pub const DOS_PAGE_LEN: usize = 512;
+pub const DOS_PARAGRAPH_LEN: usize = 16;
+
+const DOS_HEADER_CBLP_OFFSET: usize = 0x02;
+const DOS_HEADER_CP_OFFSET: usize = 0x04;
+const DOS_HEADER_CRLC_OFFSET: usize = 0x06;
+const DOS_HEADER_CPARHDR_OFFSET: usize = 0x08;
+const DOS_HEADER_IP_OFFSET: usize = 0x14;
+const DOS_HEADER_CS_OFFSET: usize = 0x16;
+const DOS_HEADER_LFANEW_OFFSET: usize = 0x3c;
+const COFF_HEADER_SYMPTR_OFFSET: usize = 8;
+const PE_OPTIONAL_HEADER_SIZE_OF_HEADERS_OFFSET: usize = 60;
+const DEBUG_DIRECTORY_POINTER_TO_RAW_DATA_OFFSET: usize = 24;
+
+/* mov ax, 4c01h; int 21h */
+pub const MINIMAL_DOS_STUB: [u8; 5] = [0xb8, 0x01, 0x4c, 0xcd, 0x21];
+
+/* the stub the Microsoft linker emits by default */
+pub const DEFAULT_DOS_STUB: [u8; 64] = [
+    0x0e, 0x1f, 0xba, 0x0e, 0x00, 0xb4, 0x09, 0xcd,
+    0x21, 0xb8, 0x01, 0x4c, 0xcd, 0x21, 0x54, 0x68,
+    0x69, 0x73, 0x20, 0x70, 0x72, 0x6f, 0x67, 0x72,
+    0x61, 0x6d, 0x20, 0x63, 0x61, 0x6e, 0x6e, 0x6f,
+    0x74, 0x20, 0x62, 0x65, 0x20, 0x72, 0x75, 0x6e,
+    0x20, 0x69, 0x6e, 0x20, 0x44, 0x4f, 0x53, 0x20,
+    0x6d, 0x6f, 0x64, 0x65, 0x2e, 0x0d, 0x0d, 0x0a,
+    0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+];
+
+impl<'a> PeFile<'a> {
+    /* everything between the DOS header and the PE signature, including any
+     * Rich header */
+    pub fn get_dos_stub(&self) -> &'a[u8] {
+        let lfanew: usize = (self.get_dos_header().get_lfanew() as usize)
+            .min(self.data.len());
+
+        match lfanew {
+            t if t > DOS_HEADER_LEN => &self.data[DOS_HEADER_LEN..t],
+            _ => &[]
+        }
+    }
+
+    /* returns a copy of the file with `stub` in place of the DOS stub; if
+     * the PE headers no longer fit in SizeOfHeaders it is grown by whole
+     * file alignment units and the section data shifted to suit */
+    pub fn replace_dos_stub(&self, stub: &[u8]) -> Result<Vec<u8>, ButylError> {
+        self.rebuild_with_dos_stub(stub, false)
+    }
+
+    /* replaces the DOS stub with one that just exits, resetting the DOS
+     * entry point so that it is the stub that runs */
+    pub fn minimise_dos_stub(&self) -> Result<Vec<u8>, ButylError> {
+        self.rebuild_with_dos_stub(&MINIMAL_DOS_STUB, true)
+    }
+
+    fn rebuild_with_dos_stub(&self, stub: &[u8], reset_entry: bool) ->
+        Result<Vec<u8>, ButylError> {
+        let old_lfanew: usize = self.get_dos_header().get_lfanew() as usize;
+        let headers_end: usize = self.get_section_table_offset() +
+            self.get_sections().len() * PE_SECTION_HEADER_LEN;
+        let size_of_headers: usize =
+            self.get_optional_header().get_size_of_headers() as usize;
+
+        if old_lfanew < DOS_HEADER_LEN || headers_end > size_of_headers {
+            return Err(ButylError::UnsupportedFeatureError);
+        }
+
+        let headers: &[u8] = read_bytes(self.data, old_lfanew,
+            headers_end - old_lfanew)?;
+        let lfanew: usize = (DOS_HEADER_LEN + stub.len())
+            .next_multiple_of(PE_HEADER_ALIGNMENT);
+
+        /* anything else inside SizeOfHeaders, bound imports say, is found by
+         * RVA and so has to stay put, with the new headers clear of it */
+        let trailing: &[u8] = self.data
+            .get(headers_end..size_of_headers.min(self.data.len()))
+            .unwrap_or(&[]);
+        let kept: Option<usize> = trailing.iter().position(|t| *t != 0);
+
+        if let Some(t) = kept {
+            if lfanew + headers.len() > headers_end + t {
+                return Err(ButylError::ExcessiveDataError);
+            }
+        }
+
+        /* the headers have to stay below the first section once mapped */
+        let shift: usize = match lfanew + headers.len() {
+            t if t <= size_of_headers => 0,
+            t => t.next_multiple_of(self.get_address_space()
+                .get_file_alignment() as usize) - size_of_headers
+        };
+        let headers_limit: usize = self.get_sections().iter()
+            .map(|t| t.get_virtual_address() as usize)
+            .min()
+            .unwrap_or(self.get_optional_header().get_size_of_image() as usize);
+
+        if size_of_headers + shift > headers_limit {
+            return Err(ButylError::ExcessiveDataError);
+        }
+
+        let mut data: Vec<u8> = read_bytes(self.data, 0, DOS_HEADER_LEN)?
+            .to_vec();
+        data.extend_from_slice(stub);
+        data.resize(lfanew, 0);
+        data.extend_from_slice(headers);
+        data.resize(size_of_headers + shift, 0);
+
+        if let Some(t) = kept {
+            data[headers_end + t..headers_end + trailing.len()]
+                .copy_from_slice(&trailing[t..]);
+        }
+
+        data.extend_from_slice(self.data.get(size_of_headers..)
+            .unwrap_or(&[]));
+
+        let image_len: usize = DOS_HEADER_LEN + stub.len();
+        write_le_u16(&mut data, DOS_HEADER_CBLP_OFFSET,
+            (image_len % DOS_PAGE_LEN) as u16)?;
+        write_le_u16(&mut data, DOS_HEADER_CP_OFFSET,
+            image_len.div_ceil(DOS_PAGE_LEN) as u16)?;
+        write_le_u32(&mut data, DOS_HEADER_LFANEW_OFFSET, lfanew as u32)?;
+
+        if reset_entry {
+            write_le_u16(&mut data, DOS_HEADER_CRLC_OFFSET, 0)?;
+            write_le_u16(&mut data, DOS_HEADER_CPARHDR_OFFSET,
+                (DOS_HEADER_LEN / DOS_PARAGRAPH_LEN) as u16)?;
+            write_le_u16(&mut data, DOS_HEADER_IP_OFFSET, 0)?;
+            write_le_u16(&mut data, DOS_HEADER_CS_OFFSET, 0)?;
+        }
+
+        /* offsets within the PE headers, which now start at `lfanew` */
+        let relocate = |offset: usize| offset - old_lfanew + lfanew;
+
+        if shift != 0 {
+            self.shift_file_offsets(&mut data, shift, &relocate)?;
+        }
+
+        let check_sum_offset: usize = relocate(self.get_check_sum_offset());
+        let check_sum: u32 = pe::compute_check_sum(&data, check_sum_offset);
+        write_le_u32(&mut data, check_sum_offset, check_sum)?;
+
+        Ok(data)
+    }
+
+    /* anything addressed by file offset past the headers moves by `shift` */
+    fn shift_file_offsets(&self, data: &mut [u8], shift: usize,
+        relocate: &dyn Fn(usize) -> usize) -> Result<(), ButylError> {
+        let size_of_headers: u32 =
+            self.get_optional_header().get_size_of_headers();
+        let shifted = |t: u32| if t >= size_of_headers {
+            t.checked_add(shift as u32).ok_or(ButylError::ExcessiveDataError)
+        } else {
+            Ok(t)
+        };
+
+        write_le_u32(data, relocate(self.get_optional_header_offset() +
+            PE_OPTIONAL_HEADER_SIZE_OF_HEADERS_OFFSET),
+            shifted(size_of_headers)?)?;
+
+        if self.get_coff_header().get_symptr() != 0 {
+            write_le_u32(data, relocate(self.get_coff_header_offset() +
+                COFF_HEADER_SYMPTR_OFFSET),
+                shifted(self.get_coff_header().get_symptr())?)?;
+        }
+
+        for (i, section) in self.get_sections().iter().enumerate() {
+            if section.get_pointer_to_raw_data() != 0 {
+                write_le_u32(data, relocate(self.get_section_table_offset() +
+                    i * PE_SECTION_HEADER_LEN +
+                    SECTION_HEADER_POINTER_TO_RAW_DATA_OFFSET),
+                    shifted(section.get_pointer_to_raw_data())?)?;
+            }
+        }
+
+        /* the certificate table's "virtual address" is a file offset */
+        if let Some(directory) =
+            self.get_data_directory(DataDirectoryType::Security) {
+            write_le_u32(data, relocate(self.get_optional_header_offset() +
+                self.get_optional_header()
+                    .get_data_directory_offset(DataDirectoryType::Security)),
+                shifted(directory.get_virtual_address())?)?;
+        }
+
+        if let Some(directory) =
+            self.get_data_directory(DataDirectoryType::Debug) {
+            let offset: usize =
+                self.rva_to_offset(directory.get_virtual_address())?;
+
+            for (i, entry) in self.get_debug_directories()?.iter()
+                .enumerate() {
+                let mut field: usize = offset + i * DEBUG_DIRECTORY_LEN +
+                    DEBUG_DIRECTORY_POINTER_TO_RAW_DATA_OFFSET;
+
+                field = if field >= size_of_headers as usize {
+                    field + shift
+                } else {
+                    relocate(field)
+                };
+
+                if entry.get_pointer_to_raw_data() != 0 {
+                    write_le_u32(data, field,
+                        shifted(entry.get_pointer_to_raw_data())?)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe::SectionHeader;
+
+    /* an unsigned launcher with a debug directory, and a signed one */
+    const DEBUG: &[u8] =
+        include_bytes!("../../tests/fixtures/pe/setuptools-cli-32.exe");
+    const SIGNED: &[u8] =
+        include_bytes!("../../tests/fixtures/pe/conda-cli-32.exe");
+
+    fn section_data<'a>(pe: &PeFile<'a>, section: &SectionHeader) ->
+        &'a[u8] {
+        read_bytes(pe.get_data(), section.get_pointer_to_raw_data() as usize,
+            section.get_size_of_raw_data() as usize).unwrap()
+    }
+
+    /* everything a rebuilt file has to keep, given how far the section data
+     * moved */
+    fn check_rebuilt(old: &PeFile, new: &PeFile, shift: u32) {
+        let size_of_headers: u32 =
+            old.get_optional_header().get_size_of_headers();
+
+        assert_eq!(new.get_optional_header().get_size_of_headers(),
+            size_of_headers + shift);
+        assert_eq!(new.get_optional_header().get_check_sum(),
+            new.compute_check_sum());
+        assert_eq!(new.get_sections().len(), old.get_sections().len());
+
+        let debug_rva: Option<u32> = old
+            .get_data_directory(DataDirectoryType::Debug)
+            .map(|t| t.get_virtual_address());
+
+        for (a, b) in old.get_sections().iter().zip(new.get_sections()) {
+            let expected: u32 = match a.get_pointer_to_raw_data() {
+                0 => 0,
+                t => t + shift
+            };
+
+            assert_eq!(b.get_pointer_to_raw_data(), expected);
+
+            /* a debug directory in a section has its own offsets moved */
+            let has_debug: bool = debug_rva.is_some_and(|t|
+                t >= a.get_virtual_address() &&
+                t < a.get_virtual_address() + a.get_size_of_raw_data());
+
+            if shift == 0 || !has_debug {
+                assert!(section_data(new, b) == section_data(old, a));
+            }
+        }
+
+        let old_debug = old.get_debug_directories().unwrap();
+        let new_debug = new.get_debug_directories().unwrap();
+
+        assert_eq!(new_debug.len(), old_debug.len());
+
+        for (a, b) in old_debug.iter().zip(new_debug.iter()) {
+            assert_eq!(b.get_pointer_to_raw_data(),
+                a.get_pointer_to_raw_data() + shift);
+            assert!(read_bytes(new.get_data(),
+                b.get_pointer_to_raw_data() as usize,
+                b.get_size_of_data() as usize).unwrap() ==
+                read_bytes(old.get_data(), a.get_pointer_to_raw_data() as usize,
+                a.get_size_of_data() as usize).unwrap());
+        }
+
+        let old_security = old.get_data_directory(DataDirectoryType::Security);
+        let new_security = new.get_data_directory(DataDirectoryType::Security);
+
+        assert_eq!(new_security.map(|t| t.get_virtual_address()),
+            old_security.map(|t| t.get_virtual_address() + shift));
+        assert!(new.get_certificates().unwrap().iter()
+            .map(|t| t.get_certificate())
+            .eq(old.get_certificates().unwrap().iter()
+                .map(|t| t.get_certificate())));
+    }
+
+    #[test]
+    fn minimal_stub_keeps_the_layout() {
+        for original in [DEBUG, SIGNED] {
+            let old: PeFile = PeFile::from_le_bytes(original).unwrap();
+            let data: Vec<u8> = old.minimise_dos_stub().unwrap();
+            let new: PeFile = PeFile::from_le_bytes(&data).unwrap();
+
+            assert_eq!(new.get_dos_header().get_lfanew(), 0x48);
+            assert!(new.get_dos_stub().starts_with(&MINIMAL_DOS_STUB));
+            assert_eq!(new.get_dos_header().get_ip(), 0);
+            assert_eq!(new.get_dos_header().get_cs(), 0);
+            assert_eq!(data.len(), original.len());
+            check_rebuilt(&old, &new, 0);
+        }
+    }
+
+    #[test]
+    fn long_stub_shifts_the_sections() {
+        let stub: Vec<u8> = vec![0x90; 0x300];
+
+        for original in [DEBUG, SIGNED] {
+            let old: PeFile = PeFile::from_le_bytes(original).unwrap();
+            let data: Vec<u8> = old.replace_dos_stub(&stub).unwrap();
+            let new: PeFile = PeFile::from_le_bytes(&data).unwrap();
+
+            assert_eq!(new.get_dos_header().get_lfanew(), 0x340);
+            assert_eq!(new.get_dos_stub(), &stub[..]);
+            assert_eq!(data.len(), original.len() + 0x200);
+            check_rebuilt(&old, &new, 0x200);
+        }
+    }
+
+    #[test]
+    fn data_after_the_section_table_is_kept_or_refused() {
+        let old: PeFile = PeFile::from_le_bytes(DEBUG).unwrap();
+        let headers_end: usize = old.get_section_table_offset() +
+            old.get_sections().len() * PE_SECTION_HEADER_LEN;
+        let mut original: Vec<u8> = DEBUG.to_vec();
+
+        original[headers_end + 0x10] = 0xff;
+
+        let old: PeFile = PeFile::from_le_bytes(&original).unwrap();
+        let data: Vec<u8> = old.minimise_dos_stub().unwrap();
+
+        assert_eq!(data[headers_end + 0x10], 0xff);
+        assert!(matches!(old.replace_dos_stub(&[0x90; 0x300]),
+            Err(ButylError::ExcessiveDataError)));
+    }
+}
@@ -0,0 +1,166 @@
+use std::result::Result;
+
+use crate::errors::*;
+use crate::pe::{PeFile, DataDirectory, DataDirectoryType};
+use crate::utils::*;
+
+pub const TLS_DIRECTORY_LEN_32: usize = 24;
+pub const TLS_DIRECTORY_LEN_64: usize = 40;
+
+/* PE32 and PE32+ differ only in the width of the four address fields, so
+ * both are widened to 64 bits here */
+#[derive(Debug, Default, Clone)]
+pub struct TlsDirectory {
+    start_address_of_raw_data: u64, /* VA of the TLS template start */
+    end_address_of_raw_data: u64,   /* VA of the TLS template end */
+    address_of_index: u64,          /* VA of the TLS index slot */
+    address_of_callbacks: u64,      /* VA of the NULL-terminated callbacks */
+    size_of_zero_fill: u32,         /* zeroed bytes after the template */
+    characteristics: u32            /* alignment flags */
+}
+
+impl TlsDirectory {
+    pub fn get_start_address_of_raw_data(&self) -> u64 {
+        self.start_address_of_raw_data
+    }
+
+    pub fn get_end_address_of_raw_data(&self) -> u64 {
+        self.end_address_of_raw_data
+    }
+
+    pub fn get_address_of_index(&self) -> u64 {
+        self.address_of_index
+    }
+
+    pub fn get_address_of_callbacks(&self) -> u64 {
+        self.address_of_callbacks
+    }
+
+    pub fn get_size_of_zero_fill(&self) -> u32 {
+        self.size_of_zero_fill
+    }
+
+    pub fn get_characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    /* alignment of the TLS block, from IMAGE_SCN_ALIGN_* bits 20-23 */
+    pub fn get_alignment(&self) -> Option<u32> {
+        match (self.characteristics >> 20) & 0xf {
+            0 => None,
+            t => Some(1 << (t - 1))
+        }
+    }
+
+    pub fn from_le_bytes(bytes: &[u8], is_64_bit: bool) ->
+        Result<TlsDirectory, ButylError> {
+        let mut tls_directory: TlsDirectory = TlsDirectory::default();
+
+        if is_64_bit {
+            tls_directory.start_address_of_raw_data = read_le_u64(bytes, 0)?;
+            tls_directory.end_address_of_raw_data = read_le_u64(bytes, 8)?;
+            tls_directory.address_of_index = read_le_u64(bytes, 16)?;
+            tls_directory.address_of_callbacks = read_le_u64(bytes, 24)?;
+            tls_directory.size_of_zero_fill = read_le_u32(bytes, 32)?;
+            tls_directory.characteristics = read_le_u32(bytes, 36)?;
+        } else {
+            tls_directory.start_address_of_raw_data =
+                read_le_u32(bytes, 0)? as u64;
+            tls_directory.end_address_of_raw_data =
+                read_le_u32(bytes, 4)? as u64;
+            tls_directory.address_of_index = read_le_u32(bytes, 8)? as u64;
+            tls_directory.address_of_callbacks = read_le_u32(bytes, 12)? as u64;
+            tls_directory.size_of_zero_fill = read_le_u32(bytes, 16)?;
+            tls_directory.characteristics = read_le_u32(bytes, 20)?;
+        }
+
+        Ok(tls_directory)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TlsCallback {
+    va: u64,                /* address as stored in the callback array */
+    rva: Option<u32>,       /* None if outside the image */
+    offset: Option<usize>   /* None if not backed by the file */
+}
+
+impl TlsCallback {
+    pub fn get_va(&self) -> u64 {
+        self.va
+    }
+
+    pub fn get_rva(&self) -> Option<u32> {
+        self.rva
+    }
+
+    pub fn get_offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_tls_directory(&self) ->
+        Result<Option<TlsDirectory>, ButylError> {
+        let directory: DataDirectory =
+            match self.get_data_directory(DataDirectoryType::Tls) {
+                Some(t) => t,
+                None => return Ok(None)
+            };
+
+        let len: usize = if self.is_64_bit() {
+            TLS_DIRECTORY_LEN_64
+        } else {
+            TLS_DIRECTORY_LEN_32
+        };
+
+        Ok(Some(TlsDirectory::from_le_bytes(
+            self.read_rva(directory.get_virtual_address(), len)?,
+            self.is_64_bit())?))
+    }
+
+    pub fn get_tls_callbacks(&self) -> Result<Vec<TlsCallback>, ButylError> {
+        let tls_directory: TlsDirectory = match self.get_tls_directory()? {
+            Some(t) => t,
+            None => return Ok(Vec::new())
+        };
+
+        let mut callbacks: Vec<TlsCallback> = Vec::new();
+
+        if tls_directory.get_address_of_callbacks() == 0 {
+            return Ok(callbacks);
+        }
+
+        let array: u32 =
+            self.va_to_rva(tls_directory.get_address_of_callbacks())?;
+        let width: u32 = if self.is_64_bit() { 8 } else { 4 };
+
+        loop {
+            let offset: u32 = (callbacks.len() as u32).checked_mul(width)
+                .and_then(|t| t.checked_add(array))
+                .ok_or(ButylError::MalformedDataError)?;
+            let raw: &[u8] = self.read_rva(offset, width as usize)?;
+
+            let va: u64 = if self.is_64_bit() {
+                read_le_u64(raw, 0)?
+            } else {
+                read_le_u32(raw, 0)? as u64
+            };
+
+            if va == 0 {
+                break;
+            }
+
+            let rva: Option<u32> = self.va_to_rva(va).ok();
+
+            callbacks.push(TlsCallback {
+                va,
+                rva,
+                offset: rva.and_then(|t| self.rva_to_offset(t).ok())
+            });
+        }
+
+        Ok(callbacks)
+    }
+}
+
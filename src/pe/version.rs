@@ -0,0 +1,353 @@
+use std::result::Result;
+
+use serde::Serialize;
+
+use crate::errors::*;
+use crate::pe::PeFile;
+use crate::pe::resources::{ResourceDirectory, ResourceType};
+use crate::utils::*;
+
+pub const VS_FIXEDFILEINFO_SIGNATURE: u32 = 0xfeef_04bd;
+pub const VS_FIXEDFILEINFO_LEN: usize = 52;
+pub const VERSION_BLOCK_HEADER_LEN: usize = 6;
+pub const VERSION_BLOCK_MAX_DEPTH: usize = 3;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct FixedFileInfo {
+    signature: u32,             /* 0xfeef04bd */
+    struc_version: u32,         /* structure version */
+    file_version_ms: u32,       /* file version, high half */
+    file_version_ls: u32,       /* file version, low half */
+    product_version_ms: u32,    /* product version, high half */
+    product_version_ls: u32,    /* product version, low half */
+    file_flags_mask: u32,       /* valid bits of file_flags */
+    file_flags: u32,            /* debug, prerelease, patched, etc. */
+    file_os: u32,               /* target operating system */
+    file_type: u32,             /* application, DLL, driver, etc. */
+    file_subtype: u32,          /* driver or font subtype */
+    file_date_ms: u32,          /* creation date, high half */
+    file_date_ls: u32           /* creation date, low half */
+}
+
+impl FixedFileInfo {
+    pub fn get_signature(&self) -> u32 {
+        self.signature
+    }
+
+    pub fn get_struc_version(&self) -> u32 {
+        self.struc_version
+    }
+
+    pub fn get_file_version_ms(&self) -> u32 {
+        self.file_version_ms
+    }
+
+    pub fn get_file_version_ls(&self) -> u32 {
+        self.file_version_ls
+    }
+
+    pub fn get_file_version(&self) -> [u16; 4] {
+        split_version(self.file_version_ms, self.file_version_ls)
+    }
+
+    pub fn get_file_version_as_string(&self) -> String {
+        join_version(self.get_file_version())
+    }
+
+    pub fn get_product_version_ms(&self) -> u32 {
+        self.product_version_ms
+    }
+
+    pub fn get_product_version_ls(&self) -> u32 {
+        self.product_version_ls
+    }
+
+    pub fn get_product_version(&self) -> [u16; 4] {
+        split_version(self.product_version_ms, self.product_version_ls)
+    }
+
+    pub fn get_product_version_as_string(&self) -> String {
+        join_version(self.get_product_version())
+    }
+
+    pub fn get_file_flags_mask(&self) -> u32 {
+        self.file_flags_mask
+    }
+
+    pub fn get_file_flags(&self) -> u32 {
+        self.file_flags
+    }
+
+    pub fn get_file_os(&self) -> u32 {
+        self.file_os
+    }
+
+    pub fn get_file_type(&self) -> u32 {
+        self.file_type
+    }
+
+    pub fn get_file_subtype(&self) -> u32 {
+        self.file_subtype
+    }
+
+    pub fn get_file_date_ms(&self) -> u32 {
+        self.file_date_ms
+    }
+
+    pub fn get_file_date_ls(&self) -> u32 {
+        self.file_date_ls
+    }
+
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<FixedFileInfo, ButylError> {
+        let mut fixed_file_info: FixedFileInfo = FixedFileInfo::default();
+
+        fixed_file_info.signature = read_le_u32(bytes, 0)?;
+
+        if fixed_file_info.signature != VS_FIXEDFILEINFO_SIGNATURE {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        fixed_file_info.struc_version = read_le_u32(bytes, 4)?;
+        fixed_file_info.file_version_ms = read_le_u32(bytes, 8)?;
+        fixed_file_info.file_version_ls = read_le_u32(bytes, 12)?;
+        fixed_file_info.product_version_ms = read_le_u32(bytes, 16)?;
+        fixed_file_info.product_version_ls = read_le_u32(bytes, 20)?;
+        fixed_file_info.file_flags_mask = read_le_u32(bytes, 24)?;
+        fixed_file_info.file_flags = read_le_u32(bytes, 28)?;
+        fixed_file_info.file_os = read_le_u32(bytes, 32)?;
+        fixed_file_info.file_type = read_le_u32(bytes, 36)?;
+        fixed_file_info.file_subtype = read_le_u32(bytes, 40)?;
+        fixed_file_info.file_date_ms = read_le_u32(bytes, 44)?;
+        fixed_file_info.file_date_ls = read_le_u32(bytes, 48)?;
+
+        Ok(fixed_file_info)
+    }
+}
+
+fn split_version(ms: u32, ls: u32) -> [u16; 4] {
+    [(ms >> 16) as u16, ms as u16, (ls >> 16) as u16, ls as u16]
+}
+
+fn join_version(version: [u16; 4]) -> String {
+    format!("{}.{}.{}.{}", version[0], version[1], version[2], version[3])
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StringTable {
+    key: String,                    /* language and code page in hex */
+    strings: Vec<(String, String)>
+}
+
+impl StringTable {
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn get_language(&self) -> Option<u16> {
+        self.key.get(0..4).and_then(|t| u16::from_str_radix(t, 16).ok())
+    }
+
+    pub fn get_code_page(&self) -> Option<u16> {
+        self.key.get(4..8).and_then(|t| u16::from_str_radix(t, 16).ok())
+    }
+
+    pub fn get_strings(&self) -> &[(String, String)] {
+        &self.strings
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.iter()
+            .find(|t| t.0 == key)
+            .map(|t| t.1.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VersionInfo {
+    fixed_file_info: Option<FixedFileInfo>,
+    string_tables: Vec<StringTable>,
+    translations: Vec<(u16, u16)>   /* language and code page pairs */
+}
+
+impl VersionInfo {
+    pub fn get_fixed_file_info(&self) -> Option<&FixedFileInfo> {
+        self.fixed_file_info.as_ref()
+    }
+
+    pub fn get_string_tables(&self) -> &[StringTable] {
+        &self.string_tables
+    }
+
+    pub fn get_translations(&self) -> &[(u16, u16)] {
+        &self.translations
+    }
+
+    /* looks a key up in the first string table that defines it */
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.string_tables.iter().find_map(|t| t.get(key))
+    }
+
+    pub fn get_company_name(&self) -> Option<&str> {
+        self.get_string("CompanyName")
+    }
+
+    pub fn get_file_description(&self) -> Option<&str> {
+        self.get_string("FileDescription")
+    }
+
+    pub fn get_original_filename(&self) -> Option<&str> {
+        self.get_string("OriginalFilename")
+    }
+
+    pub fn get_product_name(&self) -> Option<&str> {
+        self.get_string("ProductName")
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<VersionInfo, ButylError> {
+        let root: VersionBlock = VersionBlock::parse(bytes, 0, 0)?;
+
+        if root.key != "VS_VERSION_INFO" {
+            return Err(ButylError::InvalidMagicError);
+        }
+
+        let mut version_info: VersionInfo = VersionInfo::default();
+
+        if !root.value.is_empty() {
+            version_info.fixed_file_info =
+                Some(FixedFileInfo::from_le_bytes(root.value)?);
+        }
+
+        for child in root.children.iter() {
+            match child.key.as_str() {
+                "StringFileInfo" => {
+                    for table in child.children.iter() {
+                        version_info.string_tables.push(StringTable {
+                            key: table.key.clone(),
+                            strings: table.children.iter()
+                                .map(|t| (t.key.clone(), decode_utf16(t.value)))
+                                .collect()
+                        });
+                    }
+                },
+                "VarFileInfo" => {
+                    for var in child.children.iter()
+                        .filter(|t| t.key == "Translation") {
+                        for pair in var.value.chunks_exact(4) {
+                            version_info.translations.push((
+                                read_le_u16(pair, 0)?,
+                                read_le_u16(pair, 2)?));
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        Ok(version_info)
+    }
+}
+
+/* every node of a VS_VERSIONINFO tree shares the same length-prefixed
+ * layout: a header, a key, an optional value and then child nodes */
+struct VersionBlock<'a> {
+    key: String,
+    value: &'a[u8],
+    children: Vec<VersionBlock<'a>>
+}
+
+impl<'a> VersionBlock<'a> {
+    fn parse(bytes: &'a[u8], offset: usize, depth: usize) ->
+        Result<VersionBlock<'a>, ButylError> {
+        if depth > VERSION_BLOCK_MAX_DEPTH {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let length: usize = read_le_u16(bytes, offset)? as usize;
+        let value_length: usize = read_le_u16(bytes, offset + 2)? as usize;
+        let value_type: u16 = read_le_u16(bytes, offset + 4)?;
+        let end: usize = offset + length;
+
+        if length < VERSION_BLOCK_HEADER_LEN || end > bytes.len() {
+            return Err(ButylError::MalformedDataError);
+        }
+
+        let mut cursor: usize = offset + VERSION_BLOCK_HEADER_LEN;
+        let mut units: Vec<u16> = Vec::new();
+
+        loop {
+            let unit: u16 = read_le_u16(bytes, cursor)?;
+            cursor += 2;
+
+            if unit == 0 {
+                break;
+            }
+
+            units.push(unit);
+        }
+
+        let key: String = String::from_utf16_lossy(&units);
+        let value_start: usize = align_to_dword(offset, cursor).min(end);
+
+        /* text values count UTF-16 units rather than bytes, and some
+         * compilers get this wrong, so clamp to the end of the block */
+        let value_len: usize = if value_type == 1 {
+            value_length * 2
+        } else {
+            value_length
+        };
+        let value_end: usize = (value_start + value_len).min(end);
+        let value: &[u8] = &bytes[value_start..value_end];
+
+        let mut children: Vec<VersionBlock> = Vec::new();
+
+        /* String and Var nodes sit at the bottom of the tree; anything
+         * after their value is padding */
+        if depth < VERSION_BLOCK_MAX_DEPTH {
+            let mut child: usize = align_to_dword(offset, value_end);
+
+            while child + VERSION_BLOCK_HEADER_LEN <= end {
+                let block: VersionBlock = VersionBlock::parse(bytes, child,
+                    depth + 1)?;
+                let length: usize = read_le_u16(bytes, child)? as usize;
+
+                children.push(block);
+                child = align_to_dword(offset, child + length);
+            }
+        }
+
+        Ok(VersionBlock {
+            key,
+            value,
+            children
+        })
+    }
+}
+
+fn align_to_dword(base: usize, offset: usize) -> usize {
+    base + (offset - base).div_ceil(4) * 4
+}
+
+fn decode_utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2)
+        .map(|t| ((t[1] as u16) << 8) | t[0] as u16)
+        .take_while(|t| *t != 0)
+        .collect();
+
+    String::from_utf16_lossy(&units)
+}
+
+impl<'a> PeFile<'a> {
+    pub fn get_version_info(&self) -> Result<Option<VersionInfo>, ButylError> {
+        let resources: ResourceDirectory = match self.get_resources()? {
+            Some(t) => t,
+            None => return Ok(None)
+        };
+
+        match resources.find_by_type(ResourceType::Version).first() {
+            Some(t) => Ok(Some(VersionInfo::from_le_bytes(t.get_data())?)),
+            None => Ok(None)
+        }
+    }
+}
+
@@ -1,18 +1,212 @@
+use std::path::Path;
+
 use crate::errors;
 use crate::formats;
 use crate::dos;
+use crate::com;
 use crate::coff;
+use crate::pe;
+use crate::ne;
+use crate::le;
+use crate::elf;
+use crate::macho;
 
 pub fn infer_format(data: &[u8]) -> formats::Format {
-   unimplemented!(); 
+    if data.len() >= 2 && data[0] == b'M' && data[1] == b'Z' {
+        let lfanew: usize = match read_le_u32(data, 0x3c) {
+            Ok(t) => t as usize,
+            Err(_e) => return formats::Format::DOS
+        };
+
+        if let Ok(pe::PE_SIGNATURE) = read_le_u32(data, lfanew) {
+            return formats::Format::PE;
+        }
+
+        return match read_le_u16(data, lfanew) {
+            Ok(ne::NE_SIGNATURE) => formats::Format::NE,
+            Ok(le::LE_SIGNATURE) => formats::Format::LE,
+            Ok(le::LX_SIGNATURE) => formats::Format::LX,
+            _ => formats::Format::DOS
+        };
+    }
+
+    if data.starts_with(&elf::ELF_MAGIC) {
+        return formats::Format::ELF;
+    }
+
+    if let Ok(magic) = read_le_u32(data, 0) {
+        if macho::MachOEncoding::from_magic(magic).is_some() {
+            return formats::Format::MachO;
+        }
+    }
+
+    match (read_be_u32(data, 0), read_be_u32(data, 4)) {
+        (Ok(macho::fat::FAT_MAGIC), Ok(t)) |
+        (Ok(macho::fat::FAT_MAGIC_64), Ok(t))
+            if t <= macho::fat::FAT_MAX_ARCHES =>
+            return formats::Format::FAT,
+        _ => ()
+    }
+
+    match read_le_u16(data, 0) {
+        Ok(0) | Err(_) => formats::Format::Unknown,
+        Ok(machine) => match coff::CoffHeaderMachineType::from_value(machine) {
+            coff::CoffHeaderMachineType::Unknown => formats::Format::Unknown,
+            _ => formats::Format::COFF
+        }
+    }
+}
+
+/* a .COM file has nothing in it to identify it, so it is only inferred for
+ * a file also named like one; anything else needs -f COM */
+pub fn infer_format_from_name(path: &Path, data: &[u8]) -> formats::Format {
+    let is_com_name: bool = path.extension()
+        .is_some_and(|t| t.eq_ignore_ascii_case("com"));
+
+    match infer_format(data) {
+        formats::Format::Unknown if is_com_name && com::is_com(data) =>
+            formats::Format::COM,
+        t => t
+    }
 }
 
 pub fn get_file_as(data: &[u8], format: formats::Format) ->
-    Result<formats::File, errors::ButylError> {
+    Result<formats::File<'_>, errors::ButylError> {
     Ok(match format {
-        DOS => formats::File::DOS(dos::DosFile::from_le_bytes(data)?),
-        COFF => formats::File::COFF(coff::CoffFile::from_le_bytes(data)?),
+        formats::Format::DOS =>
+            formats::File::DOS(dos::DosFile::from_le_bytes(data)?),
+        formats::Format::COM =>
+            formats::File::COM(com::ComFile::from_le_bytes(data)?),
+        formats::Format::COFF =>
+            formats::File::COFF(coff::CoffFile::from_le_bytes(data)?),
+        formats::Format::PE =>
+            formats::File::PE(Box::new(pe::PeFile::from_le_bytes(data)?)),
+        formats::Format::NE =>
+            formats::File::NE(ne::NeFile::from_le_bytes(data)?),
+        formats::Format::LE =>
+            formats::File::LE(le::LeFile::from_le_bytes(data)?),
+        formats::Format::LX =>
+            formats::File::LX(le::LeFile::from_le_bytes(data)?),
+        formats::Format::ELF =>
+            formats::File::ELF(elf::ElfFile::from_bytes(data)?),
+        formats::Format::MachO =>
+            formats::File::MachO(macho::MachOFile::from_bytes(data)?),
+        formats::Format::FAT =>
+            formats::File::FAT(macho::fat::FatFile::from_bytes(data)?),
         _ => formats::File::Unknown(())
     })
 }
 
+pub fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, errors::ButylError> {
+    match bytes.get(offset) {
+        Some(t) => Ok(*t),
+        None => Err(errors::ButylError::InsufficientDataError)
+    }
+}
+
+pub fn read_le_u16(bytes: &[u8], offset: usize) ->
+    Result<u16, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 2)?;
+    Ok(((raw[1] as u16) << 8) | raw[0] as u16)
+}
+
+pub fn read_le_u32(bytes: &[u8], offset: usize) ->
+    Result<u32, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 4)?;
+    Ok(((raw[3] as u32) << 24) |
+        ((raw[2] as u32) << 16) |
+        ((raw[1] as u32) << 8) |
+        raw[0] as u32)
+}
+
+pub fn read_le_u64(bytes: &[u8], offset: usize) ->
+    Result<u64, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 8)?;
+    Ok(((read_le_u32(raw, 4)? as u64) << 32) | read_le_u32(raw, 0)? as u64)
+}
+
+pub fn read_be_u16(bytes: &[u8], offset: usize) ->
+    Result<u16, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 2)?;
+    Ok(((raw[0] as u16) << 8) | raw[1] as u16)
+}
+
+pub fn read_be_u32(bytes: &[u8], offset: usize) ->
+    Result<u32, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 4)?;
+    Ok(((raw[0] as u32) << 24) |
+        ((raw[1] as u32) << 16) |
+        ((raw[2] as u32) << 8) |
+        raw[3] as u32)
+}
+
+pub fn read_be_u64(bytes: &[u8], offset: usize) ->
+    Result<u64, errors::ButylError> {
+    let raw: &[u8] = read_bytes(bytes, offset, 8)?;
+    Ok(((read_be_u32(raw, 0)? as u64) << 32) | read_be_u32(raw, 4)? as u64)
+}
+
+pub fn read_bytes(bytes: &[u8], offset: usize, len: usize) ->
+    Result<&[u8], errors::ButylError> {
+    match offset.checked_add(len) {
+        Some(end) if end <= bytes.len() => Ok(&bytes[offset..end]),
+        _ => Err(errors::ButylError::InsufficientDataError)
+    }
+}
+
+pub fn read_cstring(bytes: &[u8], offset: usize) ->
+    Result<String, errors::ButylError> {
+    if offset >= bytes.len() {
+        return Err(errors::ButylError::InsufficientDataError);
+    }
+
+    match bytes[offset..].iter().position(|b| *b == 0) {
+        Some(len) => Ok(String::from_utf8_lossy(
+            &bytes[offset..offset + len]).into_owned()),
+        None => Err(errors::ButylError::MalformedDataError)
+    }
+}
+
+/* a string prefixed by its length in a single byte, as in the NE and LE
+ * name tables */
+pub fn read_pascal_string(bytes: &[u8], offset: usize) ->
+    Result<String, errors::ButylError> {
+    let len: usize = read_u8(bytes, offset)? as usize;
+    let raw: &[u8] = read_bytes(bytes, offset + 1, len)?;
+
+    Ok(String::from_utf8_lossy(raw).into_owned())
+}
+
+pub fn write_le_u16(bytes: &mut [u8], offset: usize, value: u16) ->
+    Result<(), errors::ButylError> {
+    write_bytes(bytes, offset, &value.to_le_bytes())
+}
+
+pub fn write_le_u32(bytes: &mut [u8], offset: usize, value: u32) ->
+    Result<(), errors::ButylError> {
+    write_bytes(bytes, offset, &value.to_le_bytes())
+}
+
+pub fn write_le_u64(bytes: &mut [u8], offset: usize, value: u64) ->
+    Result<(), errors::ButylError> {
+    write_bytes(bytes, offset, &value.to_le_bytes())
+}
+
+pub fn write_bytes(bytes: &mut [u8], offset: usize, value: &[u8]) ->
+    Result<(), errors::ButylError> {
+    match offset.checked_add(value.len()) {
+        Some(end) if end <= bytes.len() => {
+            bytes[offset..end].copy_from_slice(value);
+            Ok(())
+        },
+        _ => Err(errors::ButylError::InsufficientDataError)
+    }
+}
+
+/* accepts hexadecimal with a 0x prefix, or decimal */
+pub fn parse_address(string: &str) -> Result<u64, std::num::ParseIntError> {
+    match string.strip_prefix("0x").or_else(|| string.strip_prefix("0X")) {
+        Some(t) => u64::from_str_radix(t, 16),
+        None => string.parse::<u64>()
+    }
+}
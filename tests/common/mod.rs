@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+pub fn fixture(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name].iter().collect()
+}
+
+pub fn run(args: &[&str], name: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_butyl"))
+        .arg(fixture(name))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/* stdout of a run that is expected to succeed */
+pub fn show(args: &[&str], name: &str) -> String {
+    let output: Output = run(args, name);
+
+    assert!(output.status.success(), "{}",
+        String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+pub fn has_line(text: &str, line: &str) -> bool {
+    text.lines().any(|t| t.trim_end() == line)
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+
+const LE: &str = "le/test.le";
+const LX: &str = "le/test.lx";
+
+#[test]
+fn header() {
+    let text: String = show(&["-s", "header"], LX);
+
+    assert!(has_line(&text, "Signature: LX"));
+    assert!(has_line(&text, "Module type: Library"));
+    assert!(has_line(&text, "Entry point: 1:00000010"));
+    assert!(has_line(&text, "Pages: 3 of 0x100 bytes"));
+    assert!(has_line(&text, "Page offset shift: 8"));
+
+    let text: String = show(&["-s", "header"], LE);
+
+    assert!(has_line(&text, "Signature: LE"));
+    assert!(has_line(&text, "Last page size: 0x80"));
+}
+
+#[test]
+fn objects() {
+    /* LX pages are placed by their own offsets, LE pages follow one another
+     * with only the last one short */
+    assert_eq!(show(&["-s", "objects"], LX),
+        "  1: 0x00010000 0x00001000 r-x BIG\n        \
+         1 0x00000400 0x0100 LEGAL\n  \
+         2: 0x00020000 0x00002000 rw- BIG\n        \
+         2 0x00000500 0x0080 LEGAL\n        \
+         3 0x00000400 0x0000 ZEROFILL\n");
+    assert_eq!(show(&["-s", "objects"], LE),
+        "  1: 0x00010000 0x00001000 r-x BIG\n        \
+         1 0x00000400 0x0100 LEGAL\n  \
+         2: 0x00020000 0x00002000 rw- BIG\n        \
+         2 0x00000500 0x0100 LEGAL\n        \
+         3 0x00000600 0x0080 ZEROFILL\n");
+}
+
+#[test]
+fn fixups() {
+    /* internal, by ordinal, by name with a source list, through the entry
+     * table with an additive, and a 16-bit selector */
+    let fixups: &str = "Page 1 contains 4 fixups:\n  \
+        OFF32    0004 object 2:00000100\n  \
+        OFF32    0010 DOSCALLS.282\n  \
+        OFF32    0020,0030 FOO.DosExit\n  \
+        REL32    -002 entry 1 + 0x10\n\
+        Page 2 contains 1 fixups:\n  \
+        SEL16    0008 object 1:00000000\n";
+
+    assert_eq!(show(&["-s", "fixups"], LX), fixups);
+    assert_eq!(show(&["-s", "fixups"], LE), fixups);
+}
+
+#[test]
+fn entries() {
+    /* an empty bundle skips ordinal 3, then a forwarder bundle imports by
+     * name and by ordinal; LE has no forwarders */
+    let text: String = show(&["-s", "entries"], LX);
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+
+    assert_eq!(lines, [
+        "    1 ENTRY32   1:00000010 0x01 Entry",
+        "    2 ENTRY32   1:00000020 0x01 Hidden",
+        "    4 FORWARDER DOSCALLS.DosExit 0x00",
+        "    5 FORWARDER FOO.282 0x01"
+    ]);
+    assert_eq!(show(&["-s", "entries"], LE).lines().count(), 2);
+}
+
+#[test]
+fn names() {
+    let names: &str = "Module: TEST\nDescription: desc\n\
+        Resident names:\n      1 Entry\n\
+        Non-resident names:\n      2 Hidden\n";
+
+    assert_eq!(show(&["-s", "names"], LX), names);
+    assert_eq!(show(&["-s", "names"], LE), names);
+    assert_eq!(show(&["-s", "modules"], LX), "  1: DOSCALLS\n  2: FOO\n");
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+
+const DYLIB: &str = "macho/libsetuid.dylib";
+const BUNDLE: &str = "macho/i386.bundle";
+const FAT: &str = "macho/fat.bundle";
+const EXECUTABLE: &str = "macho/launcher_arm64";
+
+#[test]
+fn header() {
+    assert_eq!(show(&["-s", "header"], DYLIB),
+        "Magic: 0xfeedfacf (64-bit, little endian)\n\
+         CPU type: x86_64 (0x1000007, subtype 0x3)\n\
+         File type: DYLIB\n\
+         Load commands: 14 (1360 bytes)\n\
+         Flags: 0x100085 NOUNDEFS DYLDLINK TWOLEVEL NO_REEXPORTED_DYLIBS\n");
+    assert_eq!(show(&["-s", "header"], BUNDLE),
+        "Magic: 0xfeedface (32-bit, little endian)\n\
+         CPU type: i386 (0x7, subtype 0x3)\n\
+         File type: BUNDLE\n\
+         Load commands: 13 (1020 bytes)\n\
+         Flags: 0x85 NOUNDEFS DYLDLINK TWOLEVEL\n");
+}
+
+#[test]
+fn commands() {
+    let text: String = show(&["-s", "commands"], DYLIB);
+
+    assert_eq!(text.lines().count(), 14);
+    assert!(has_line(&text, "  0: LC_SEGMENT_64                \
+        0x000020   472 __TEXT (5 sections)"));
+    assert!(has_line(&text, "  6: LC_SYMTAB                    \
+        0x000468    24 symoff 0x52a0 nsyms 32 stroff 0x5508 strsize 0x328"));
+    assert!(has_line(&text, " 11: LC_LOAD_DYLIB                \
+        0x000518    56 /usr/lib/libSystem.B.dylib"));
+
+    let text: String = show(&["-s", "commands"], BUNDLE);
+
+    assert!(has_line(&text, "  0: LC_SEGMENT                   \
+        0x00001c   396 __TEXT (5 sections)"));
+    assert!(has_line(&text, " 12: LC_DYLIB_CODE_SIGN_DRS       \
+        0x000408    16 dataoff 0x2130 datasize 0x3c"));
+}
+
+#[test]
+fn entry_point_and_code_signature() {
+    let text: String = show(&["-s", "commands"], EXECUTABLE);
+
+    /* entryoff 31828 and dataoff 66304 datasize 1312 per llvm-objdump */
+    assert!(has_line(&text, " 12: LC_MAIN                      \
+        0x000508    24 entryoff 0x7c54 stacksize 0x0"));
+    assert!(has_line(&text, " 17: LC_CODE_SIGNATURE            \
+        0x000590    16 dataoff 0x10300 datasize 0x520"));
+}
+
+#[test]
+fn segments() {
+    let text: String = show(&["-s", "segments"], DYLIB);
+
+    assert!(has_line(&text, "__TEXT           0x0000000000000000 \
+        0x0000000000003000 0x00000000 0x00003000 r-x/r-x"));
+    assert!(has_line(&text, "    __text               0x00000000000014a0 \
+        0x0000000000001599 0x000014a0 2^4 0x80000400"));
+    assert!(has_line(&text, "__LINKEDIT       0x0000000000005000 \
+        0x0000000000001000 0x00005000 0x00000830 r--/r--"));
+
+    let text: String = show(&["-s", "segments"], BUNDLE);
+
+    assert!(has_line(&text, "__DATA           0x00001000 0x00001000 \
+        0x00001000 0x00001000 rw-/rwx"));
+    assert!(has_line(&text, "    __nl_symbol_ptr      0x00001000 \
+        0x0000000c 0x00001000 2^2 0x6"));
+}
+
+#[test]
+fn libraries() {
+    assert_eq!(show(&["-s", "libraries"], DYLIB),
+        "/Users/jesse/src/jetty/jetty.toolchain/jetty-setuid/\
+         libsetuid-osx/target/libsetuid-osx.so (compatibility version \
+         0.0.0, current version 0.0.0) [LC_ID_DYLIB]\n\
+         /usr/lib/libSystem.B.dylib (compatibility version 1.0.0, \
+         current version 1281.0.0)\n");
+    assert_eq!(show(&["-s", "libraries"], BUNDLE),
+        "/usr/lib/libSystem.B.dylib (compatibility version 1.0.0, \
+         current version 169.3.0)\n");
+}
+
+#[test]
+fn uuid() {
+    assert_eq!(show(&["-s", "uuid"], DYLIB),
+        "58E93FC9-0A70-3FFC-871E-EF79D4070E14\n");
+    assert_eq!(show(&["-s", "uuid"], BUNDLE),
+        "D28A0666-0900-3FCF-B6CD-49810771778D\n");
+}
+
+#[test]
+fn build() {
+    assert_eq!(show(&["-s", "build"], DYLIB),
+        "Platform: MACOS\nMinimum OS: 10.15.0\nSDK: 10.15.0\n\
+         Tool: LD 530.0.0\n");
+    assert_eq!(show(&["-s", "build"], EXECUTABLE),
+        "Platform: MACOS\nMinimum OS: 11.0.0\nSDK: 11.0.0\n\
+         Tool: LD 530.0.0\n");
+}
+
+#[test]
+fn arches() {
+    assert_eq!(show(&["-s", "arches"], FAT),
+        "i386       0x00001000 0x0000271c 2^12 BUNDLE\n\
+         x86_64     0x00004000 0x00003834 2^12 BUNDLE\n");
+    assert!(!run(&["-s", "arches"], DYLIB).status.success());
+}
+
+#[test]
+fn fat_slices() {
+    /* the first slice unless --arch picks another */
+    assert_eq!(show(&["-s", "uuid"], FAT),
+        "D28A0666-0900-3FCF-B6CD-49810771778D\n");
+    assert_eq!(show(&["--arch", "x86_64", "-s", "uuid"], FAT),
+        "A4D6453E-4968-39D5-8FE9-EDC14643E281\n");
+    assert_eq!(show(&["-s", "header"], FAT), show(&["-s", "header"], BUNDLE));
+    assert!(!run(&["--arch", "ppc", "-s", "uuid"], FAT).status.success());
+}
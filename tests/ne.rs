@@ -0,0 +1,56 @@
+mod common;
+
+use common::*;
+
+const PROGRAM: &str = "ne/test.exe";
+
+#[test]
+fn header() {
+    let text: String = show(&["-s", "header"], PROGRAM);
+
+    assert!(has_line(&text, "Target OS: Windows"));
+    assert!(has_line(&text, "Flags: 0x0302 MULTIPLEDATA WINAPI"));
+    assert!(has_line(&text, "Entry point: 1:0010"));
+    assert!(has_line(&text, "Initial stack: 2:0000"));
+    assert!(has_line(&text, "Segments: 4"));
+    assert!(has_line(&text, "Alignment: 16 bytes"));
+}
+
+#[test]
+fn segments() {
+    /* a zero length or allocation stands for 64K, but a segment with no
+     * sector has no file data at all */
+    assert_eq!(show(&["-s", "segments"], PROGRAM),
+        "  1: 0x000200 0x00020 0x00020 CODE MOVEABLE PRELOAD RELOCINFO\n  \
+         2: 0x000260 0x00010 0x00100 DATA\n  \
+         3: 0x0002a0 0x10000 0x10000 DATA\n  \
+         4: 0x000000 0x00000 0x10000 DATA\n");
+}
+
+#[test]
+fn names() {
+    assert_eq!(show(&["-s", "names"], PROGRAM),
+        "Module: TEST\nDescription: Test module\n\
+         Resident names:\n      1 WINMAIN\n\
+         Non-resident names:\n      2 EXPORTX\n");
+    assert_eq!(show(&["-s", "modules"], PROGRAM), "  1: KERNEL\n  2: USER\n");
+}
+
+#[test]
+fn relocations() {
+    /* by ordinal, by name, internal to a fixed segment and through the
+     * entry table */
+    assert_eq!(show(&["-s", "relocations"], PROGRAM),
+        "Segment 1 contains 4 relocations:\n  \
+         0002 FARPTR    KERNEL.91\n  \
+         0008 SELECTOR  USER.MESSAGEBOX\n  \
+         0010 OFFSET    segment 2:0004\n  \
+         0014 FARPTR    entry 1 (additive)\n");
+}
+
+#[test]
+fn resources() {
+    assert_eq!(show(&["resources"], PROGRAM),
+        "10 (RCDATA)/1  0x00000280  16 bytes\n\
+         \"MYTYPE\"/\"HELLO\"  0x00000290  16 bytes\n");
+}